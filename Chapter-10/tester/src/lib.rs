@@ -0,0 +1,181 @@
+use std::cmp::Ordering;
+
+// whichever's longer, or `y` if they're the same length. it could be either, so they both have to
+// last as long as what comes back is used
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+// the same for slices of anything, which only need counting and not looking inside
+pub fn longest_slice<'a, T>(x: &'a [T], y: &'a [T]) -> &'a [T] {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+// anything with a length
+pub trait HasLen {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl HasLen for str {
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+}
+
+impl HasLen for String {
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+}
+
+impl<T> HasLen for [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+impl<T> HasLen for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+impl<T, const N: usize> HasLen for [T; N] {
+    fn len(&self) -> usize {
+        N
+    }
+}
+
+// and for all of those. `?Sized` so that str and slices can be compared without wrapping them
+pub fn longest_by_len<'a, T: HasLen + ?Sized>(x: &'a T, y: &'a T) -> &'a T {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+// whichever `key` makes more of, or `y` on a tie like the rest. the key's given the references
+// with their own lifetime, so it can be borrowed from them, like a field, rather than copied out
+pub fn longest_by_key<'a, T, K, F>(x: &'a T, y: &'a T, mut key: F) -> &'a T
+where
+    T: ?Sized,
+    K: Ord,
+    F: FnMut(&'a T) -> K,
+{
+    if key(x) > key(y) {
+        x
+    } else {
+        y
+    }
+}
+
+// `x` if `cmp` says it's greater, and otherwise `y`
+pub fn max_by_cmp<'a, T, F>(x: &'a T, y: &'a T, cmp: F) -> &'a T
+where
+    T: ?Sized,
+    F: FnOnce(&T, &T) -> Ordering,
+{
+    match cmp(x, y) {
+        Ordering::Greater => x,
+        Ordering::Less | Ordering::Equal => y,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings() {
+        assert_eq!("Hello", longest("Hello", "Hell"));
+        // a tie goes to the second
+        assert_eq!("two", longest("one", "two"));
+        let a = String::from("Hello");
+        let b = String::from("Hello, world");
+        assert_eq!(&b, longest_by_len(&a, &b));
+        assert_eq!("abc", longest_by_len("abc", ""));
+    }
+
+    #[test]
+    fn slices() {
+        let v = vec![1, 2, 3];
+        let a = [4, 5];
+        assert_eq!(&[1, 2, 3], longest_slice(&v, &a));
+        assert_eq!(&[4, 5], longest_slice(&v[..1], &a));
+        let empty: [char; 0] = [];
+        assert_eq!(&['x'], longest_slice(&empty, &['x']));
+    }
+
+    #[test]
+    fn by_len() {
+        assert_eq!(&vec![1, 2], longest_by_len(&vec![1, 2], &vec![3]));
+        assert_eq!(&[1; 3], longest_by_len(&[0; 3], &[1; 3]));
+        assert_eq!([2, 3].as_slice(), longest_by_len([1].as_slice(), &[2, 3]));
+        assert!(HasLen::is_empty(&Vec::<u8>::new()));
+        assert!(!HasLen::is_empty("x"));
+    }
+
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    #[test]
+    fn by_key() {
+        // more bytes, but fewer characters
+        assert_eq!("héé", longest("héé", "abcd"));
+        assert_eq!("abcd", longest_by_key("héé", "abcd", |s| s.chars().count()));
+        let ann = Person {
+            name: String::from("Ann"),
+            age: 40,
+        };
+        let bob = Person {
+            name: String::from("Bob"),
+            age: 30,
+        };
+        assert_eq!("Ann", longest_by_key(&ann, &bob, |p| p.age).name);
+        // a key borrowed from the people themselves
+        assert_eq!("Bob", longest_by_key(&ann, &bob, |p| p.name.as_str()).name);
+    }
+
+    #[test]
+    fn by_cmp() {
+        assert_eq!(&5, max_by_cmp(&5, &3, Ord::cmp));
+        // the other way round picks the smaller one
+        assert_eq!(&3, max_by_cmp(&5, &3, |x, y| y.cmp(x)));
+        // equal, ignoring case, so the second
+        let cmp = |x: &str, y: &str| x.to_lowercase().cmp(&y.to_lowercase());
+        assert_eq!("HELLO", max_by_cmp("hello", "HELLO", cmp));
+        assert_eq!("b", max_by_cmp("b", "A", cmp));
+        // floats aren't Ord, but a comparator can still put them in order
+        let first = |x: &[f64], y: &[f64]| x[0].total_cmp(&y[0]);
+        assert_eq!(&[1.5, 0.0], max_by_cmp(&[1.5, 0.0][..], &[1.0, 2.0], first));
+    }
+
+    #[test]
+    fn lifetimes() {
+        let a = String::from("long string is long");
+        let result;
+        {
+            // this one could be `b`, so it can't be kept past the end of the block
+            let b = String::from("xyz");
+            assert_eq!("long string is long", longest(&a, &b));
+            // but neither of these end here, so this one can
+            result = longest_by_len(a.as_str(), "static");
+        }
+        assert_eq!("long string is long", result);
+    }
+}
@@ -1,17 +1,15 @@
+use tester::{longest, longest_by_key, longest_by_len, longest_slice};
+
 fn main() {
     let a = String::from("Hello");
-    let res;
     {
         let b = String::from("Hell");
-        res = longest(&a, &b);
+        let res = longest(&a, &b);
+        // fine in here, but not after the block: `b` has gone by then, and `res` could be `b`
+        println!("{res}");
     }
-    // println!("{res}");
-}
 
-fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
-    if x.len() > y.len() {
-        x
-    } else {
-        y
-    }
+    println!("{:?}", longest_slice(&[1, 2, 3], &[4, 5]));
+    println!("{:?}", longest_by_len(&vec!['a'], &vec!['b', 'c']));
+    println!("{}", longest_by_key("héé", "abcd", |s| s.chars().count()));
 }
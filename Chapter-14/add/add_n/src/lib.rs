@@ -0,0 +1,85 @@
+use math_core::{Integer, MathError, Transform};
+
+/// # Example
+/// ```rust
+/// use add_n::add_n;
+/// assert_eq!(42, add_n(40, 2));
+/// assert_eq!(-1, add_n(1, -2));
+/// ```
+pub fn add_n(x: i32, n: i32) -> i32 {
+    x + n
+}
+
+/// [`add_n`] for any integer, or `None` if the answer doesn't fit in one.
+///
+/// # Example
+/// ```rust
+/// use add_n::checked_add_n;
+/// assert_eq!(Some(42u8), checked_add_n(40, 2));
+/// assert_eq!(None, checked_add_n(250u8, 10));
+/// ```
+pub fn checked_add_n<T: Integer>(x: T, n: T) -> Option<T> {
+    x.checked_add(n)
+}
+
+/// `add_n` with `n` filled in ahead of time, to hand to anything that wants
+/// a function of one number, like a `Pipeline`.
+///
+/// # Example
+/// ```rust
+/// use add_n::adder;
+/// let add_ten = adder(10);
+/// assert_eq!(42, add_ten(32));
+/// assert_eq!(vec![11, 12, 13], [1, 2, 3].map(adder(10)).to_vec());
+/// ```
+pub fn adder(n: i32) -> impl Fn(i32) -> i32 {
+    move |x| add_n(x, n)
+}
+
+/// [`add_n`] with `n` filled in, as a [`Transform`] that knows how to check
+/// for overflow, which an [`adder`] can't.
+///
+/// # Example
+/// ```rust
+/// use add_n::AddN;
+/// use math_core::{MathError, Transform};
+/// assert_eq!(42, AddN(10).apply(32));
+/// assert_eq!(Err(MathError::Overflow), AddN(1).checked_apply(i32::MAX));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddN(pub i32);
+
+impl Transform for AddN {
+    fn apply(&self, x: i32) -> i32 {
+        add_n(x, self.0)
+    }
+
+    fn checked_apply(&self, x: i32) -> Result<i32, MathError> {
+        checked_add_n(x, self.0).ok_or(MathError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_n() {
+        assert_eq!(42, add_n(41, 1));
+        assert_eq!(42, add_n(42, 0));
+    }
+
+    #[test]
+    fn test_checked_add_n() {
+        assert_eq!(Some(42), checked_add_n(41, 1));
+        assert_eq!(Some(i64::MIN), checked_add_n(i64::MIN + 5, -5));
+        assert_eq!(None, checked_add_n(i64::MIN, -1));
+    }
+
+    #[test]
+    fn test_adder() {
+        let add_two = adder(2);
+        assert_eq!(42, add_two(40));
+        assert_eq!(add_n(7, 2), add_two(7));
+    }
+}
@@ -1,11 +1,112 @@
+pub use math_core::{Integer, One};
+use math_core::{MathError, Transform};
+use std::{iter::Map, ops::Add};
+
 /// # Example
 /// ```rust
 /// use add_one::add_one;
 /// let num = 41;
 /// assert_eq!(42, add_one(num));
+/// assert_eq!(2.5, add_one(1.5f64));
+/// assert_eq!(2u8, add_one(1u8));
+/// assert_eq!(2i128, add_one(1i128));
+/// ```
+///
+/// # Panics
+/// Adding one to an integer's largest value panics in a debug build, and wraps
+/// around to the smallest in a release build. [`checked_add_one`],
+/// [`saturating_add_one`], and [`wrapping_add_one`] do the same thing either
+/// way.
+pub fn add_one<T: One + Add<Output = T>>(x: T) -> T {
+    x + T::ONE
+}
+
+/// # Example
+/// ```rust
+/// use add_one::checked_add_one;
+/// assert_eq!(Some(42), checked_add_one(41));
+/// assert_eq!(None, checked_add_one(u8::MAX));
 /// ```
-pub fn add_one(x: i32) -> i32 {
-    x + 1
+pub fn checked_add_one<T: Integer>(x: T) -> Option<T> {
+    x.checked_add(T::ONE)
+}
+
+/// # Example
+/// ```rust
+/// use add_one::saturating_add_one;
+/// assert_eq!(42, saturating_add_one(41));
+/// assert_eq!(u8::MAX, saturating_add_one(u8::MAX));
+/// ```
+pub fn saturating_add_one<T: Integer>(x: T) -> T {
+    x.saturating_add(T::ONE)
+}
+
+/// # Example
+/// ```rust
+/// use add_one::wrapping_add_one;
+/// assert_eq!(42, wrapping_add_one(41));
+/// assert_eq!(i8::MIN, wrapping_add_one(i8::MAX));
+/// ```
+pub fn wrapping_add_one<T: Integer>(x: T) -> T {
+    x.wrapping_add(T::ONE)
+}
+
+/// Adds one to every number in `xs`, in place.
+///
+/// # Example
+/// ```rust
+/// use add_one::add_one_all;
+/// let mut nums = [1, 2, 41];
+/// add_one_all(&mut nums);
+/// assert_eq!([2, 3, 42], nums);
+/// ```
+pub fn add_one_all<T: One + Add<Output = T> + Copy>(xs: &mut [T]) {
+    for x in xs {
+        *x = add_one(*x);
+    }
+}
+
+/// What [`AddOneIterator::map_add_one`] turns `I` into.
+pub type MapAddOne<I> = Map<I, fn(<I as Iterator>::Item) -> <I as Iterator>::Item>;
+
+/// `map_add_one` on any iterator of numbers.
+///
+/// # Example
+/// ```rust
+/// use add_one::AddOneIterator;
+/// let nums: Vec<u8> = [1, 2, 41].into_iter().map_add_one().collect();
+/// assert_eq!(vec![2, 3, 42], nums);
+/// ```
+pub trait AddOneIterator: Iterator + Sized
+where
+    Self::Item: One + Add<Output = Self::Item>,
+{
+    fn map_add_one(self) -> MapAddOne<Self> {
+        self.map(add_one)
+    }
+}
+
+impl<I: Iterator> AddOneIterator for I where I::Item: One + Add<Output = I::Item> {}
+
+/// [`add_one`] as a [`Transform`].
+///
+/// # Example
+/// ```rust
+/// use add_one::AddOne;
+/// use math_core::Transform;
+/// assert_eq!(42, AddOne.apply(41));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddOne;
+
+impl Transform for AddOne {
+    fn apply(&self, x: i32) -> i32 {
+        add_one(x)
+    }
+
+    fn checked_apply(&self, x: i32) -> Result<i32, MathError> {
+        checked_add_one(x).ok_or(MathError::Overflow)
+    }
 }
 
 #[cfg(test)]
@@ -16,4 +117,52 @@ mod tests {
     fn test_add_one() {
         assert_eq!(42, add_one(41));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_add_one_types() {
+        assert_eq!(0, add_one(-1i64));
+        assert_eq!(u128::MAX, add_one(u128::MAX - 1));
+        assert_eq!(1.25f32, add_one(0.25f32));
+    }
+
+    #[test]
+    fn test_add_one_all() {
+        let mut empty: [i32; 0] = [];
+        add_one_all(&mut empty);
+        assert_eq!([0; 0], empty);
+
+        let mut big: Vec<i64> = (0..100_000).collect();
+        add_one_all(&mut big);
+        assert!(big.iter().copied().eq(1..100_001));
+    }
+
+    #[test]
+    fn test_map_add_one() {
+        assert_eq!(None, std::iter::empty::<i32>().map_add_one().next());
+        assert_eq!(5_000_050_000u64, (0..100_000u64).map_add_one().sum());
+        assert_eq!(
+            vec![1.5],
+            vec![0.5].into_iter().map_add_one().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_add_one_floats() {
+        use math_core::approx_eq;
+
+        // 0.7 + 0.1 is a little under 0.8, and so is this under 1.8
+        assert_ne!(1.8, add_one(0.7 + 0.1));
+        assert!(approx_eq(1.8, add_one(0.7 + 0.1), 1e-12));
+        assert!(approx_eq(1e15 + 1.0, add_one(1e15), 1e-12));
+        assert!(add_one(f64::NAN).is_nan());
+        assert_eq!(f64::INFINITY, add_one(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(None, checked_add_one(i32::MAX));
+        assert_eq!(i32::MAX, saturating_add_one(i32::MAX));
+        assert_eq!(i32::MIN, wrapping_add_one(i32::MAX));
+        assert_eq!(0usize, wrapping_add_one(usize::MAX));
+    }
+}
@@ -1,13 +1,40 @@
-use add_one::add_one;
-use times_two::times_two;
-
+use add::{add_one, adder, times_two, AddOne, Pipeline, TimesTwo, Transform};
 
 fn main() {
     println!("Hello, world!");
 
     let num1 = 41;
-    println!("\n{num1} + 1 = {}. The answer to the universe and everything.", add_one(num1));
-    
+    println!(
+        "\n{num1} + 1 = {}. The answer to the universe and everything.",
+        add_one(num1)
+    );
+
     let num2 = 21;
     println!("\nWhat is 21 * 2? It is also ... {}!!!", times_two(num2));
+
+    // all three, one after the other: (19 + 1) * 2 + 2
+    let add_two = adder(2);
+    let num3 = 19;
+    println!(
+        "\n({num3} + 1) * 2 + 2 = {}. Still the answer.",
+        add_two(times_two(add_one(num3)))
+    );
+
+    // the same sort of thing, put together from names while it runs
+    let steps = ["add1", "x2"];
+    let pipeline: Pipeline = steps.iter().filter_map(|name| step(name)).collect();
+    let num4 = 20;
+    println!(
+        "\n{} with {num4} is {}. It's always the answer.",
+        steps.join(" then "),
+        pipeline.apply(num4)
+    );
+}
+
+fn step(name: &str) -> Option<Box<dyn Transform>> {
+    match name {
+        "add1" => Some(Box::new(AddOne)),
+        "x2" => Some(Box::new(TimesTwo)),
+        _ => None,
+    }
 }
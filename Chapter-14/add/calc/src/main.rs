@@ -0,0 +1,92 @@
+use add::{parse_number, AddN, AddOne, MathError, Pipeline, TimesTwo, Transform};
+use std::{env, fmt, io, process};
+
+const USAGE: &str = "usage: calc <step>... [number]
+steps: add1, x2, or add<n> (like add5 or add-3), done in order
+with no number it reads one from stdin, so calcs chain: calc x2 21 | calc add1";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(answer) => println!("{answer}"),
+        // the steps were fine, the number just got too big for them
+        Err(e @ CalcError::Math(MathError::Overflow)) => {
+            eprintln!("calc: {e}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("calc: {e}\n{USAGE}");
+            process::exit(2);
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CalcError {
+    NothingToDo,
+    NoSuchStep(String),
+    CouldntRead(io::Error),
+    Math(MathError),
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalcError::NothingToDo => write!(f, "there's nothing to do"),
+            CalcError::NoSuchStep(name) => write!(f, "there's no '{name}' step"),
+            CalcError::CouldntRead(e) => write!(f, "couldn't read a number: {e}"),
+            CalcError::Math(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<MathError> for CalcError {
+    fn from(e: MathError) -> CalcError {
+        CalcError::Math(e)
+    }
+}
+
+fn run(args: &[String]) -> Result<i32, CalcError> {
+    // the number is last, when there is one; one too big for an i32 is still a number, not a step
+    let number = match args.last().map(|last| parse_number(last)) {
+        Some(Ok(x)) => Some(x),
+        Some(Err(MathError::Overflow)) => return Err(MathError::Overflow.into()),
+        _ => None,
+    };
+    let steps = match number {
+        Some(_) => &args[..args.len() - 1],
+        None => args,
+    };
+    if steps.is_empty() {
+        return Err(CalcError::NothingToDo);
+    }
+    // all the steps have to make sense before it's worth waiting on stdin
+    let pipeline = steps
+        .iter()
+        .map(|name| step(name))
+        .collect::<Result<Pipeline, CalcError>>()?;
+    let x = match number {
+        Some(x) => x,
+        None => read_number()?,
+    };
+    Ok(pipeline.checked_apply(x)?)
+}
+
+fn step(name: &str) -> Result<Box<dyn Transform>, CalcError> {
+    match name {
+        "add1" => Ok(Box::new(AddOne)),
+        "x2" => Ok(Box::new(TimesTwo)),
+        _ => match name.strip_prefix("add").map(str::parse) {
+            Some(Ok(n)) => Ok(Box::new(AddN(n))),
+            _ => Err(CalcError::NoSuchStep(name.to_string())),
+        },
+    }
+}
+
+fn read_number() -> Result<i32, CalcError> {
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .map_err(CalcError::CouldntRead)?;
+    Ok(parse_number(&line)?)
+}
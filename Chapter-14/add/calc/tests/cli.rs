@@ -0,0 +1,118 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+fn calc(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_calc"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn answer(args: &[&str], stdin: &str) -> String {
+    let output = calc(args, stdin);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn one_step() {
+    assert_eq!("42\n", answer(&["add1", "41"], ""));
+    assert_eq!("42\n", answer(&["x2", "21"], ""));
+    assert_eq!("42\n", answer(&["add-3", "45"], ""));
+}
+
+#[test]
+fn steps_in_order() {
+    assert_eq!("42\n", answer(&["add1", "x2", "20"], ""));
+    assert_eq!("41\n", answer(&["x2", "add1", "20"], ""));
+}
+
+#[test]
+fn chained() {
+    // calc x2 21 | calc add1
+    let doubled = answer(&["x2", "21"], "");
+    assert_eq!("43\n", answer(&["add1"], &doubled));
+}
+
+#[test]
+fn overflow() {
+    let output = calc(&["x2", "2000000000"], "");
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "calc: the number doesn't fit in the number type\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // numbers that are too big to start with, as an argument or on stdin
+    for (args, stdin) in [
+        (&["3000000000"][..], "5\n"),
+        (&["add1"][..], "99999999999\n"),
+    ] {
+        let output = calc(args, stdin);
+        assert_eq!(Some(1), output.status.code(), "{:?}", args);
+        assert_eq!(
+            "calc: the number doesn't fit in the number type\n",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    assert_eq!(
+        Some(1),
+        calc(&["add1", &i32::MAX.to_string()], "").status.code()
+    );
+    assert_eq!(
+        Some(1),
+        calc(&["add-1", &i32::MIN.to_string()], "").status.code()
+    );
+}
+
+#[test]
+fn mistakes() {
+    for (args, stdin) in [
+        (&["times3", "1"][..], ""),
+        (&["add1"][..], "lots\n"),
+        (&["add1"][..], ""),
+        (&["41"][..], ""),
+        (&[][..], "41\n"),
+    ] {
+        let output = calc(args, stdin);
+        assert_eq!(Some(2), output.status.code(), "{:?}", args);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("usage:"));
+    }
+}
+
+#[test]
+fn bad_steps_dont_wait_for_stdin() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_calc"))
+        .arg("bogus")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    // keep stdin open, so reading it would wait forever
+    let _stdin = child.stdin.take();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if Instant::now() > deadline {
+            child.kill().unwrap();
+            panic!("calc waited on stdin");
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    assert_eq!(Some(2), status.code());
+}
@@ -0,0 +1,70 @@
+use std::{error::Error, fmt};
+
+/// What can go wrong doing sums with the workspace's crates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MathError {
+    /// A number is too big (or too small) to fit in the type it's meant to
+    /// be: an answer, or a number read from text.
+    Overflow,
+    /// Text that was meant to be a number, but isn't one.
+    NotANumber(String),
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MathError::Overflow => write!(f, "the number doesn't fit in the number type"),
+            MathError::NotANumber(text) => write!(f, "'{text}' isn't a number"),
+        }
+    }
+}
+
+impl Error for MathError {}
+
+/// Reads a number from `text`, leaving out any whitespace around it.
+///
+/// Text that's all digits (with maybe a sign in front) but doesn't fit in
+/// `T` is an [`Overflow`](MathError::Overflow) rather than not a number.
+///
+/// # Example
+/// ```rust
+/// use math_core::{parse_number, MathError};
+/// assert_eq!(Ok(42), parse_number::<i32>(" 42\n"));
+/// assert_eq!(Err(MathError::NotANumber("lots".to_string())), parse_number::<i32>("lots"));
+/// assert_eq!(Err(MathError::Overflow), parse_number::<i32>("3000000000"));
+/// ```
+pub fn parse_number<T: std::str::FromStr>(text: &str) -> Result<T, MathError> {
+    let text = text.trim();
+    text.parse().map_err(|_| {
+        let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            MathError::Overflow
+        } else {
+            MathError::NotANumber(text.to_string())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(Ok(-3i64), parse_number("-3"));
+        assert_eq!(Err(MathError::Overflow), parse_number::<i32>("3000000000"));
+        assert_eq!(Err(MathError::Overflow), parse_number::<u8>("-3"));
+        assert_eq!(
+            Err(MathError::NotANumber("-".to_string())),
+            parse_number::<i32>("-")
+        );
+        assert_eq!(
+            Err(MathError::NotANumber("3.5".to_string())),
+            parse_number::<i32>("3.5")
+        );
+        assert_eq!(
+            "'x' isn't a number",
+            parse_number::<u8>("x").unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,31 @@
+/// Whether `a` and `b` are the same, give or take rounding.
+///
+/// Floats can't hold most decimals exactly, so `0.1 + 0.2` comes out as
+/// `0.30000000000000004` and `==` says it isn't `0.3`. This allows them to be
+/// `tolerance` apart, as a fraction of the bigger of the two (or absolutely,
+/// near zero, where a fraction of almost nothing is no room at all).
+///
+/// # Example
+/// ```rust
+/// use math_core::approx_eq;
+/// assert_ne!(0.3, 0.1 + 0.2);
+/// assert!(approx_eq(0.3, 0.1 + 0.2, 1e-12));
+/// assert!(!approx_eq(0.3, 0.31, 1e-12));
+/// ```
+pub fn approx_eq(a: f64, b: f64, tolerance: f64) -> bool {
+    let scale = a.abs().max(b.abs()).max(1.0);
+    (a - b).abs() <= tolerance * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approx_eq() {
+        assert!(approx_eq(1e20, 1e20 + 1e5, 1e-12));
+        assert!(approx_eq(0.0, 1e-13, 1e-12));
+        assert!(!approx_eq(0.0, 1e-11, 1e-12));
+        assert!(!approx_eq(f64::NAN, f64::NAN, 1e-12));
+    }
+}
@@ -0,0 +1,13 @@
+//! What the workspace's crates share: the traits that make their functions
+//! generic, the [`Transform`] they all implement, the [`MathError`] for when
+//! a sum can't be done, and [`approx_eq`] for testing the float ones.
+
+mod error;
+mod float;
+mod number;
+mod transform;
+
+pub use error::{parse_number, MathError};
+pub use float::approx_eq;
+pub use number::{Integer, One};
+pub use transform::{Pipeline, Transform};
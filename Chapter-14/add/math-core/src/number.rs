@@ -0,0 +1,47 @@
+/// Numbers that have a one to add.
+///
+/// Implemented for every primitive integer and float type.
+pub trait One {
+    const ONE: Self;
+}
+
+macro_rules! impl_one {
+    ($one:literal: $($t:ty)*) => {
+        $(impl One for $t {
+            const ONE: Self = $one;
+        })*
+    };
+}
+
+impl_one!(1: u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+impl_one!(1.0: f32 f64);
+
+/// Integers, which run out of room at the top.
+///
+/// The methods are the integer types' own, so that functions like
+/// `checked_add_one` can be generic over them.
+pub trait Integer: One + Copy {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn wrapping_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty)*) => {
+        $(impl Integer for $t {
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn saturating_add(self, rhs: Self) -> Self {
+                <$t>::saturating_add(self, rhs)
+            }
+
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$t>::wrapping_add(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
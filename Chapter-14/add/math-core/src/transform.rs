@@ -0,0 +1,132 @@
+use crate::MathError;
+
+/// Something that turns one number into another.
+///
+/// The workspace's crates each have one, and any `Fn(i32) -> i32` is one too,
+/// so they can all go in the same [`Pipeline`].
+pub trait Transform {
+    fn apply(&self, x: i32) -> i32;
+
+    /// [`apply`](Transform::apply), but with [`MathError::Overflow`] when the
+    /// answer doesn't fit in an `i32`, rather than a panic or a wrapped-around
+    /// answer. The workspace's transforms all check; anything else is taken at
+    /// its word, since there's no telling what a closure does inside.
+    fn checked_apply(&self, x: i32) -> Result<i32, MathError> {
+        Ok(self.apply(x))
+    }
+}
+
+impl<F: Fn(i32) -> i32> Transform for F {
+    fn apply(&self, x: i32) -> i32 {
+        self(x)
+    }
+}
+
+/// Transforms one after the other, picked while the program runs.
+///
+/// # Example
+/// ```rust
+/// use math_core::{Pipeline, Transform};
+/// let pipeline = Pipeline::new().then(|x| x + 1).then(|x| x * 2);
+/// assert_eq!(42, pipeline.apply(20));
+/// ```
+#[derive(Default)]
+pub struct Pipeline {
+    steps: Vec<Box<dyn Transform>>,
+}
+
+impl Pipeline {
+    /// A pipeline that leaves numbers as they are, until it's given some steps.
+    pub fn new() -> Pipeline {
+        Pipeline::default()
+    }
+
+    pub fn then(mut self, step: impl Transform + 'static) -> Pipeline {
+        self.push(Box::new(step));
+        self
+    }
+
+    pub fn push(&mut self, step: Box<dyn Transform>) {
+        self.steps.push(step);
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl Transform for Pipeline {
+    fn apply(&self, x: i32) -> i32 {
+        self.steps.iter().fold(x, |x, step| step.apply(x))
+    }
+
+    /// Stops at the first step that overflows.
+    fn checked_apply(&self, x: i32) -> Result<i32, MathError> {
+        self.steps
+            .iter()
+            .try_fold(x, |x, step| step.checked_apply(x))
+    }
+}
+
+impl FromIterator<Box<dyn Transform>> for Pipeline {
+    fn from_iter<I: IntoIterator<Item = Box<dyn Transform>>>(steps: I) -> Pipeline {
+        Pipeline {
+            steps: steps.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let pipeline = Pipeline::new();
+        assert!(pipeline.is_empty());
+        assert_eq!(42, pipeline.apply(42));
+    }
+
+    #[test]
+    fn test_in_order() {
+        let add_then_double = Pipeline::new().then(|x| x + 1).then(|x| x * 2);
+        let double_then_add = Pipeline::new().then(|x| x * 2).then(|x| x + 1);
+        assert_eq!(42, add_then_double.apply(20));
+        assert_eq!(41, double_then_add.apply(20));
+    }
+
+    #[test]
+    fn test_checked() {
+        struct Double;
+        impl Transform for Double {
+            fn apply(&self, x: i32) -> i32 {
+                x * 2
+            }
+
+            fn checked_apply(&self, x: i32) -> Result<i32, MathError> {
+                x.checked_mul(2).ok_or(MathError::Overflow)
+            }
+        }
+        let pipeline = Pipeline::new().then(|x| x + 1).then(Double);
+        assert_eq!(Ok(42), pipeline.checked_apply(20));
+        assert_eq!(
+            Err(MathError::Overflow),
+            pipeline.checked_apply(i32::MAX / 2)
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        let steps: Vec<Box<dyn Transform>> = vec![
+            Box::new(|x| x + 1),
+            Box::new(Pipeline::new().then(|x| x * 2).then(|x| x * 2)),
+        ];
+        let pipeline: Pipeline = steps.into_iter().collect();
+        assert_eq!(2, pipeline.len());
+        assert_eq!(44, pipeline.apply(10));
+    }
+}
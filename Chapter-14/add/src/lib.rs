@@ -0,0 +1,13 @@
+//! Everything in the workspace from one dependency.
+//!
+//! # Example
+//! ```rust
+//! use add::{add_one, times_two, AddOne, Pipeline, TimesTwo, Transform};
+//! assert_eq!(42, times_two(add_one(20)));
+//! assert_eq!(42, Pipeline::new().then(AddOne).then(TimesTwo).apply(20));
+//! ```
+
+pub use add_n::*;
+pub use add_one::*;
+pub use math_core::*;
+pub use times_two::*;
@@ -0,0 +1,15 @@
+// cargo run --release -p times_two --example bench
+use times_two::{bench, times_two_all, times_two_slice, times_two_slice_parallel};
+
+fn main() {
+    for len in [1_000, 100_000, 10_000_000] {
+        let input: Vec<i32> = (0..len).map(|x| x % 1000).collect();
+        println!("{len} numbers:");
+        println!("  one at a time  {:?}", bench(&input, 10, times_two_all));
+        println!("  in chunks      {:?}", bench(&input, 10, times_two_slice));
+        println!(
+            "  on threads     {:?}",
+            bench(&input, 10, times_two_slice_parallel)
+        );
+    }
+}
@@ -1,13 +1,202 @@
+use math_core::{MathError, Transform};
+use std::{
+    iter::Map,
+    thread,
+    time::{Duration, Instant},
+};
+
 /// # Example
 /// ```rust
 /// use times_two::times_two;
 /// let num = 21;
 /// assert_eq!(42, times_two(num));
 /// ```
+///
+/// # Panics
+/// Doubling anything past half of `i32::MAX` either way panics in a debug
+/// build, and wraps around in a release build. [`checked_times_two`],
+/// [`saturating_times_two`], and [`wrapping_times_two`] do the same thing
+/// either way.
 pub fn times_two(x: i32) -> i32 {
     x * 2
 }
 
+/// # Example
+/// ```rust
+/// use times_two::checked_times_two;
+/// assert_eq!(Some(42), checked_times_two(21));
+/// assert_eq!(None, checked_times_two(i32::MAX));
+/// ```
+pub fn checked_times_two(x: i32) -> Option<i32> {
+    x.checked_mul(2)
+}
+
+/// # Example
+/// ```rust
+/// use times_two::saturating_times_two;
+/// assert_eq!(42, saturating_times_two(21));
+/// assert_eq!(i32::MIN, saturating_times_two(i32::MIN / 2 - 1));
+/// ```
+pub fn saturating_times_two(x: i32) -> i32 {
+    x.saturating_mul(2)
+}
+
+/// # Example
+/// ```rust
+/// use times_two::wrapping_times_two;
+/// assert_eq!(42, wrapping_times_two(21));
+/// assert_eq!(-2, wrapping_times_two(i32::MAX));
+/// ```
+pub fn wrapping_times_two(x: i32) -> i32 {
+    x.wrapping_mul(2)
+}
+
+/// [`times_two`] for floats, which don't overflow: they go to infinity.
+///
+/// # Example
+/// ```rust
+/// use times_two::times_two_f64;
+/// use math_core::approx_eq;
+/// assert!(approx_eq(0.6, times_two_f64(0.1 + 0.2), 1e-12));
+/// assert_eq!(f64::INFINITY, times_two_f64(f64::MAX));
+/// ```
+pub fn times_two_f64(x: f64) -> f64 {
+    x * 2.0
+}
+
+/// Doubles every number in `xs`, in place.
+///
+/// # Example
+/// ```rust
+/// use times_two::times_two_all;
+/// let mut nums = [1, 2, 21];
+/// times_two_all(&mut nums);
+/// assert_eq!([2, 4, 42], nums);
+/// ```
+pub fn times_two_all(xs: &mut [i32]) {
+    for x in xs {
+        *x = times_two(*x);
+    }
+}
+
+/// What [`TimesTwoIterator::map_times_two`] turns `I` into.
+pub type MapTimesTwo<I> = Map<I, fn(i32) -> i32>;
+
+/// How many to work through at a time in [`times_two_slice`].
+const CHUNK: usize = 8;
+
+/// The shortest slice [`times_two_slice_parallel`] bothers splitting between
+/// threads. Below this, starting them takes longer than the doubling does.
+pub const PARALLEL_THRESHOLD: usize = 1 << 16;
+
+/// [`times_two_all`] for big slices: the same answer, sooner.
+///
+/// It goes through in fixed-size chunks, so the compiler knows how many there
+/// are in each one and can unroll the loop and double several at once.
+///
+/// # Example
+/// ```rust
+/// use times_two::times_two_slice;
+/// let mut nums: Vec<i32> = (0..100).collect();
+/// times_two_slice(&mut nums);
+/// assert_eq!(198, nums[99]);
+/// ```
+pub fn times_two_slice(xs: &mut [i32]) {
+    let mut chunks = xs.chunks_exact_mut(CHUNK);
+    for chunk in &mut chunks {
+        let chunk: &mut [i32; CHUNK] = chunk.try_into().expect("chunks are all CHUNK long");
+        for x in chunk {
+            *x = times_two(*x);
+        }
+    }
+    for x in chunks.into_remainder() {
+        *x = times_two(*x);
+    }
+}
+
+/// [`times_two_slice`] on a thread per core, for slices of at least
+/// [`PARALLEL_THRESHOLD`]. Anything shorter stays on this thread.
+///
+/// # Example
+/// ```rust
+/// use times_two::times_two_slice_parallel;
+/// let mut nums = vec![21; 1_000_000];
+/// times_two_slice_parallel(&mut nums);
+/// assert!(nums.iter().all(|&x| x == 42));
+/// ```
+pub fn times_two_slice_parallel(xs: &mut [i32]) {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    if xs.len() < PARALLEL_THRESHOLD || threads == 1 {
+        return times_two_slice(xs);
+    }
+    let part = xs.len().div_ceil(threads);
+    thread::scope(|s| {
+        for part in xs.chunks_mut(part) {
+            s.spawn(|| times_two_slice(part));
+        }
+    });
+}
+
+/// How long `f` takes on `input`: the fastest of `runs` tries, each on a fresh
+/// copy, so a slow run from something else going on doesn't count.
+///
+/// # Example
+/// ```rust
+/// use times_two::{bench, times_two_all, times_two_slice};
+/// let input = vec![1; 10_000];
+/// let simple = bench(&input, 5, times_two_all);
+/// let chunked = bench(&input, 5, times_two_slice);
+/// println!("{simple:?} against {chunked:?}");
+/// ```
+pub fn bench(input: &[i32], runs: u32, f: impl Fn(&mut [i32])) -> Duration {
+    (0..runs.max(1))
+        .map(|_| {
+            let mut xs = input.to_vec();
+            let start = Instant::now();
+            f(&mut xs);
+            start.elapsed()
+        })
+        .min()
+        .expect("there's always at least one run")
+}
+
+/// `map_times_two` on any iterator of `i32`s.
+///
+/// # Example
+/// ```rust
+/// use times_two::TimesTwoIterator;
+/// let nums: Vec<i32> = [1, 2, 21].into_iter().map_times_two().collect();
+/// assert_eq!(vec![2, 4, 42], nums);
+/// ```
+pub trait TimesTwoIterator: Iterator<Item = i32> + Sized {
+    fn map_times_two(self) -> MapTimesTwo<Self> {
+        self.map(times_two)
+    }
+}
+
+impl<I: Iterator<Item = i32>> TimesTwoIterator for I {}
+
+/// [`times_two`] as a [`Transform`].
+///
+/// # Example
+/// ```rust
+/// use times_two::TimesTwo;
+/// use math_core::Transform;
+/// assert_eq!(42, TimesTwo.apply(21));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimesTwo;
+
+impl Transform for TimesTwo {
+    fn apply(&self, x: i32) -> i32 {
+        times_two(x)
+    }
+
+    fn checked_apply(&self, x: i32) -> Result<i32, MathError> {
+        checked_times_two(x).ok_or(MathError::Overflow)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -17,4 +206,83 @@ mod tests {
         let num = 21;
         assert_eq!(42, times_two(num));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_times_two_all() {
+        let mut empty: [i32; 0] = [];
+        times_two_all(&mut empty);
+        assert_eq!([0; 0], empty);
+
+        let mut big: Vec<i32> = (0..100_000).collect();
+        times_two_all(&mut big);
+        assert!(big.iter().copied().eq((0..200_000).step_by(2)));
+    }
+
+    #[test]
+    fn test_map_times_two() {
+        assert_eq!(None, std::iter::empty().map_times_two().next());
+        assert_eq!(
+            9_999_900_000i64,
+            (0..100_000).map_times_two().map(i64::from).sum()
+        );
+    }
+
+    #[test]
+    fn test_times_two_f64() {
+        use math_core::approx_eq;
+
+        assert_ne!(0.6, times_two_f64(0.1 + 0.2));
+        assert!(approx_eq(0.6, times_two_f64(0.1 + 0.2), 1e-12));
+        assert!(approx_eq(-2.2, times_two_f64(-1.1), 1e-12));
+        assert_eq!(0.0, times_two_f64(0.0));
+        assert!(times_two_f64(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_times_two_slice() {
+        for len in [
+            0,
+            1,
+            CHUNK - 1,
+            CHUNK,
+            CHUNK + 1,
+            1000,
+            PARALLEL_THRESHOLD + 3,
+        ] {
+            let input: Vec<i32> = (0..len as i32).map(|x| x - 500).collect();
+            let mut expected = input.clone();
+            times_two_all(&mut expected);
+
+            let mut chunked = input.clone();
+            times_two_slice(&mut chunked);
+            assert_eq!(expected, chunked, "{len} long");
+
+            let mut parallel = input.clone();
+            times_two_slice_parallel(&mut parallel);
+            assert_eq!(expected, parallel, "{len} long");
+        }
+    }
+
+    #[test]
+    fn test_bench() {
+        let input = vec![21; 100];
+        let runs = std::cell::Cell::new(0);
+        bench(&input, 3, |xs| {
+            // a fresh copy every time
+            assert!(xs.iter().all(|&x| x == 21));
+            times_two_slice(xs);
+            runs.set(runs.get() + 1);
+        });
+        assert_eq!(3, runs.get());
+        // and the input's left alone
+        assert!(input.iter().all(|&x| x == 21));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(None, checked_times_two(i32::MAX / 2 + 1));
+        assert_eq!(Some(i32::MAX - 1), checked_times_two(i32::MAX / 2));
+        assert_eq!(i32::MAX, saturating_times_two(i32::MAX / 2 + 1));
+        assert_eq!(i32::MIN, wrapping_times_two(i32::MAX / 2 + 1));
+    }
+}
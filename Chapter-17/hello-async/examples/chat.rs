@@ -0,0 +1,28 @@
+use hello_async::{async_stdin_lines, chat, Combinators, ShutdownSignal};
+use std::{env, net::TcpListener, process};
+
+fn main() {
+    let address = env::args()
+        .nth(1)
+        .unwrap_or_else(|| chat::DEFAULT_ADDRESS.to_string());
+    let listener = TcpListener::bind(&address).unwrap_or_else(|e| {
+        eprintln!("Couldn't listen on {address}: {e}");
+        process::exit(1);
+    });
+    println!("Chatting on {address}. Connect with telnet or nc, and type `quit` here to stop.");
+
+    trpl::run(async {
+        let shutdown = ShutdownSignal::new();
+        // the end of the input stops it too
+        let quit = async {
+            let mut lines = async_stdin_lines();
+            while let Some(Ok(line)) = lines.next().await {
+                if line.trim() == "quit" {
+                    break;
+                }
+            }
+            shutdown.trigger();
+        };
+        trpl::join!(chat::serve(listener, shutdown.clone()), quit);
+    });
+}
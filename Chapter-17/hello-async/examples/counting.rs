@@ -0,0 +1,42 @@
+use hello_async::{interval, race, select_all};
+use std::{future::Future, pin::Pin, time::Duration};
+use trpl::Either;
+
+fn main() {
+    trpl::run(async {
+        let fut1 = count("task 1", 10, Duration::from_millis(500));
+        let fut2 = count("task 2", 5, Duration::from_millis(500));
+        trpl::join!(fut1, fut2);
+
+        // the same two, but only until one of them is done
+        let fut1 = count("task 1", 10, Duration::from_millis(500));
+        let fut2 = count("task 2", 5, Duration::from_millis(500));
+        match race(fut1, fut2).await {
+            Either::Left(name) | Either::Right(name) => println!("{name} won the race!"),
+        }
+
+        // and any number of them, finishing one at a time
+        let mut futures: Vec<Pin<Box<dyn Future<Output = &str>>>> = vec![
+            Box::pin(count("task 3", 3, Duration::from_millis(300))),
+            Box::pin(count("task 4", 2, Duration::from_millis(700))),
+            Box::pin(count("task 5", 4, Duration::from_millis(100))),
+        ];
+        let mut place = 1;
+        while !futures.is_empty() {
+            let (name, _, rest) = select_all(futures).await;
+            println!("{name} came in at number {place}");
+            futures = rest;
+            place += 1;
+        }
+    });
+}
+
+// says hi `up_to` times, once every `period`
+async fn count(name: &str, up_to: u32, period: Duration) -> &str {
+    let mut ticks = interval(period);
+    for i in 0..up_to {
+        ticks.tick().await;
+        println!("hi number {i} from {name}!");
+    }
+    name
+}
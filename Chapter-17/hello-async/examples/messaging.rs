@@ -0,0 +1,139 @@
+use hello_async::{
+    bounded_channel, fan_in, fan_out, race, Ack, Combinators, Message, ReceiverStream, Requester,
+    ShutdownSignal, Throttle,
+};
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    time::{Duration, Instant},
+};
+use trpl::Either;
+
+fn main() {
+    trpl::run(async {
+        // room for two messages at a time. the senders don't wait between messages, so they fill
+        // it up straight away, and from then on each `send` waits for the slow receiver to take one
+        let (tx, rx) = bounded_channel(2);
+        // and the acks coming back the other way
+        let (ack_tx, acks) = bounded_channel(2);
+        let tx1 = tx.clone();
+        let tx2 = tx.clone();
+        let shutdown = ShutdownSignal::new();
+        let signal = shutdown.clone();
+        let signal1 = shutdown.clone();
+
+        let tx_fut = pin!(async move {
+            let msgs = vec![
+                Message::Text(String::from("hi")),
+                Message::Text(String::from("from")),
+                Message::Text(String::from("the")),
+                Message::Text(String::from("future")),
+                Message::Ping,
+            ];
+            for msg in msgs {
+                let sent = msg.clone();
+                // stop straight away on shutdown, even halfway through waiting for room
+                match race(signal.listen(), tx.send(msg)).await {
+                    Either::Left(()) => break,
+                    Either::Right(result) => result.unwrap(),
+                }
+                println!("sent: {sent:?}");
+            }
+            // dropping the sender here tells the receiver there's nothing more coming
+        });
+
+        // these ones wait to hear that each message got there before sending the next
+        let tx1_fut = pin!(async move {
+            let requester = Requester::new(tx1, acks);
+            for text in ["hello", "from", "future 1"] {
+                match race(signal1.listen(), requester.request(text)).await {
+                    Either::Left(()) => break,
+                    Either::Right(result) => println!("acked: #{} `{text}`", result.unwrap()),
+                }
+            }
+        });
+
+        // asks for a shutdown, which waits its turn behind whatever's already been sent. the
+        // senders stop once the receiver gets to it, and the receiver gets through whatever they'd
+        // already sent before it finishes
+        let shutdown_fut = pin!(async move {
+            trpl::sleep(Duration::from_millis(3000)).await;
+            println!("asking to shut down");
+            tx2.send(Message::Shutdown).await.unwrap();
+        });
+
+        let ack_tx = &ack_tx;
+        let shutdown = &shutdown;
+        let rx_fut = pin!(ReceiverStream::new(rx).for_each(|msg| async move {
+            println!("received: {msg:?}");
+            match msg {
+                // if it's given up waiting, there's nobody to tell
+                Message::AckRequired { id, .. } => ack_tx.send(Ack { id }).await.unwrap_or(()),
+                Message::Shutdown => shutdown.trigger(),
+                Message::Text(_) | Message::Ping => {}
+            }
+            trpl::sleep(Duration::from_millis(500)).await;
+        }));
+
+        let futures: Vec<Pin<&mut dyn Future<Output = ()>>> =
+            vec![tx_fut, tx1_fut, rx_fut, shutdown_fut];
+        trpl::join_all(futures).await;
+
+        share_out().await;
+        smooth_out().await;
+    });
+}
+
+// some jobs shared out between three workers, whose results all come back on one stream. the
+// smaller the job the longer it takes, so they finish in a different order from the one they were
+// handed out in
+async fn share_out() {
+    let (jobs_tx, jobs_rx) = bounded_channel(2);
+    let mut results = Vec::new();
+    let mut workers = Vec::new();
+    for (worker, jobs) in fan_out(jobs_rx, 3).into_iter().enumerate() {
+        let (tx, rx) = bounded_channel(2);
+        results.push(rx);
+        workers.push(trpl::spawn_task(async move {
+            while let Some(job) = jobs.recv().await {
+                trpl::sleep(Duration::from_millis(600 / job)).await;
+                let result = format!("worker {worker} squared {job} to get {}", job * job);
+                tx.send(result).await.unwrap();
+            }
+        }));
+    }
+
+    let jobs = async move {
+        for job in 1..=6u64 {
+            jobs_tx.send(job).await.unwrap();
+        }
+    };
+    let results = fan_in(results).for_each(|result| async move { println!("{result}") });
+    trpl::join!(jobs, results);
+    for worker in workers {
+        worker.await.unwrap();
+    }
+}
+
+// a sender that sends in bursts, but gets to send three a second at most. the first three of each
+// burst go straight away, and the rest come through one at a time after that
+async fn smooth_out() {
+    let (tx, rx) = bounded_channel(10);
+    let mut tx = Throttle::new(tx, 3, Duration::from_secs(1));
+    let start = Instant::now();
+
+    let send = async move {
+        for burst in 1..=2 {
+            for n in 1..=5 {
+                tx.send(format!("burst {burst}, message {n}"))
+                    .await
+                    .unwrap();
+            }
+            trpl::sleep(Duration::from_secs(2)).await;
+        }
+    };
+    let receive = ReceiverStream::new(rx).for_each(|msg| async move {
+        println!("{:>5}ms: {msg}", start.elapsed().as_millis());
+    });
+    trpl::join!(send, receive);
+}
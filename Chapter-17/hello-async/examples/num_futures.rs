@@ -1,5 +1,5 @@
-use std::{future::Future, time::Duration};
-use trpl::Either;
+use hello_async::timeout;
+use std::time::Duration;
 
 fn main() {
     trpl::run(async {
@@ -16,13 +16,3 @@ fn main() {
         }
     });
 }
-
-async fn timeout<F: Future>(
-    future_to_try: F,
-    max_time: Duration,
-) -> Result<F::Output, Duration> {
-    match trpl::race(future_to_try, trpl::sleep(max_time)).await {
-        Either::Left(out) => Ok(out),
-        Either::Right(_) => Err(max_time),
-    }
-}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use hello_async::{async_stdin_lines, bounded_channel, interval, race, Combinators};
+use std::time::Duration;
+use trpl::Either;
+
+fn main() {
+    trpl::run(async {
+        println!("Say something, or `quit` to stop.");
+        let mut lines = async_stdin_lines();
+
+        // some work going on in the background the whole time, with updates now and then
+        let (tx, mut updates) = bounded_channel(1);
+        trpl::spawn_task(async move {
+            let mut ticks = interval(Duration::from_secs(3));
+            ticks.tick().await;
+            for done in (10..=100).step_by(10) {
+                ticks.tick().await;
+                if tx
+                    .send(format!("background work {done}% done"))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        // whichever comes first, the user or the work. neither gets lost by waiting on both,
+        // since the one that loses is only waiting and hasn't taken anything yet
+        let mut working = true;
+        loop {
+            let event = if working {
+                race(lines.next(), updates.recv()).await
+            } else {
+                Either::Left(lines.next().await)
+            };
+            match event {
+                Either::Left(Some(Ok(line))) => match line.trim() {
+                    "quit" => break,
+                    "" => {}
+                    line => println!("You said: {line}"),
+                },
+                Either::Left(Some(Err(e))) => {
+                    eprintln!("Problem reading that: {e}");
+                    break;
+                }
+                // the end of the input
+                Either::Left(None) => break,
+                Either::Right(Some(update)) => println!("({update})"),
+                Either::Right(None) => {
+                    println!("(background work all done)");
+                    working = false;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,30 @@
+use hello_async::AsyncMutex;
+use std::{sync::Arc, time::Duration};
+
+fn main() {
+    trpl::run(async {
+        let counter = Arc::new(AsyncMutex::new(0));
+        let arrivals = Arc::new(AsyncMutex::new(Vec::new()));
+        let mut handles = vec![];
+
+        for task in 0..10 {
+            let counter = Arc::clone(&counter);
+            let arrivals = Arc::clone(&arrivals);
+            let handle = trpl::spawn_task(async move {
+                // the later tasks get there first
+                trpl::sleep(Duration::from_millis(100 - task * 10)).await;
+                let mut num = counter.lock().await;
+                *num += 1;
+                arrivals.lock().await.push(task);
+            });
+            handles.push(handle);
+        }
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        println!("Result: {}", *counter.lock().await);
+        println!("In order of arrival: {:?}", *arrivals.lock().await);
+    });
+}
@@ -0,0 +1,336 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::{poll_fn, Future},
+    pin::pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+// a channel that holds at most `capacity` messages. once it's full, `send` waits for the receiver
+// to take one, so a slow receiver slows the senders down to its own pace (backpressure) instead of
+// the messages piling up without end the way they can in trpl::channel
+//
+// panics if `capacity` is 0, since then nothing could ever be sent
+pub fn bounded_channel<T>(capacity: usize) -> (BoundedSender<T>, BoundedReceiver<T>) {
+    assert!(
+        capacity > 0,
+        "a channel needs room for at least one message"
+    );
+    let shared = Arc::new(Mutex::new(State {
+        queue: VecDeque::with_capacity(capacity),
+        capacity,
+        senders: 1,
+        receiving: true,
+        send_wakers: Vec::new(),
+        recv_waker: None,
+    }));
+    (
+        BoundedSender {
+            shared: Arc::clone(&shared),
+        },
+        BoundedReceiver { shared },
+    )
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    capacity: usize,
+    // how many senders are left. at 0, the receiver gets None once the queue is empty
+    senders: usize,
+    // false once the receiver's dropped, and nothing sent would ever be read
+    receiving: bool,
+    // senders waiting for room, and the receiver waiting for a message
+    send_wakers: Vec<Waker>,
+    recv_waker: Option<Waker>,
+}
+
+pub struct BoundedSender<T> {
+    shared: Arc<Mutex<State<T>>>,
+}
+
+pub struct BoundedReceiver<T> {
+    shared: Arc<Mutex<State<T>>>,
+}
+
+// the receiver's gone. the message that couldn't be sent comes back
+#[derive(Debug, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the receiver has been dropped")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for SendError<T> {}
+
+// why `try_send` couldn't send. either way the message comes back
+#[derive(Debug, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    // there's no room right now
+    Full(T),
+    // the receiver's gone
+    Disconnected(T),
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "the channel is full"),
+            TrySendError::Disconnected(_) => write!(f, "the receiver has been dropped"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for TrySendError<T> {}
+
+impl<T> BoundedSender<T> {
+    // waits for room when the channel's full
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut value = Some(value);
+        poll_fn(|cx| {
+            let mut state = self.shared.lock().unwrap();
+            if !state.receiving {
+                return Poll::Ready(Err(SendError(value.take().expect("only sent once"))));
+            }
+            if state.queue.len() < state.capacity {
+                state.queue.push_back(value.take().expect("only sent once"));
+                if let Some(waker) = state.recv_waker.take() {
+                    waker.wake();
+                }
+                return Poll::Ready(Ok(()));
+            }
+            // full, so hang on to it and wait to be told there's room
+            if !state.send_wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                state.send_wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    // sends only if there's room right now, for when waiting on one receiver would hold up
+    // everything else
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.shared.lock().unwrap();
+        if !state.receiving {
+            return Err(TrySendError::Disconnected(value));
+        }
+        if state.queue.len() == state.capacity {
+            return Err(TrySendError::Full(value));
+        }
+        state.queue.push_back(value);
+        if let Some(waker) = state.recv_waker.take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    // for a plain thread that isn't running any async code, like one stuck reading a file or the
+    // terminal: sends, with the whole thread waiting if the channel's full
+    pub fn blocking_send(&self, value: T) -> Result<(), SendError<T>> {
+        block_on(self.send(value))
+    }
+}
+
+// runs `future` on this thread, with the thread parked whenever it's waiting. only for futures
+// that don't need a runtime, like the channel's own, which are woken by the other end of it
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        // parking can also just stop on its own, which only means checking again
+        thread::park();
+    }
+}
+
+// wakes a thread that's parked waiting for the channel
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+impl<T> BoundedReceiver<T> {
+    // the next message, or None once every sender's gone and everything they sent has been read
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    // `recv` for a plain thread, which waits for the next message
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        block_on(self.recv())
+    }
+
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.shared.lock().unwrap();
+        if let Some(value) = state.queue.pop_front() {
+            // there's room now, so whoever was waiting for it can try again
+            for waker in state.send_wakers.drain(..) {
+                waker.wake();
+            }
+            return Poll::Ready(Some(value));
+        }
+        if state.senders == 0 {
+            return Poll::Ready(None);
+        }
+        state.recv_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Clone for BoundedSender<T> {
+    fn clone(&self) -> BoundedSender<T> {
+        self.shared.lock().unwrap().senders += 1;
+        BoundedSender {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for BoundedSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock().unwrap();
+        state.senders -= 1;
+        // the last one going means the receiver isn't waiting for anything any more
+        if state.senders == 0 {
+            if let Some(waker) = state.recv_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> Drop for BoundedReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock().unwrap();
+        state.receiving = false;
+        for waker in state.send_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::race;
+    use std::time::Duration;
+    use trpl::Either;
+
+    #[test]
+    fn waits_when_full() {
+        trpl::run(async {
+            let (tx, mut rx) = bounded_channel(2);
+            tx.send(1).await.unwrap();
+            tx.send(2).await.unwrap();
+            // there's no room for a third until something's taken out
+            let third = race(tx.send(3), trpl::sleep(Duration::from_millis(50))).await;
+            assert!(matches!(third, Either::Right(())));
+            assert_eq!(Some(1), rx.recv().await);
+            tx.send(3).await.unwrap();
+            assert_eq!(Some(2), rx.recv().await);
+            assert_eq!(Some(3), rx.recv().await);
+        });
+    }
+
+    #[test]
+    fn slow_receiver() {
+        trpl::run(async {
+            let (tx, mut rx) = bounded_channel(1);
+            let send = async move {
+                for n in 0..5 {
+                    tx.send(n).await.unwrap();
+                }
+            };
+            let receive = async {
+                let mut received = Vec::new();
+                while let Some(n) = rx.recv().await {
+                    trpl::sleep(Duration::from_millis(5)).await;
+                    received.push(n);
+                }
+                received
+            };
+            let ((), received) = trpl::join!(send, receive);
+            assert_eq!(vec![0, 1, 2, 3, 4], received);
+        });
+    }
+
+    #[test]
+    fn from_a_thread() {
+        let (tx, mut rx) = bounded_channel(1);
+        let sender = thread::spawn(move || {
+            for n in 0..5 {
+                tx.blocking_send(n).unwrap();
+            }
+        });
+        let received = trpl::run(async {
+            let mut received = Vec::new();
+            while let Some(n) = rx.recv().await {
+                trpl::sleep(Duration::from_millis(5)).await;
+                received.push(n);
+            }
+            received
+        });
+        sender.join().unwrap();
+        assert_eq!(vec![0, 1, 2, 3, 4], received);
+
+        // and the other way round
+        let (tx, mut rx) = bounded_channel(1);
+        let receiver = thread::spawn(move || {
+            let mut received = Vec::new();
+            while let Some(n) = rx.blocking_recv() {
+                received.push(n);
+            }
+            received
+        });
+        trpl::run(async move {
+            for n in 0..5 {
+                tx.send(n).await.unwrap();
+            }
+        });
+        assert_eq!(vec![0, 1, 2, 3, 4], receiver.join().unwrap());
+    }
+
+    #[test]
+    fn closing() {
+        trpl::run(async {
+            let (tx, mut rx) = bounded_channel(4);
+            let tx1 = tx.clone();
+            tx.send("a").await.unwrap();
+            tx1.send("b").await.unwrap();
+            drop(tx);
+            drop(tx1);
+            // what was sent before the senders went still arrives
+            assert_eq!(Some("a"), rx.recv().await);
+            assert_eq!(Some("b"), rx.recv().await);
+            assert_eq!(None, rx.recv().await);
+
+            let (tx, rx) = bounded_channel(1);
+            drop(rx);
+            assert_eq!(Err(SendError("c")), tx.send("c").await);
+        });
+    }
+
+    #[test]
+    fn try_send() {
+        trpl::run(async {
+            let (tx, mut rx) = bounded_channel(1);
+            assert_eq!(Ok(()), tx.try_send(1));
+            assert_eq!(Err(TrySendError::Full(2)), tx.try_send(2));
+            assert_eq!(Some(1), rx.recv().await);
+            assert_eq!(Ok(()), tx.try_send(3));
+            assert_eq!(Some(3), rx.recv().await);
+            drop(rx);
+            assert_eq!(Err(TrySendError::Disconnected(4)), tx.try_send(4));
+        });
+    }
+}
@@ -0,0 +1,261 @@
+/*
+ * A chat server: everything anyone connected says, everyone else hears. Each line a client sends
+ * goes out to the others as `[<id>] <line>`, and they're told when someone comes or goes with
+ * `* <id> joined` and `* <id> left`. Clients are numbered from 1, in the order they connect.
+ *
+ * trpl has nothing for sockets, so anything that blocks on one gets a thread of its own and talks
+ * to the async side over the bounded channels: one thread takes connections, and each client has
+ * a thread writing to it. Reading is `async_lines`, which is a thread as well underneath. What's
+ * async is the part in the middle. A task for each client passes its lines on to the hub, which
+ * is a task that knows who's connected and sends everything on to everyone else.
+ *
+ * The hub never waits on any one client. Each has room for 16 lines that haven't been written out
+ * to them yet, and a client who lets that fill up (by not reading, say) is hung up on rather than
+ * holding up the chat for everyone else.
+ */
+
+use crate::{
+    channel::{bounded_channel, BoundedReceiver, BoundedSender, TrySendError},
+    input::async_lines,
+    race,
+    shutdown::ShutdownSignal,
+    stream::{Combinators, ReceiverStream},
+};
+use std::{
+    collections::BTreeMap,
+    io::{self, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    thread,
+};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+// what the hub hears about
+enum Event {
+    Joined {
+        id: usize,
+        tx: BoundedSender<String>,
+        // for hanging up on them
+        stream: TcpStream,
+    },
+    Said {
+        id: usize,
+        line: String,
+    },
+    Left {
+        id: usize,
+    },
+}
+
+// takes clients and relays between them until `shutdown` is triggered. the clients still
+// connected then are hung up on
+pub async fn serve(listener: TcpListener, shutdown: ShutdownSignal) {
+    let (events, events_rx) = bounded_channel(64);
+    let accept = async move {
+        let mut incoming = incoming(listener);
+        let mut id = 1;
+        while let Some(stream) = incoming.next().await {
+            // a connection that falls over before it gets going isn't worth stopping for
+            if let Ok(stream) = stream {
+                if connect(id, stream, &events).await.is_ok() {
+                    id += 1;
+                }
+            }
+        }
+    };
+    race(shutdown.listen(), async {
+        trpl::join!(hub(events_rx), accept)
+    })
+    .await;
+}
+
+// the connections to `listener`, taken on a thread of its own since taking them blocks. the
+// thread stops once nobody wants any more, though not until the next one comes in
+fn incoming(listener: TcpListener) -> ReceiverStream<io::Result<TcpStream>> {
+    let (tx, rx) = bounded_channel(4);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if tx.blocking_send(stream).is_err() {
+                break;
+            }
+        }
+    });
+    ReceiverStream::new(rx)
+}
+
+// starts a new client's reader and writer, and tells the hub about them
+async fn connect(id: usize, stream: TcpStream, events: &BoundedSender<Event>) -> io::Result<()> {
+    let (tx, rx) = bounded_channel(16);
+    let writer = stream.try_clone()?;
+    let hangup = stream.try_clone()?;
+    thread::spawn(move || write_lines(writer, rx));
+    // the hub is still running while connections are being taken, so it's still listening
+    events
+        .send(Event::Joined {
+            id,
+            tx,
+            stream: hangup,
+        })
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "the hub has stopped"))?;
+    trpl::spawn_task(read_lines(id, stream, events.clone()));
+    Ok(())
+}
+
+// passes on what a client says until they hang up
+async fn read_lines(id: usize, stream: TcpStream, events: BoundedSender<Event>) {
+    let mut lines = async_lines(BufReader::new(stream));
+    while let Some(Ok(line)) = lines.next().await {
+        let line = line.trim_end().to_string();
+        if events.send(Event::Said { id, line }).await.is_err() {
+            return;
+        }
+    }
+    events.send(Event::Left { id }).await.unwrap_or(());
+}
+
+// writes what the hub sends to a client until the hub stops sending, and then hangs up on them.
+// that's the reading half too, so their reader finds out
+fn write_lines(mut stream: TcpStream, mut lines: BoundedReceiver<String>) {
+    while let Some(line) = lines.blocking_recv() {
+        if writeln!(stream, "{line}").is_err() {
+            break;
+        }
+    }
+    stream.shutdown(Shutdown::Both).unwrap_or(());
+}
+
+// sends everything that happens to everyone it didn't happen to, without waiting for anyone
+async fn hub(mut events: BoundedReceiver<Event>) {
+    let mut clients: BTreeMap<usize, (BoundedSender<String>, TcpStream)> = BTreeMap::new();
+    while let Some(event) = events.recv().await {
+        let (from, line) = match event {
+            Event::Joined { id, tx, stream } => {
+                clients.insert(id, (tx, stream));
+                (id, format!("* {id} joined"))
+            }
+            Event::Said { id, line } => (id, format!("[{id}] {line}")),
+            Event::Left { id } => {
+                clients.remove(&id);
+                (id, format!("* {id} left"))
+            }
+        };
+        let mut gone = Vec::new();
+        for (&id, (tx, stream)) in clients.iter().filter(|(&id, _)| id != from) {
+            match tx.try_send(line.clone()) {
+                Ok(()) => {}
+                // so far behind they'd hold everyone up. hanging up stops their writer even if
+                // it's stuck writing to them, and their reader will say they've left
+                Err(TrySendError::Full(_)) => {
+                    stream.shutdown(Shutdown::Both).unwrap_or(());
+                    gone.push(id);
+                }
+                // their writer stopped, and their reader's about to say they've left
+                Err(TrySendError::Disconnected(_)) => gone.push(id),
+            }
+        }
+        for id in gone {
+            clients.remove(&id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::BufRead, net::SocketAddr, time::Duration};
+
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+    }
+
+    impl Client {
+        fn connect(address: SocketAddr) -> Client {
+            let stream = TcpStream::connect(address).unwrap();
+            // so a test that's gone wrong fails rather than waits forever
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            Client {
+                reader: BufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+            }
+        }
+
+        fn say(&mut self, line: &str) {
+            writeln!(self.writer, "{line}").unwrap();
+        }
+
+        fn hear(&mut self) -> String {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            line.trim_end().to_string()
+        }
+    }
+
+    // stops the server however the clients finish, even by panicking
+    struct StopOnDrop(ShutdownSignal);
+
+    impl Drop for StopOnDrop {
+        fn drop(&mut self) {
+            self.0.trigger();
+        }
+    }
+
+    #[test]
+    fn relays() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let shutdown = ShutdownSignal::new();
+        let stop = StopOnDrop(shutdown.clone());
+        let clients = thread::spawn(move || {
+            let _stop = stop;
+            let mut first = Client::connect(address);
+            let mut second = Client::connect(address);
+            assert_eq!("* 2 joined", first.hear());
+            second.say("hi");
+            assert_eq!("[2] hi", first.hear());
+            first.say("hello\r");
+            assert_eq!("[1] hello", second.hear());
+            let mut third = Client::connect(address);
+            assert_eq!("* 3 joined", first.hear());
+            assert_eq!("* 3 joined", second.hear());
+            drop(second);
+            assert_eq!("* 2 left", first.hear());
+            assert_eq!("* 2 left", third.hear());
+            third.say("bye");
+            assert_eq!("[3] bye", first.hear());
+        });
+        trpl::run(serve(listener, shutdown));
+        clients.join().unwrap();
+    }
+
+    #[test]
+    fn hangs_up_on_clients_who_dont_read() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let shutdown = ShutdownSignal::new();
+        let stop = StopOnDrop(shutdown.clone());
+        let clients = thread::spawn(move || {
+            let _stop = stop;
+            let _stuck = Client::connect(address);
+            let mut talker = Client::connect(address);
+            let mut listener = Client::connect(address);
+            assert_eq!("* 3 joined", talker.hear());
+            // enough that the stuck client's socket fills up, and then its queue, long before
+            // this runs out. the listener keeps up, one line at a time
+            let line = "x".repeat(64 * 1024);
+            for _ in 0..10_000 {
+                talker.say(&line);
+                match listener.hear() {
+                    left if left == "* 1 left" => return,
+                    heard => assert_eq!(format!("[2] {line}"), heard),
+                }
+            }
+            panic!("the client who never reads was never hung up on");
+        });
+        trpl::run(serve(listener, shutdown));
+        clients.join().unwrap();
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{channel::BoundedReceiver, mutex::AsyncMutex};
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use trpl::Stream;
+
+// one receiver's messages shared between `n` workers, one handle each. whichever worker asks next
+// gets the next message, so a busy one doesn't hold anything up while the others are free
+pub fn fan_out<T>(rx: BoundedReceiver<T>, n: usize) -> Vec<SharedReceiver<T>> {
+    let shared = SharedReceiver {
+        rx: Arc::new(AsyncMutex::new(rx)),
+    };
+    vec![shared; n]
+}
+
+pub struct SharedReceiver<T> {
+    rx: Arc<AsyncMutex<BoundedReceiver<T>>>,
+}
+
+// not derived, which would need T: Clone for no reason
+impl<T> Clone for SharedReceiver<T> {
+    fn clone(&self) -> SharedReceiver<T> {
+        SharedReceiver {
+            rx: Arc::clone(&self.rx),
+        }
+    }
+}
+
+impl<T> SharedReceiver<T> {
+    // the next message nobody else has had, or None once they've all been had
+    pub async fn recv(&self) -> Option<T> {
+        self.rx.lock().await.recv().await
+    }
+}
+
+// all of `receivers` as one stream, with their messages in whatever order they turn up in. it ends
+// once every one of them has
+pub fn fan_in<T>(receivers: impl IntoIterator<Item = BoundedReceiver<T>>) -> FanIn<T> {
+    FanIn {
+        receivers: receivers.into_iter().map(Some).collect(),
+        next: 0,
+    }
+}
+
+pub struct FanIn<T> {
+    // None once that one's finished
+    receivers: Vec<Option<BoundedReceiver<T>>>,
+    // where to start looking next time, so one busy receiver can't keep the rest waiting
+    next: usize,
+}
+
+impl<T> Stream for FanIn<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = &mut *self;
+        let len = this.receivers.len();
+        for i in (0..len).map(|k| (this.next + k) % len) {
+            let Some(rx) = &mut this.receivers[i] else {
+                continue;
+            };
+            match rx.poll_recv(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.next = i + 1;
+                    return Poll::Ready(Some(item));
+                }
+                Poll::Ready(None) => this.receivers[i] = None,
+                Poll::Pending => {}
+            }
+        }
+        if this.receivers.iter().all(Option::is_none) {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bounded_channel, Combinators};
+    use std::time::Duration;
+
+    #[test]
+    fn out_and_back_in() {
+        trpl::run(async {
+            let (jobs_tx, jobs_rx) = bounded_channel(4);
+            let mut results = Vec::new();
+            let workers: Vec<_> = fan_out(jobs_rx, 3)
+                .into_iter()
+                .enumerate()
+                .map(|(worker, jobs)| {
+                    let (tx, rx) = bounded_channel(4);
+                    results.push(rx);
+                    trpl::spawn_task(async move {
+                        while let Some(job) = jobs.recv().await {
+                            trpl::sleep(Duration::from_millis(job % 3)).await;
+                            tx.send((worker, job * 10)).await.unwrap();
+                        }
+                    })
+                })
+                .collect();
+            let send = async move {
+                for job in 0..20u64 {
+                    jobs_tx.send(job).await.unwrap();
+                }
+            };
+            let mut done = Vec::new();
+            let receive = fan_in(results).for_each(|(worker, result)| {
+                done.push(result);
+                assert!(worker < 3);
+                async {}
+            });
+            trpl::join!(send, receive);
+            for worker in workers {
+                worker.await.unwrap();
+            }
+            // every job done once, whichever worker did it and whenever it finished
+            done.sort();
+            assert_eq!((0..20).map(|job| job * 10).collect::<Vec<_>>(), done);
+        });
+    }
+
+    #[test]
+    fn fan_in_ends() {
+        trpl::run(async {
+            let mut none = fan_in(Vec::<BoundedReceiver<u32>>::new());
+            assert_eq!(None, none.next().await);
+
+            let (tx1, rx1) = bounded_channel(1);
+            let (tx2, rx2) = bounded_channel(1);
+            let mut merged = fan_in([rx1, rx2]);
+            drop(tx1);
+            tx2.send(2).await.unwrap();
+            assert_eq!(Some(2), merged.next().await);
+            drop(tx2);
+            assert_eq!(None, merged.next().await);
+        });
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{channel::bounded_channel, stream::ReceiverStream};
+use std::{
+    io::{self, BufRead, BufReader},
+    thread,
+};
+
+// the lines typed into the terminal, as they're typed, without blocking anything async while
+// waiting for them. the stream ends at the end of the input, or after the first error reading it
+pub fn async_stdin_lines() -> ReceiverStream<io::Result<String>> {
+    async_lines(BufReader::new(io::stdin()))
+}
+
+// the lines of `reader`, read on a thread of its own since reading blocks. without the trailing
+// newline, like BufRead::lines. the thread stops once nobody wants any more lines, though not
+// until it's got the next one to find that out
+pub fn async_lines(reader: impl BufRead + Send + 'static) -> ReceiverStream<io::Result<String>> {
+    let (tx, rx) = bounded_channel(16);
+    thread::spawn(move || {
+        for line in reader.lines() {
+            let failed = line.is_err();
+            if tx.blocking_send(line).is_err() || failed {
+                break;
+            }
+        }
+    });
+    ReceiverStream::new(rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinators;
+    use std::io::Cursor;
+
+    #[test]
+    fn lines() {
+        trpl::run(async {
+            let mut lines = async_lines(Cursor::new("first\nsecond\r\n\nlast"));
+            let mut read = Vec::new();
+            while let Some(line) = lines.next().await {
+                read.push(line.unwrap());
+            }
+            assert_eq!(vec!["first", "second", "", "last"], read);
+        });
+    }
+
+    #[test]
+    fn stops_after_an_error() {
+        trpl::run(async {
+            // not UTF-8
+            let mut lines = async_lines(Cursor::new(b"fine\n\xff\xfe\nnever read\n".to_vec()));
+            assert_eq!("fine", lines.next().await.unwrap().unwrap());
+            assert!(lines.next().await.unwrap().is_err());
+            assert!(lines.next().await.is_none());
+        });
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+// ticks every `period`, starting straight away. the ticks are due at fixed times counted from the
+// start, not a period after the last one finished, so however long the work between them takes
+// (as long as it's less than a period) they don't drift later and later
+//
+// panics if `period` is zero
+pub fn interval(period: Duration) -> Interval {
+    assert!(
+        !period.is_zero(),
+        "an interval needs a period longer than nothing"
+    );
+    Interval {
+        next: Instant::now(),
+        period,
+    }
+}
+
+pub struct Interval {
+    // when the next tick's due
+    next: Instant,
+    period: Duration,
+}
+
+impl Interval {
+    // waits for the next tick, and says when it was due. one that's already overdue comes straight
+    // away, and any that were missed altogether in the meantime are skipped rather than all coming
+    // at once to catch up
+    pub async fn tick(&mut self) -> Instant {
+        let due = self.next;
+        let now = Instant::now();
+        if due > now {
+            trpl::sleep(due - now).await;
+        }
+        self.next = due + self.period;
+        let now = Instant::now();
+        while self.next <= now {
+            self.next += self.period;
+        }
+        due
+    }
+
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn on_schedule() {
+        trpl::run(async {
+            let start = Instant::now();
+            let mut ticks = interval(Duration::from_millis(20));
+            let first = ticks.tick().await;
+            assert!(first.duration_since(start) < Duration::from_millis(20));
+            for n in 1..5 {
+                let due = ticks.tick().await;
+                assert_eq!(first + Duration::from_millis(20) * n, due);
+                assert!(Instant::now() >= due);
+            }
+        });
+    }
+
+    #[test]
+    fn no_drift() {
+        trpl::run(async {
+            let mut ticks = interval(Duration::from_millis(30));
+            let start = ticks.tick().await;
+            for _ in 0..4 {
+                // some work between ticks, which a plain sleep would add on each time
+                thread::sleep(Duration::from_millis(15));
+                ticks.tick().await;
+            }
+            // 4 periods, not 4 periods and 4 lots of work
+            let elapsed = start.elapsed();
+            assert!(elapsed >= Duration::from_millis(120));
+            assert!(elapsed < Duration::from_millis(175), "{elapsed:?}");
+        });
+    }
+
+    #[test]
+    fn skips_missed_ticks() {
+        trpl::run(async {
+            let mut ticks = interval(Duration::from_millis(20));
+            let start = ticks.tick().await;
+            thread::sleep(Duration::from_millis(50));
+            // the one due at 20ms is late, and the one due at 40ms is skipped
+            assert_eq!(start + Duration::from_millis(20), ticks.tick().await);
+            assert_eq!(start + Duration::from_millis(60), ticks.tick().await);
+        });
+    }
+}
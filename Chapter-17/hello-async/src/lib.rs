@@ -0,0 +1,26 @@
+pub mod channel;
+pub mod chat;
+pub mod fan;
+pub mod input;
+pub mod interval;
+pub mod mutex;
+pub mod pipeline;
+pub mod protocol;
+pub mod retry;
+pub mod select;
+pub mod shutdown;
+pub mod stream;
+pub mod throttle;
+
+pub use channel::{bounded_channel, BoundedReceiver, BoundedSender, SendError, TrySendError};
+pub use fan::{fan_in, fan_out, FanIn, SharedReceiver};
+pub use input::{async_lines, async_stdin_lines};
+pub use interval::{interval, Interval};
+pub use mutex::{AsyncMutex, AsyncMutexGuard};
+pub use pipeline::sum_of_squares;
+pub use protocol::{Ack, Disconnected, Message, Requester};
+pub use retry::{retry, RetryPolicy};
+pub use select::{race, select_all, timeout};
+pub use shutdown::{Listener, ShutdownSignal};
+pub use stream::{Combinators, ReceiverStream};
+pub use throttle::Throttle;
@@ -0,0 +1,138 @@
+use std::{
+    future::poll_fn,
+    ops::{Deref, DerefMut},
+    sync::Mutex,
+    task::{Poll, Waker},
+};
+
+// like std::sync::Mutex, except that waiting for it doesn't block the thread: `lock` is a future,
+// so other tasks carry on while one waits its turn. the guard can be held across an `.await`
+// (which isn't a good idea with a std one, since every task on that thread would be stuck behind
+// it), and the value's the same throughout as far as everyone else is concerned
+pub struct AsyncMutex<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    // None while somebody has it
+    value: Option<T>,
+    waiters: Vec<Waker>,
+}
+
+pub struct AsyncMutexGuard<'a, T> {
+    mutex: &'a AsyncMutex<T>,
+    // only ever None after it's been put back, in drop
+    value: Option<T>,
+}
+
+impl<T> AsyncMutex<T> {
+    pub fn new(value: T) -> AsyncMutex<T> {
+        AsyncMutex {
+            state: Mutex::new(State {
+                value: Some(value),
+                waiters: Vec::new(),
+            }),
+        }
+    }
+
+    pub async fn lock(&self) -> AsyncMutexGuard<'_, T> {
+        poll_fn(|cx| {
+            let mut state = self.state.lock().unwrap();
+            match state.value.take() {
+                Some(value) => Poll::Ready(AsyncMutexGuard {
+                    mutex: self,
+                    value: Some(value),
+                }),
+                None => {
+                    if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                        state.waiters.push(cx.waker().clone());
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    // nobody else can have it, since nobody else can have the mutex
+    pub fn into_inner(self) -> T {
+        let state = self.state.into_inner().unwrap();
+        state.value.expect("a guard can't outlive its mutex")
+    }
+}
+
+impl<T> Deref for AsyncMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().expect("held until dropped")
+    }
+}
+
+impl<T> DerefMut for AsyncMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().expect("held until dropped")
+    }
+}
+
+impl<T> Drop for AsyncMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.mutex.state.lock().unwrap();
+        state.value = self.value.take();
+        // everybody gets another go, rather than just the next one. if that one's given up waiting
+        // in the meantime, nobody would be woken at all
+        for waker in state.waiters.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn counter() {
+        trpl::run(async {
+            let counter = Arc::new(AsyncMutex::new(0));
+            let handles: Vec<_> = (0..10)
+                .map(|_| {
+                    let counter = Arc::clone(&counter);
+                    trpl::spawn_task(async move {
+                        *counter.lock().await += 1;
+                    })
+                })
+                .collect();
+            for handle in handles {
+                handle.await.unwrap();
+            }
+            assert_eq!(10, *counter.lock().await);
+        });
+    }
+
+    #[test]
+    fn held_across_await() {
+        trpl::run(async {
+            let log = AsyncMutex::new(Vec::new());
+            let tasks = (0..5).map(|n| {
+                let log = &log;
+                async move {
+                    let mut log = log.lock().await;
+                    log.push(n);
+                    // the others get to run here, but not to get at the Vec
+                    trpl::yield_now().await;
+                    log.push(n);
+                }
+            });
+            trpl::join_all(tasks).await;
+            let mut log = log.into_inner();
+            // each task's two went in together
+            for pair in log.chunks(2) {
+                assert_eq!(pair[0], pair[1]);
+            }
+            log.sort();
+            assert_eq!(vec![0, 0, 1, 1, 2, 2, 3, 3, 4, 4], log);
+        });
+    }
+}
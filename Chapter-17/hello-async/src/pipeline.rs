@@ -0,0 +1,83 @@
+use crate::channel::{bounded_channel, BoundedReceiver, BoundedSender};
+
+// numbers go in one end and their squares get added up at the other, with each stage a task of its
+// own and a channel between each one and the next. once the numbers run out, the producer drops
+// its sender, which ends the squarer's loop, which drops its sender, which ends the sum. nothing
+// has to be told to stop
+pub async fn sum_of_squares<I>(numbers: I, capacity: usize) -> u64
+where
+    I: IntoIterator<Item = u64> + Send + 'static,
+    I::IntoIter: Send,
+{
+    let (numbers_tx, numbers_rx) = bounded_channel(capacity);
+    let (squares_tx, squares_rx) = bounded_channel(capacity);
+    let produce = trpl::spawn_task(produce(numbers, numbers_tx));
+    let square = trpl::spawn_task(square(numbers_rx, squares_tx));
+    let sum = trpl::spawn_task(sum(squares_rx));
+    produce.await.unwrap();
+    square.await.unwrap();
+    sum.await.unwrap()
+}
+
+// stage one: everything in `numbers`, or as many as anyone's still there for
+pub async fn produce<I: IntoIterator>(numbers: I, tx: BoundedSender<I::Item>) {
+    for n in numbers {
+        if tx.send(n).await.is_err() {
+            break;
+        }
+    }
+}
+
+// stage two
+pub async fn square(mut rx: BoundedReceiver<u64>, tx: BoundedSender<u64>) {
+    while let Some(n) = rx.recv().await {
+        if tx.send(n * n).await.is_err() {
+            break;
+        }
+    }
+}
+
+// stage three
+pub async fn sum(mut rx: BoundedReceiver<u64>) -> u64 {
+    let mut total = 0;
+    while let Some(n) = rx.recv().await {
+        total += n;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_squares() {
+        trpl::run(async {
+            assert_eq!(385, sum_of_squares(1..=10, 2).await);
+            assert_eq!(
+                385,
+                sum_of_squares(vec![10, 9, 8, 7, 6, 5, 4, 3, 2, 1], 1).await
+            );
+            assert_eq!(0, sum_of_squares([], 4).await);
+        });
+    }
+
+    #[test]
+    fn stops_when_nobody_is_listening() {
+        trpl::run(async {
+            let (numbers_tx, numbers_rx) = bounded_channel(2);
+            let (squares_tx, mut squares_rx) = bounded_channel(2);
+            // the numbers never run out, so only the end of the pipeline going can stop them
+            let produce = trpl::spawn_task(produce(1.., numbers_tx));
+            let square = trpl::spawn_task(square(numbers_rx, squares_tx));
+            let mut first = Vec::new();
+            for _ in 0..3 {
+                first.push(squares_rx.recv().await.unwrap());
+            }
+            drop(squares_rx);
+            square.await.unwrap();
+            produce.await.unwrap();
+            assert_eq!(vec![1, 4, 9], first);
+        });
+    }
+}
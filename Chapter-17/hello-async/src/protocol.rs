@@ -0,0 +1,162 @@
+use crate::channel::{BoundedReceiver, BoundedSender};
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt,
+    future::poll_fn,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Text(String),
+    // only to check the other end's still there
+    Ping,
+    // text the sender waits to hear has arrived, with an `Ack` of the same id
+    AckRequired { id: u64, text: String },
+    // nothing more's coming, and everyone should stop
+    Shutdown,
+}
+
+// the answer to an `AckRequired`, sent back on a channel of its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ack {
+    pub id: u64,
+}
+
+// one of the channels closed, so there's no one to send to or no answer coming
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disconnected;
+
+impl fmt::Display for Disconnected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the other end has gone")
+    }
+}
+
+impl Error for Disconnected {}
+
+// sends messages, and for the ones that need it waits for the ack with the right id. any number
+// of requests can be waiting at once, and their acks can come back in any order
+pub struct Requester {
+    tx: BoundedSender<Message>,
+    next_id: AtomicU64,
+    replies: Mutex<Replies>,
+}
+
+struct Replies {
+    acks: BoundedReceiver<Ack>,
+    // acks that came in while a different request was reading, for their own request to find
+    arrived: HashSet<u64>,
+    // the requests waiting on an ack. only one of them is waiting on the channel itself, so it
+    // wakes the rest whenever something arrives in case it's theirs
+    waiting: Vec<Waker>,
+}
+
+impl Requester {
+    pub fn new(tx: BoundedSender<Message>, acks: BoundedReceiver<Ack>) -> Requester {
+        Requester {
+            tx,
+            next_id: AtomicU64::new(0),
+            replies: Mutex::new(Replies {
+                acks,
+                arrived: HashSet::new(),
+                waiting: Vec::new(),
+            }),
+        }
+    }
+
+    // without waiting to hear back
+    pub async fn send(&self, message: Message) -> Result<(), Disconnected> {
+        self.tx.send(message).await.map_err(|_| Disconnected)
+    }
+
+    // sends `text` as an `AckRequired`, and waits for its ack. the id it was sent with
+    pub async fn request(&self, text: impl Into<String>) -> Result<u64, Disconnected> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let text = text.into();
+        self.send(Message::AckRequired { id, text }).await?;
+        poll_fn(|cx| self.poll_ack(id, cx)).await?;
+        Ok(id)
+    }
+
+    fn poll_ack(&self, id: u64, cx: &mut Context<'_>) -> Poll<Result<(), Disconnected>> {
+        let mut replies = self.replies.lock().unwrap();
+        loop {
+            if replies.arrived.remove(&id) {
+                return Poll::Ready(Ok(()));
+            }
+            match replies.acks.poll_recv(cx) {
+                Poll::Ready(Some(ack)) => {
+                    replies.arrived.insert(ack.id);
+                    for waker in replies.waiting.drain(..) {
+                        waker.wake();
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(Err(Disconnected)),
+                Poll::Pending => {
+                    if !replies.waiting.iter().any(|w| w.will_wake(cx.waker())) {
+                        replies.waiting.push(cx.waker().clone());
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_channel;
+
+    #[test]
+    fn acks_in_any_order() {
+        trpl::run(async {
+            let (tx, mut rx) = bounded_channel(4);
+            let (ack_tx, acks) = bounded_channel(4);
+            let requester = Requester::new(tx, acks);
+            // takes both requests before answering either, and answers the second one first
+            let respond = async move {
+                let mut ids = Vec::new();
+                while ids.len() < 2 {
+                    match rx.recv().await {
+                        Some(Message::AckRequired { id, .. }) => ids.push(id),
+                        other => assert_eq!(Some(Message::Ping), other),
+                    }
+                }
+                for id in ids.into_iter().rev() {
+                    ack_tx.send(Ack { id }).await.unwrap();
+                }
+            };
+            let request = async {
+                requester.send(Message::Ping).await.unwrap();
+                trpl::join!(requester.request("first"), requester.request("second"))
+            };
+            let ((first, second), ()) = trpl::join!(request, respond);
+            assert_eq!((Ok(0), Ok(1)), (first, second));
+        });
+    }
+
+    #[test]
+    fn disconnected() {
+        trpl::run(async {
+            let (tx, rx) = bounded_channel(1);
+            let (_ack_tx, acks) = bounded_channel(1);
+            drop(rx);
+            let requester = Requester::new(tx, acks);
+            assert_eq!(Err(Disconnected), requester.request("hello?").await);
+
+            // it arrives, but nothing ever answers
+            let (tx, _rx) = bounded_channel(1);
+            let (ack_tx, acks) = bounded_channel(1);
+            drop(ack_tx);
+            let requester = Requester::new(tx, acks);
+            assert_eq!(Err(Disconnected), requester.request("hello?").await);
+        });
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+// how hard `retry` tries
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    // including the first, so 1 never retries. 0 still tries once
+    pub max_attempts: u32,
+    // the wait after the first failure, which doubles after each one after that
+    pub base_delay: Duration,
+    // up to this much more on top of each wait, picked at random, so that lots of tasks that
+    // failed together don't all come back at the same moment and fail together again
+    pub jitter: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            jitter: Duration::ZERO,
+        }
+    }
+}
+
+impl RetryPolicy {
+    // how long to wait after the `failures`th failure in a row
+    pub fn delay(&self, failures: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(1 << failures.saturating_sub(1).min(31));
+        backoff.saturating_add(self.jitter.mul_f64(random_fraction()))
+    }
+}
+
+// calls `op` until it works or it's been tried `policy.max_attempts` times, sleeping for longer
+// and longer in between. what it worked out as, or its last error
+pub async fn retry<T, E, F, Fut>(mut op: F, policy: RetryPolicy) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut failures = 0;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                failures += 1;
+                if failures >= policy.max_attempts {
+                    return Err(e);
+                }
+                trpl::sleep(policy.delay(failures)).await;
+            }
+        }
+    }
+}
+
+// somewhere from 0 to 1. there's no rand here, but every RandomState is seeded differently, which
+// is plenty random for spreading out retries
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    random as f64 / u64::MAX as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::Cell, time::Instant};
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(10),
+            jitter: Duration::ZERO,
+        }
+    }
+
+    // fails until it's been called `works_on` times
+    fn flaky(calls: &Cell<u32>, works_on: u32) -> impl Future<Output = Result<u32, String>> {
+        let call = calls.get() + 1;
+        calls.set(call);
+        async move {
+            if call < works_on {
+                Err(format!("failed on call {call}"))
+            } else {
+                Ok(call)
+            }
+        }
+    }
+
+    #[test]
+    fn works_eventually() {
+        trpl::run(async {
+            let calls = Cell::new(0);
+            let start = Instant::now();
+            assert_eq!(Ok(3), retry(|| flaky(&calls, 3), policy(5)).await);
+            assert_eq!(3, calls.get());
+            // 10ms after the first failure, then 20ms after the second
+            assert!(start.elapsed() >= Duration::from_millis(30));
+        });
+    }
+
+    #[test]
+    fn gives_up() {
+        trpl::run(async {
+            let calls = Cell::new(0);
+            assert_eq!(
+                Err("failed on call 4".to_string()),
+                retry(|| flaky(&calls, 10), policy(4)).await
+            );
+            assert_eq!(4, calls.get());
+
+            let calls = Cell::new(0);
+            assert!(retry(|| flaky(&calls, 2), policy(0)).await.is_err());
+            assert_eq!(1, calls.get());
+        });
+    }
+
+    #[test]
+    fn delays() {
+        let policy = policy(10);
+        assert_eq!(
+            [10, 20, 40, 80].map(Duration::from_millis),
+            [1, 2, 3, 4].map(|failures| policy.delay(failures))
+        );
+        let jittery = RetryPolicy {
+            jitter: Duration::from_millis(5),
+            ..policy
+        };
+        for _ in 0..100 {
+            let delay = jittery.delay(2);
+            assert!(delay >= Duration::from_millis(20) && delay <= Duration::from_millis(25));
+        }
+        // doubling forever doesn't overflow, it just stops at the longest wait there is
+        let forever = RetryPolicy {
+            base_delay: Duration::MAX,
+            ..policy
+        };
+        assert_eq!(Duration::MAX, forever.delay(u32::MAX));
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    task::Poll,
+    time::Duration,
+};
+use trpl::Either;
+
+// whichever of `a` and `b` finishes first, and the other one gets dropped. like trpl::race, it
+// isn't fair: `a` gets polled first every time, so if both are ready at once `a` wins
+pub async fn race<A: Future, B: Future>(a: A, b: B) -> Either<A::Output, B::Output> {
+    let mut a = pin!(a);
+    let mut b = pin!(b);
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = a.as_mut().poll(cx) {
+            return Poll::Ready(Either::Left(output));
+        }
+        if let Poll::Ready(output) = b.as_mut().poll(cx) {
+            return Poll::Ready(Either::Right(output));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+// what `future` finished with, or how long it was given if it took longer than that
+pub async fn timeout<F: Future>(future: F, max_time: Duration) -> Result<F::Output, Duration> {
+    match race(future, trpl::sleep(max_time)).await {
+        Either::Left(output) => Ok(output),
+        Either::Right(()) => Err(max_time),
+    }
+}
+
+// the first of `futures` to finish: what it finished with, where it was in the Vec, and the rest
+// of them (still in order) to carry on with. they have to be Unpin to be polled from a Vec, which a
+// Pin<Box<...>> or Pin<&mut ...> always is
+//
+// panics if `futures` is empty, since there'd be nothing to ever finish
+pub async fn select_all<F: Future + Unpin>(mut futures: Vec<F>) -> (F::Output, usize, Vec<F>) {
+    assert!(!futures.is_empty(), "select_all needs at least one future");
+    let (output, index) = poll_fn(|cx| {
+        for (index, future) in futures.iter_mut().enumerate() {
+            if let Poll::Ready(output) = Pin::new(future).poll(cx) {
+                return Poll::Ready((output, index));
+            }
+        }
+        Poll::Pending
+    })
+    .await;
+    futures.remove(index);
+    (output, index, futures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_one_wins() {
+        trpl::run(async {
+            let slow = async {
+                trpl::sleep(Duration::from_millis(50)).await;
+                "slow"
+            };
+            let fast = async { 42 };
+            assert!(matches!(race(slow, fast).await, Either::Right(42)));
+        });
+    }
+
+    #[test]
+    fn ties_go_left() {
+        trpl::run(async {
+            assert!(matches!(
+                race(async { 1 }, async { 2 }).await,
+                Either::Left(1)
+            ));
+        });
+    }
+
+    #[test]
+    fn timeouts() {
+        trpl::run(async {
+            let slow = trpl::sleep(Duration::from_secs(5));
+            let limit = Duration::from_millis(10);
+            assert_eq!(Err(limit), timeout(slow, limit).await);
+            assert_eq!(Ok("quick"), timeout(async { "quick" }, limit).await);
+        });
+    }
+
+    #[test]
+    fn select_all_keeps_the_rest() {
+        trpl::run(async {
+            let futures: Vec<Pin<Box<dyn Future<Output = u64>>>> = [30, 10, 20]
+                .into_iter()
+                .map(|ms| {
+                    Box::pin(async move {
+                        trpl::sleep(Duration::from_millis(ms)).await;
+                        ms
+                    }) as Pin<Box<dyn Future<Output = u64>>>
+                })
+                .collect();
+
+            let (first, index, rest) = select_all(futures).await;
+            assert_eq!((10, 1, 2), (first, index, rest.len()));
+            let (second, index, rest) = select_all(rest).await;
+            assert_eq!((20, 1, 1), (second, index, rest.len()));
+            let (third, _, rest) = select_all(rest).await;
+            assert_eq!(30, third);
+            assert!(rest.is_empty());
+        });
+    }
+}
@@ -0,0 +1,105 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+// tells every task holding a clone of it that it's time to stop. triggering it is once and for
+// all: it can't be taken back, and anything that listens afterwards hears it straight away
+#[derive(Clone, Default)]
+pub struct ShutdownSignal {
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Default)]
+struct Shared {
+    triggered: bool,
+    // everyone waiting to hear about it
+    wakers: Vec<Waker>,
+}
+
+impl ShutdownSignal {
+    pub fn new() -> ShutdownSignal {
+        ShutdownSignal::default()
+    }
+
+    pub fn trigger(&self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.triggered = true;
+        for waker in shared.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.shared.lock().unwrap().triggered
+    }
+
+    // a future that finishes once the signal's been triggered. race it against whatever a task is
+    // waiting on to stop waiting as soon as there's no point
+    pub fn listen(&self) -> Listener {
+        Listener {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+pub struct Listener {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Future for Listener {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.triggered {
+            return Poll::Ready(());
+        }
+        if !shared.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            shared.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::race;
+    use std::time::Duration;
+    use trpl::Either;
+
+    #[test]
+    fn wakes_listeners() {
+        trpl::run(async {
+            let signal = ShutdownSignal::new();
+            let trigger = signal.clone();
+            let (first, second, ()) = trpl::join!(
+                race(signal.listen(), trpl::sleep(Duration::from_secs(10))),
+                race(signal.listen(), trpl::sleep(Duration::from_secs(10))),
+                async {
+                    trpl::sleep(Duration::from_millis(10)).await;
+                    trigger.trigger();
+                }
+            );
+            assert!(matches!(first, Either::Left(())));
+            assert!(matches!(second, Either::Left(())));
+        });
+    }
+
+    #[test]
+    fn stays_triggered() {
+        trpl::run(async {
+            let signal = ShutdownSignal::new();
+            assert!(!signal.is_triggered());
+            let quiet = race(signal.listen(), trpl::sleep(Duration::from_millis(10))).await;
+            assert!(matches!(quiet, Either::Right(())));
+            signal.clone().trigger();
+            assert!(signal.is_triggered());
+            // too late to be woken, but not to hear about it
+            signal.listen().await;
+        });
+    }
+}
@@ -0,0 +1,196 @@
+use crate::channel::BoundedReceiver;
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use trpl::Stream;
+
+// a bounded channel's receiver as a stream of everything sent on it, which ends once the senders
+// are all gone
+pub struct ReceiverStream<T> {
+    rx: BoundedReceiver<T>,
+}
+
+impl<T> ReceiverStream<T> {
+    pub fn new(rx: BoundedReceiver<T>) -> ReceiverStream<T> {
+        ReceiverStream { rx }
+    }
+
+    pub fn into_inner(self) -> BoundedReceiver<T> {
+        self.rx
+    }
+}
+
+impl<T> From<BoundedReceiver<T>> for ReceiverStream<T> {
+    fn from(rx: BoundedReceiver<T>) -> ReceiverStream<T> {
+        ReceiverStream::new(rx)
+    }
+}
+
+impl<T> Stream for ReceiverStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+// the few combinators the examples need, for any stream. named so they don't get mixed up with
+// trpl::StreamExt's, which has all of these and more: import one or the other, not both
+pub trait Combinators: Stream {
+    fn map<T, F: FnMut(Self::Item) -> T>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+    {
+        Map { stream: self, f }
+    }
+
+    // only the items `keep` says yes to
+    fn filter<F: FnMut(&Self::Item) -> bool>(self, keep: F) -> Filter<Self, F>
+    where
+        Self: Sized,
+    {
+        Filter { stream: self, keep }
+    }
+
+    // the first `n` items, then it ends whether this one has or not
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take {
+            stream: self,
+            left: n,
+        }
+    }
+
+    fn next(&mut self) -> impl Future<Output = Option<Self::Item>>
+    where
+        Self: Unpin,
+    {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx))
+    }
+
+    // runs `f` on each item, one after the other, waiting for each to finish before taking the
+    // next one. a slow `f` slows the whole stream down, all the way back to whatever's sending
+    fn for_each<F, Fut>(mut self, mut f: F) -> impl Future<Output = ()>
+    where
+        Self: Sized + Unpin,
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        async move {
+            while let Some(item) = self.next().await {
+                f(item).await;
+            }
+        }
+    }
+}
+
+impl<S: Stream + ?Sized> Combinators for S {}
+
+pub struct Map<S, F> {
+    stream: S,
+    f: F,
+}
+
+pub struct Filter<S, F> {
+    stream: S,
+    keep: F,
+}
+
+pub struct Take<S> {
+    stream: S,
+    left: usize,
+}
+
+// the closures are never pinned, only the streams, so these can move whenever their stream can
+impl<S: Unpin, F> Unpin for Map<S, F> {}
+impl<S: Unpin, F> Unpin for Filter<S, F> {}
+
+impl<S: Stream + Unpin, F: FnMut(S::Item) -> T, T> Stream for Map<S, F> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = &mut *self;
+        Pin::new(&mut this.stream)
+            .poll_next(cx)
+            .map(|item| item.map(&mut this.f))
+    }
+}
+
+impl<S: Stream + Unpin, F: FnMut(&S::Item) -> bool> Stream for Filter<S, F> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        let this = &mut *self;
+        // keeps going through whatever's ready until one's kept, or there's nothing ready
+        loop {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) if !(this.keep)(&item) => continue,
+                other => return other,
+            }
+        }
+    }
+}
+
+impl<S: Stream + Unpin> Stream for Take<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        if self.left == 0 {
+            return Poll::Ready(None);
+        }
+        let next = Pin::new(&mut self.stream).poll_next(cx);
+        if let Poll::Ready(Some(_)) = next {
+            self.left -= 1;
+        }
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_channel;
+
+    #[test]
+    fn combinators() {
+        trpl::run(async {
+            let (tx, rx) = bounded_channel(10);
+            for n in 1..=10 {
+                tx.send(n).await.unwrap();
+            }
+            drop(tx);
+            let mut stream = ReceiverStream::new(rx)
+                .filter(|n| n % 2 == 0)
+                .map(|n| n * 10)
+                .take(3);
+            let mut received = Vec::new();
+            while let Some(n) = stream.next().await {
+                received.push(n);
+            }
+            assert_eq!(vec![20, 40, 60], received);
+        });
+    }
+
+    #[test]
+    fn ends_with_the_senders() {
+        trpl::run(async {
+            let (tx, rx) = bounded_channel(1);
+            let send = async move {
+                for word in ["a", "b", "c"] {
+                    tx.send(word).await.unwrap();
+                }
+            };
+            let mut received = Vec::new();
+            let receive = ReceiverStream::new(rx).for_each(|word| {
+                received.push(word);
+                async {}
+            });
+            trpl::join!(send, receive);
+            assert_eq!(vec!["a", "b", "c"], received);
+        });
+    }
+}
@@ -0,0 +1,195 @@
+use crate::channel::{BoundedSender, SendError};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+use trpl::Stream;
+
+// lets at most `limit` messages through in any `window`, and smooths out the rest: a token bucket
+// that holds `limit` tokens, takes one for each message, and gets one back every `window / limit`.
+// a burst straight after a quiet spell goes through at once, as far as the tokens go, and after
+// that they're let through one at a time as the tokens come back
+//
+// wraps a sender, so `send` waits its turn, or a stream, which hands its items out no faster
+pub struct Throttle<S> {
+    inner: S,
+    bucket: Bucket,
+    // what a stream's waiting on before it can hand out the next item
+    delay: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+}
+
+impl<S> Throttle<S> {
+    // panics if `limit` or `window` is zero, since then nothing would ever get through
+    pub fn new(inner: S, limit: u32, window: Duration) -> Throttle<S> {
+        Throttle {
+            inner,
+            bucket: Bucket::new(limit, window, Instant::now()),
+            delay: None,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<T> Throttle<BoundedSender<T>> {
+    // waits for a token before sending, as well as for room in the channel
+    pub async fn send(&mut self, value: T) -> Result<(), SendError<T>> {
+        while let Some(wait) = self.bucket.wait(Instant::now()) {
+            trpl::sleep(wait).await;
+        }
+        self.bucket.take();
+        self.inner.send(value).await
+    }
+}
+
+impl<S: Stream + Unpin> Stream for Throttle<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(delay) = &mut this.delay {
+                ready!(delay.as_mut().poll(cx));
+                this.delay = None;
+            }
+            match this.bucket.wait(Instant::now()) {
+                Some(wait) => this.delay = Some(Box::pin(trpl::sleep(wait))),
+                None => break,
+            }
+        }
+        // only an item that's actually there uses up a token
+        let next = Pin::new(&mut this.inner).poll_next(cx);
+        if let Poll::Ready(Some(_)) = next {
+            this.bucket.take();
+        }
+        next
+    }
+}
+
+struct Bucket {
+    limit: u32,
+    // how long it takes for one token to come back
+    per_token: Duration,
+    tokens: u32,
+    // when the last token came back, which the next one's counted from
+    refilled: Instant,
+}
+
+impl Bucket {
+    fn new(limit: u32, window: Duration, now: Instant) -> Bucket {
+        assert!(limit > 0, "a throttle has to let something through");
+        assert!(
+            !window.is_zero(),
+            "a throttle needs a window longer than nothing"
+        );
+        Bucket {
+            limit,
+            // at least a nanosecond, so tokens can't come back infinitely fast
+            per_token: (window / limit).max(Duration::from_nanos(1)),
+            tokens: limit,
+            refilled: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let back =
+            now.saturating_duration_since(self.refilled).as_nanos() / self.per_token.as_nanos();
+        if self.tokens as u128 + back >= self.limit as u128 {
+            // full, and anything past that is wasted, so it starts counting again from now
+            self.tokens = self.limit;
+            self.refilled = now;
+        } else {
+            self.tokens += back as u32;
+            self.refilled += self.per_token * back as u32;
+        }
+    }
+
+    // None when there's a token now, or how long until there is one
+    fn wait(&mut self, now: Instant) -> Option<Duration> {
+        self.refill(now);
+        if self.tokens > 0 {
+            None
+        } else {
+            Some((self.refilled + self.per_token).saturating_duration_since(now))
+        }
+    }
+
+    // only once `wait` has said there's one
+    fn take(&mut self) {
+        self.tokens -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bounded_channel, Combinators, ReceiverStream};
+
+    #[test]
+    fn bucket() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        let mut bucket = Bucket::new(3, Duration::from_millis(300), start);
+        for _ in 0..3 {
+            assert_eq!(None, bucket.wait(start));
+            bucket.take();
+        }
+        assert_eq!(Some(Duration::from_millis(100)), bucket.wait(start));
+        assert_eq!(Some(Duration::from_millis(40)), bucket.wait(ms(60)));
+        assert_eq!(None, bucket.wait(ms(100)));
+        bucket.take();
+        // one at 200ms, and the one due at 300ms isn't here yet
+        assert_eq!(None, bucket.wait(ms(250)));
+        bucket.take();
+        assert_eq!(Some(Duration::from_millis(50)), bucket.wait(ms(250)));
+        // it only ever fills up to the limit, however long it's left
+        bucket.wait(ms(10_000));
+        for _ in 0..3 {
+            bucket.take();
+        }
+        assert!(bucket.wait(ms(10_000)).is_some());
+    }
+
+    #[test]
+    fn throttled_sender() {
+        trpl::run(async {
+            let (tx, mut rx) = bounded_channel(10);
+            let mut tx = Throttle::new(tx, 2, Duration::from_millis(40));
+            let start = Instant::now();
+            for n in 0..6 {
+                tx.send(n).await.unwrap();
+            }
+            // two straight away, then one every 20ms
+            assert!(start.elapsed() >= Duration::from_millis(80));
+            drop(tx);
+            let mut received = Vec::new();
+            while let Some(n) = rx.recv().await {
+                received.push(n);
+            }
+            assert_eq!(vec![0, 1, 2, 3, 4, 5], received);
+        });
+    }
+
+    #[test]
+    fn throttled_stream() {
+        trpl::run(async {
+            let (tx, rx) = bounded_channel(10);
+            for n in 0..5 {
+                tx.send(n).await.unwrap();
+            }
+            drop(tx);
+            let mut stream = Throttle::new(ReceiverStream::new(rx), 3, Duration::from_millis(60));
+            let start = Instant::now();
+            let mut received = Vec::new();
+            while let Some(n) = stream.next().await {
+                received.push(n);
+            }
+            assert!(start.elapsed() >= Duration::from_millis(40));
+            assert_eq!(vec![0, 1, 2, 3, 4], received);
+        });
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * What can be typed in the middle of a game: a guess, or one of a few words.
+ *
+ *     42        guess 42 (or whatever the secret is, like a word)
+ *     hint      find out something about the secret, for a price
+ *     history   every guess so far, and how it went
+ *     quit      stop playing
+ *
+ * Words can be typed in any case. In a game of words they can't be guessed, since they'd be taken
+ * for the command.
+ */
+
+use crate::Secret;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command<T = u32> {
+    Guess(T),
+    Hint,
+    History,
+    Quit,
+}
+
+impl<T: Secret> FromStr for Command<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Command<T>, String> {
+        let s = s.trim();
+        match s.to_lowercase().as_str() {
+            "hint" => Ok(Command::Hint),
+            "history" => Ok(Command::History),
+            "quit" | "exit" => Ok(Command::Quit),
+            _ => match s.parse() {
+                Ok(guess) => Ok(Command::Guess(guess)),
+                Err(_) => Err(format!(
+                    "'{}' isn't a {}, or hint, history, or quit",
+                    s,
+                    T::NAME
+                )),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words::Word;
+
+    #[test]
+    fn commands() {
+        assert_eq!(Ok(Command::Guess(42)), " 42\n".parse());
+        assert_eq!(Ok(Command::Hint), "HINT".parse::<Command>());
+        assert_eq!(Ok(Command::History), "history".parse::<Command>());
+        assert_eq!(Ok(Command::Quit), "Exit".parse::<Command>());
+        assert_eq!(
+            Err("'-3' isn't a number, or hint, history, or quit".to_string()),
+            "-3".parse::<Command>()
+        );
+        assert!("".parse::<Command>().is_err());
+        assert_eq!(
+            Ok(Command::Guess("apple".parse().unwrap())),
+            "Apple".parse::<Command<Word>>()
+        );
+        assert_eq!(Ok(Command::Quit), "quit".parse::<Command<Word>>());
+        assert_eq!(
+            Err("'x1' isn't a word, or hint, history, or quit".to_string()),
+            "x1".parse::<Command<Word>>()
+        );
+    }
+}
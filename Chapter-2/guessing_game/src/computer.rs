@@ -0,0 +1,197 @@
+/*
+ * The game the other way around: you think of a number and the computer finds it, by guessing the
+ * middle of whatever's left and halving it with every answer. Each "higher" moves the bottom of
+ * the range up past the guess, and each "lower" moves the top down below it.
+ *
+ * If the answers ever leave nothing in between (higher than 50 but lower than 51, say), somebody's
+ * not being straight with it, and it says which two answers don't add up instead of carrying on.
+ */
+
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Answer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Answer, String> {
+        match s.trim().to_lowercase().as_str() {
+            "h" | "higher" => Ok(Answer::Higher),
+            "l" | "lower" => Ok(Answer::Lower),
+            "c" | "correct" => Ok(Answer::Correct),
+            other => Err(format!("'{}' isn't h, l, or c", other)),
+        }
+    }
+}
+
+// the answers that can't all be true. `above` is the biggest number the secret was said to be
+// higher than and `below` the smallest it was said to be lower than. either can be the edge of the
+// range instead, when there's no answer on that side
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contradiction {
+    pub above: Bound,
+    pub below: Bound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    Answer(u32),
+    // the secret is at least (or at most) this, because that's the range
+    Edge(u32),
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.above, self.below) {
+            (Bound::Answer(above), Bound::Answer(below)) => write!(
+                f,
+                "you said it was higher than {} and lower than {}, and there's nothing in between",
+                above, below
+            ),
+            (Bound::Answer(above), Bound::Edge(max)) => write!(
+                f,
+                "you said it was higher than {}, but it's meant to be {} at most",
+                above, max
+            ),
+            (Bound::Edge(min), Bound::Answer(below)) => write!(
+                f,
+                "you said it was lower than {}, but it's meant to be {} at least",
+                below, min
+            ),
+            // the range itself is never empty
+            (Bound::Edge(min), Bound::Edge(max)) => {
+                write!(f, "there's nothing from {} to {}", min, max)
+            }
+        }
+    }
+}
+
+impl Error for Contradiction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guesser {
+    above: Bound,
+    below: Bound,
+    // answered so far. one that didn't add up doesn't count
+    pub guesses: u32,
+}
+
+impl Guesser {
+    // for a number from 1 to `max`
+    pub fn new(max: u32) -> Guesser {
+        Guesser {
+            above: Bound::Edge(1),
+            below: Bound::Edge(max),
+            guesses: 0,
+        }
+    }
+
+    // what's left, from lowest to highest. empty once the answers contradict each other
+    fn range(&self) -> (u32, u32) {
+        let low = match self.above {
+            Bound::Answer(n) => n + 1,
+            Bound::Edge(n) => n,
+        };
+        let high = match self.below {
+            Bound::Answer(n) => n.saturating_sub(1),
+            Bound::Edge(n) => n,
+        };
+        (low, high)
+    }
+
+    // the middle of what's left
+    pub fn guess(&self) -> u32 {
+        let (low, high) = self.range();
+        low + (high - low) / 2
+    }
+
+    // narrows things down after `answer` about `guess`. fails if that leaves nothing to guess
+    pub fn answer(&mut self, guess: u32, answer: Answer) -> Result<(), Contradiction> {
+        let mut next = self.clone();
+        match answer {
+            Answer::Higher => next.above = Bound::Answer(guess),
+            Answer::Lower => next.below = Bound::Answer(guess),
+            Answer::Correct => {}
+        }
+        let (low, high) = next.range();
+        if low > high {
+            // and `self` stays as it was, so the guess can be asked again
+            return Err(Contradiction {
+                above: next.above,
+                below: next.below,
+            });
+        }
+        next.guesses += 1;
+        *self = next;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    // plays a whole game against `secret`, answering honestly
+    fn find(secret: u32, max: u32) -> u32 {
+        let mut guesser = Guesser::new(max);
+        loop {
+            let guess = guesser.guess();
+            let answer = match secret.cmp(&guess) {
+                Ordering::Less => Answer::Lower,
+                Ordering::Greater => Answer::Higher,
+                Ordering::Equal => Answer::Correct,
+            };
+            guesser.answer(guess, answer).unwrap();
+            if answer == Answer::Correct {
+                return guesser.guesses;
+            }
+        }
+    }
+
+    #[test]
+    fn finds_anything() {
+        for secret in 1..=100 {
+            assert!(find(secret, 100) <= 7, "took too long to find {}", secret);
+        }
+        assert_eq!(1, find(50, 100));
+        assert!(find(1, 1000) <= 10 && find(1000, 1000) <= 10);
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(Ok(Answer::Higher), " H ".parse());
+        assert_eq!(Ok(Answer::Correct), "correct".parse());
+        assert!("maybe".parse::<Answer>().is_err());
+    }
+
+    #[test]
+    fn contradictions() {
+        let mut guesser = Guesser::new(100);
+        guesser.answer(50, Answer::Higher).unwrap();
+        guesser.answer(52, Answer::Lower).unwrap();
+        assert_eq!(51, guesser.guess());
+        let wrong = guesser.answer(51, Answer::Lower).unwrap_err();
+        assert_eq!(
+            "you said it was higher than 50 and lower than 51, and there's nothing in between",
+            wrong.to_string()
+        );
+        // nothing changed, so it can go on with a better answer
+        assert_eq!((51, 2), (guesser.guess(), guesser.guesses));
+
+        let mut guesser = Guesser::new(100);
+        assert_eq!(
+            "you said it was higher than 100, but it's meant to be 100 at most",
+            guesser.answer(100, Answer::Higher).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "you said it was lower than 1, but it's meant to be 1 at least",
+            guesser.answer(1, Answer::Lower).unwrap_err().to_string()
+        );
+    }
+}
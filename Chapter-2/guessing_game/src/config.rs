@@ -0,0 +1,417 @@
+/*
+ * How a game is set up, from the command line:
+ *
+ *     guessing_game --difficulty hard
+ *     guessing_game --max 1000 --attempts 5
+ *
+ * The difficulty picks a range for the secret number (1-50, 1-100, or 1-1000) and how many guesses
+ * you get to find it. `--max` changes the top of the range whatever the difficulty, and the number
+ * of guesses goes along with it: it's always however many it takes to find any number in the range
+ * by halving it every time, plus a few to spare on easy and a couple on normal. On hard there's
+ * nothing to spare. `--attempts` sets the number of guesses outright.
+ *
+ * `--hints` says whether each wrong guess is warmer or colder than the one before it, and
+ * `--timed` puts the game against the clock: two minutes on easy, one on normal, and 45 seconds on
+ * hard.
+ *
+ * `--computer` turns the game around: you think of the number, and the computer guesses.
+ * `--hotseat` is for two people at one keyboard, one picking the number and the other guessing.
+ * `--words` plays with a word instead of a number. The player doesn't know which words the secret
+ * could be, so it gets twice as many guesses as halving the list it's picked from would take.
+ *
+ * `--daily` is the daily challenge: the same secret for everybody all day, picked with the date.
+ * It's one game a day, so it doesn't go with `--seed`.
+ *
+ * `--host [address]` holds the secret for players somewhere else, who play with
+ * `--join <address>`. The address is 127.0.0.1:7878 when there isn't one.
+ *
+ * `--debug` shows the secret as soon as it's picked, which is handy for trying things out and no
+ * good for anything else, so those games don't count for high scores. `--seed <number>` picks the
+ * same secrets in the same order every time it's given the same number.
+ */
+
+use crate::{net, words};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{fmt, str::FromStr, time::Duration};
+
+// in order, easiest first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    // what a win is worth, per guess left over
+    pub fn points(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Normal => 20,
+            Difficulty::Hard => 50,
+        }
+    }
+
+    fn highest(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 50,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 1000,
+        }
+    }
+
+    // how long a timed game gets
+    fn time_limit(&self) -> Duration {
+        Duration::from_secs(match self {
+            Difficulty::Easy => 120,
+            Difficulty::Normal => 60,
+            Difficulty::Hard => 45,
+        })
+    }
+
+    // guesses to spare, over the fewest that always find it
+    fn spare(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 2,
+            Difficulty::Hard => 0,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        // so `{:<6}` lines names up
+        f.pad(name)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!(
+                "there's no {} difficulty, try easy, normal, or hard",
+                s
+            )),
+        }
+    }
+}
+
+// who's guessing
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Mode {
+    #[default]
+    Player,
+    // one player picks the number for the other
+    Hotseat,
+    Computer,
+    // guessing a word instead
+    Words,
+    // one game, with a secret picked by the date
+    Daily,
+    // picking the number for players who connect to this address
+    Host(String),
+    // playing against whoever's hosting at this address
+    Join(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameConfig {
+    pub mode: Mode,
+    pub difficulty: Difficulty,
+    // the secret number is from 1 to this
+    pub max: u32,
+    pub attempts: u32,
+    // warmer or colder after every wrong guess
+    pub hints: bool,
+    // None for all the time in the world
+    pub time_limit: Option<Duration>,
+    // shows the secret, which is cheating
+    pub debug: bool,
+    // where the secrets come from. None for somewhere different every time
+    pub seed: Option<u64>,
+}
+
+impl Default for GameConfig {
+    fn default() -> GameConfig {
+        GameConfig::new(Difficulty::default(), None, None)
+    }
+}
+
+impl GameConfig {
+    // `max` and `attempts` override what the difficulty would have picked
+    pub fn new(difficulty: Difficulty, max: Option<u32>, attempts: Option<u32>) -> GameConfig {
+        let max = max.unwrap_or(difficulty.highest());
+        GameConfig {
+            mode: Mode::default(),
+            difficulty,
+            max,
+            attempts: attempts.unwrap_or(halvings(max) + difficulty.spare()),
+            hints: false,
+            time_limit: None,
+            debug: false,
+            seed: None,
+        }
+    }
+
+    // what to pick secrets with: the same ones every time for the same seed
+    pub fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        }
+    }
+
+    // whether the secret is from the difficulty's own range, so the number of guesses it took can
+    // be compared with other games on the same difficulty
+    pub fn standard(&self) -> bool {
+        self.max == self.difficulty.highest()
+    }
+
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<GameConfig, String> {
+        let mut difficulty = Difficulty::default();
+        let mut max = None;
+        let mut attempts = None;
+        let mut hints = false;
+        let mut timed = false;
+        let mut debug = false;
+        let mut seed = None;
+        let mut mode = Mode::default();
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--hints" => hints = true,
+                "--timed" => timed = true,
+                "--debug" => debug = true,
+                "--seed" => {
+                    let value = value(&mut args, &arg)?;
+                    match value.parse() {
+                        Ok(n) => seed = Some(n),
+                        Err(_) => return Err(format!("--seed needs a number, not {}", value)),
+                    }
+                }
+                "--computer" => mode = Mode::Computer,
+                "--hotseat" => mode = Mode::Hotseat,
+                "--words" => mode = Mode::Words,
+                "--daily" => mode = Mode::Daily,
+                "--host" => {
+                    let address = args.next_if(|a| !a.starts_with("--"));
+                    mode = Mode::Host(address.unwrap_or(net::DEFAULT_ADDRESS.to_string()));
+                }
+                "--join" => mode = Mode::Join(value(&mut args, &arg)?),
+                "--difficulty" => difficulty = value(&mut args, &arg)?.parse()?,
+                "--max" => max = Some(number(&mut args, &arg, 2)?),
+                "--attempts" => attempts = Some(number(&mut args, &arg, 1)?),
+                _ => return Err(format!("there's no {} option", arg)),
+            }
+        }
+        if mode == Mode::Words {
+            if max.is_some() {
+                return Err("--max doesn't go with --words".to_string());
+            }
+            let words = words::WORDS.len() as u32;
+            attempts = attempts.or(Some(2 * halvings(words) + difficulty.spare()));
+        }
+        if mode == Mode::Daily && seed.is_some() {
+            return Err("--seed doesn't go with --daily".to_string());
+        }
+        Ok(GameConfig {
+            mode,
+            hints,
+            debug,
+            seed,
+            time_limit: timed.then(|| difficulty.time_limit()),
+            ..GameConfig::new(difficulty, max, attempts)
+        })
+    }
+}
+
+// whatever comes after `option`
+fn value(args: &mut impl Iterator<Item = String>, option: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("{} needs something after it", option))
+}
+
+// a number of at least `least` after `option`
+fn number(
+    args: &mut impl Iterator<Item = String>,
+    option: &str,
+    least: u32,
+) -> Result<u32, String> {
+    let value = value(args, option)?;
+    match value.parse() {
+        Ok(n) if n >= least => Ok(n),
+        _ => Err(format!(
+            "{} needs a number of at least {}, not {}",
+            option, least, value
+        )),
+    }
+}
+
+// how many guesses it takes to be sure of finding a number from 1 to `max`, halving what's left
+// every time: the number of bits in `max`
+fn halvings(max: u32) -> u32 {
+    u32::BITS - max.leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn difficulties() {
+        assert_eq!(
+            Ok(GameConfig {
+                difficulty: Difficulty::Normal,
+                max: 100,
+                attempts: 9,
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args(""))
+        );
+        assert_eq!(
+            Ok(GameConfig {
+                difficulty: Difficulty::Easy,
+                max: 50,
+                attempts: 11,
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args("--difficulty easy"))
+        );
+        assert_eq!(
+            Ok(GameConfig {
+                difficulty: Difficulty::Hard,
+                max: 1000,
+                attempts: 10,
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args("--difficulty hard"))
+        );
+    }
+
+    #[test]
+    fn custom_range() {
+        assert_eq!(
+            Ok(GameConfig {
+                difficulty: Difficulty::Hard,
+                max: 1_000_000,
+                attempts: 20,
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args("--max 1000000 --difficulty hard"))
+        );
+        assert_eq!(
+            Ok(GameConfig {
+                difficulty: Difficulty::Normal,
+                max: 100,
+                attempts: 3,
+                hints: true,
+                time_limit: Some(Duration::from_secs(60)),
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args("--attempts 3 --hints --timed"))
+        );
+        assert_eq!(
+            Ok(GameConfig {
+                mode: Mode::Computer,
+                max: 1000,
+                attempts: 12,
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args("--computer --max 1000"))
+        );
+        assert_eq!(
+            Ok(GameConfig {
+                mode: Mode::Hotseat,
+                difficulty: Difficulty::Easy,
+                max: 50,
+                attempts: 11,
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args("--hotseat --difficulty easy"))
+        );
+        assert_eq!(
+            Ok(GameConfig {
+                mode: Mode::Words,
+                difficulty: Difficulty::Hard,
+                max: 1000,
+                attempts: 14,
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args("--words --difficulty hard"))
+        );
+        assert!(GameConfig::from_args(args("--words --max 10")).is_err());
+        assert_eq!(
+            Ok(Mode::Host("127.0.0.1:7878".to_string())),
+            GameConfig::from_args(args("--host --hints")).map(|c| c.mode)
+        );
+        assert_eq!(
+            Ok(Mode::Host("0.0.0.0:9000".to_string())),
+            GameConfig::from_args(args("--host 0.0.0.0:9000")).map(|c| c.mode)
+        );
+        assert_eq!(
+            Ok(Mode::Join("example.com:7878".to_string())),
+            GameConfig::from_args(args("--join example.com:7878")).map(|c| c.mode)
+        );
+        assert!(GameConfig::from_args(args("--join")).is_err());
+        assert_eq!(
+            Ok(GameConfig {
+                debug: true,
+                seed: Some(12345678901),
+                ..GameConfig::default()
+            }),
+            GameConfig::from_args(args("--seed 12345678901 --debug"))
+        );
+        assert!(GameConfig::from_args(args("--seed -1")).is_err());
+        assert!(GameConfig::from_args(args("--seed")).is_err());
+        assert_eq!(
+            Ok(Mode::Daily),
+            GameConfig::from_args(args("--daily --difficulty hard")).map(|c| c.mode)
+        );
+        assert!(GameConfig::from_args(args("--daily --seed 3")).is_err());
+        assert!(GameConfig::from_args(args("--attempts 0")).is_err());
+        assert!(GameConfig::from_args(args("--max 1")).is_err());
+        assert!(GameConfig::from_args(args("--max lots")).is_err());
+        assert!(GameConfig::from_args(args("--difficulty brutal")).is_err());
+        assert!(GameConfig::from_args(args("--max")).is_err());
+        assert!(GameConfig::from_args(args("--loud yes")).is_err());
+    }
+
+    #[test]
+    fn seeds() {
+        use rand::Rng;
+        let seeded = GameConfig {
+            seed: Some(7),
+            ..GameConfig::default()
+        };
+        let numbers = |config: &GameConfig| {
+            let mut rng = config.rng();
+            (0..5).map(|_| rng.gen::<u32>()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(&seeded), numbers(&seeded));
+    }
+
+    #[test]
+    fn enough_guesses() {
+        // 1 to 7 takes 3 (4, then 2 or 6, then whatever's left), and 8 takes one more
+        assert_eq!((3, 4), (halvings(7), halvings(8)));
+        assert_eq!(7, halvings(100));
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ * The daily challenge: one secret a day, the same for everybody. A `Day` is a count of days since
+ * 1 January 1970 in UTC, which makes a seed that everyone's clock agrees on (give or take a
+ * timezone, for whoever's playing around midnight). It's written as a date, 2024-03-09, both on
+ * the screen and in the scores file.
+ *
+ * There's no date library here, so the days are turned into years, months, and days and back by
+ * counting in 400-year cycles, which are always exactly 146097 days long.
+ */
+
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Day(u32);
+
+// days from 1 March 0000 to 1 January 1970. years start in March for this, so that the leap day is
+// the last day of the year
+const EPOCH: i64 = 719_468;
+const CYCLE: i64 = 146_097;
+
+impl Day {
+    pub fn today() -> Day {
+        // a clock from before 1970 is wrong, and the first day is as good a guess as any
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Day((seconds / (24 * 60 * 60)) as u32)
+    }
+
+    // what the day's secret is picked with
+    pub fn seed(&self) -> u64 {
+        self.0 as u64
+    }
+
+    // the year, month, and day of the month
+    fn date(&self) -> (i64, i64, i64) {
+        let days = self.0 as i64 + EPOCH;
+        let cycle = days / CYCLE;
+        let day_of_cycle = days - cycle * CYCLE;
+        let year_of_cycle = (day_of_cycle - day_of_cycle / 1460 + day_of_cycle / 36524
+            - day_of_cycle / 146_096)
+            / 365;
+        let day_of_year =
+            day_of_cycle - (365 * year_of_cycle + year_of_cycle / 4 - year_of_cycle / 100);
+        // counting from March
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_cycle + cycle * 400 + (month <= 2) as i64;
+        (year, month, day)
+    }
+
+    // None for a day before 1970. doesn't check that the month has that many days
+    fn from_date(year: i64, month: i64, day: i64) -> Option<Day> {
+        let year = year - (month <= 2) as i64;
+        let cycle = year.div_euclid(400);
+        let year_of_cycle = year - cycle * 400;
+        let month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * month + 2) / 5 + day - 1;
+        let day_of_cycle =
+            year_of_cycle * 365 + year_of_cycle / 4 - year_of_cycle / 100 + day_of_year;
+        let days = cycle * CYCLE + day_of_cycle - EPOCH;
+        u32::try_from(days).ok().map(Day)
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = self.date();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl FromStr for Day {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Day, String> {
+        let error = || format!("'{}' isn't a date", s);
+        let parts = s
+            .split('-')
+            .map(|part| part.parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| error())?;
+        let [year, month, day] = parts[..] else {
+            return Err(error());
+        };
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(error());
+        }
+        // the 31st of a 30-day month comes out as the 1st of the next one, and then doesn't
+        // match what it was written as
+        match Day::from_date(year, month, day) {
+            Some(d) if d.date() == (year, month, day) => Ok(d),
+            _ => Err(error()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates() {
+        assert_eq!("1970-01-01", Day(0).to_string());
+        assert_eq!("2000-02-29", Day(11_016).to_string());
+        assert_eq!("2000-03-01", Day(11_017).to_string());
+        assert_eq!("2024-03-09", Day(19_791).to_string());
+        assert_eq!(Ok(Day(19_791)), "2024-03-09".parse());
+        // every day there is, near enough, both ways
+        for n in (0..200_000).step_by(7) {
+            assert_eq!(Ok(Day(n)), Day(n).to_string().parse());
+        }
+    }
+
+    #[test]
+    fn not_dates() {
+        for s in [
+            "",
+            "today",
+            "2024-03",
+            "2024-03-09-01",
+            "2024-13-01",
+            "2023-02-29",
+            "2024-04-31",
+            "1969-12-31",
+        ] {
+            assert!(s.parse::<Day>().is_err(), "{}", s);
+        }
+        assert_eq!(Ok(Day(19_782)), "2024-02-29".parse());
+    }
+}
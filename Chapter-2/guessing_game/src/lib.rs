@@ -0,0 +1,529 @@
+/*
+ * The guessing game itself, without any of the typing and printing: a `Game` is handed a secret
+ * (or picks one with whatever random number generator it's given, so a seeded one plays the same
+ * game every time) and takes guesses one at a time, saying how each one compares.
+ *
+ * A game is over when it's won or out of guesses. A win scores the difficulty's points for the
+ * guess that got it and for every guess that was left over, so getting it first try on normal with
+ * 9 guesses is worth 9 * 20 = 180 and getting it on the very last guess is worth 20. Running out
+ * of guesses scores nothing.
+ *
+ * Keeping every guess is also what makes hints work: a guess is warmer when it's closer to the
+ * secret than the one before it was, and colder when it's further away.
+ *
+ * A timed game is also over once its time is up, and a win in one scores a bonus for every second
+ * left on the clock, worth a tenth of the difficulty's points. The clock starts when the game is
+ * made and stops at the last guess.
+ *
+ * Clues give away something about the secret (whether it's even, then whether 5 divides it, then
+ * 3), and each one costs a guess's worth of points off the score.
+ *
+ * The secret doesn't have to be a number. Anything that's a `Secret` will do, which mostly means
+ * anything that can be put in order, so that every wrong guess comes either before or after it:
+ * `words::Word` plays the same game with words in dictionary order. Warmer and colder and the
+ * bounds left to guess in are only for numbers, though.
+ */
+
+pub mod command;
+pub mod computer;
+pub mod config;
+pub mod daily;
+pub mod net;
+pub mod render;
+pub mod scores;
+pub mod session;
+pub mod words;
+
+use config::GameConfig;
+use rand::Rng;
+use std::{
+    cmp::Ordering,
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+// what a game can be played with
+pub trait Secret: Ord + Clone + fmt::Display + FromStr {
+    // what to call one, in "'x' isn't a number"
+    const NAME: &'static str;
+    // what a guess that comes before the secret is, and one that comes after it
+    const TOO_SMALL: &'static str;
+    const TOO_BIG: &'static str;
+
+    // the `n`th clue about it, in the order they're given out
+    fn clue(&self, n: usize) -> Option<Clue>;
+
+    // whether it can be guessed in a game set up with `config`, for keeping out guesses that could
+    // never be right. anything that parses can, unless there's a range to be outside of
+    fn check(&self, _config: &GameConfig) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl Secret for u32 {
+    const NAME: &'static str = "number";
+    const TOO_SMALL: &'static str = "Too small";
+    const TOO_BIG: &'static str = "Too big";
+
+    fn clue(&self, n: usize) -> Option<Clue> {
+        match n {
+            0 => Some(Clue::Even(self.is_multiple_of(2))),
+            1 => Some(Clue::DivisibleBy(5, self.is_multiple_of(5))),
+            2 => Some(Clue::DivisibleBy(3, self.is_multiple_of(3))),
+            _ => None,
+        }
+    }
+
+    fn check(&self, config: &GameConfig) -> Result<(), String> {
+        match (1..=config.max).contains(self) {
+            true => Ok(()),
+            false => Err(format!(
+                "Your guess must be between 1 and {}, got {}",
+                config.max, self
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Correct,
+    // the game was already over (or out of time), so the guess didn't count
+    Over,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    Warmer,
+    Colder,
+    // just as far away, from the other side (or the same guess again)
+    Same,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::Warmer => write!(f, "Getting warmer."),
+            Hint::Colder => write!(f, "Getting colder."),
+            Hint::Same => write!(f, "No warmer, no colder."),
+        }
+    }
+}
+
+// something true about the secret
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Clue {
+    Even(bool),
+    DivisibleBy(u32, bool),
+    StartsWith(char),
+    EndsWith(char),
+    Letters(usize),
+}
+
+impl fmt::Display for Clue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Clue::Even(true) => write!(f, "It's even."),
+            Clue::Even(false) => write!(f, "It's odd."),
+            Clue::DivisibleBy(n, true) => write!(f, "It's divisible by {}.", n),
+            Clue::DivisibleBy(n, false) => write!(f, "It isn't divisible by {}.", n),
+            Clue::StartsWith(c) => write!(f, "It starts with '{}'.", c),
+            Clue::EndsWith(c) => write!(f, "It ends with '{}'.", c),
+            Clue::Letters(n) => write!(f, "It has {} letters.", n),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game<T = u32> {
+    config: GameConfig,
+    secret: T,
+    // every guess so far, in order
+    guesses: Vec<T>,
+    started: Instant,
+    // how long it took, once it's over
+    finished: Option<Duration>,
+    // how many have been given out
+    clues: usize,
+}
+
+impl<T: Secret> Game<T> {
+    pub fn new(config: GameConfig, secret: T) -> Game<T> {
+        Game {
+            config,
+            secret,
+            guesses: Vec::new(),
+            started: Instant::now(),
+            finished: None,
+            clues: 0,
+        }
+    }
+
+    // a game whose clock started at `started` instead of now
+    pub fn with_start(mut self, started: Instant) -> Game<T> {
+        self.started = started;
+        self
+    }
+
+    // the game after `guesses`, for tests all over the crate to start from
+    #[cfg(test)]
+    pub(crate) fn after(mut self, guesses: &[T]) -> Game<T> {
+        for guess in guesses {
+            self.guess(guess.clone());
+        }
+        self
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn secret(&self) -> &T {
+        &self.secret
+    }
+
+    pub fn guesses(&self) -> &[T] {
+        &self.guesses
+    }
+
+    pub fn guess(&mut self, guess: T) -> Outcome {
+        if self.over() {
+            return Outcome::Over;
+        }
+        let outcome = compare(&guess, &self.secret);
+        self.guesses.push(guess);
+        if self.over() {
+            self.finished = Some(self.started.elapsed());
+        }
+        outcome
+    }
+
+    // every guess so far, with how it compared
+    pub fn history(&self) -> Vec<(T, Outcome)> {
+        self.guesses
+            .iter()
+            .map(|guess| (guess.clone(), compare(guess, &self.secret)))
+            .collect()
+    }
+
+    // the next clue, which comes off the score. None once they've all been given, or the game's
+    // over
+    pub fn clue(&mut self) -> Option<Clue> {
+        if self.over() {
+            return None;
+        }
+        let clue = self.secret.clue(self.clues)?;
+        self.clues += 1;
+        Some(clue)
+    }
+
+    pub fn clues(&self) -> usize {
+        self.clues
+    }
+
+    // time spent so far, or all told once it's over
+    pub fn elapsed(&self) -> Duration {
+        self.finished.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    // None when the game isn't timed
+    pub fn time_left(&self) -> Option<Duration> {
+        let limit = self.config.time_limit?;
+        Some(limit.saturating_sub(self.elapsed()))
+    }
+
+    pub fn out_of_time(&self) -> bool {
+        !self.won() && self.time_left() == Some(Duration::ZERO)
+    }
+
+    pub fn won(&self) -> bool {
+        self.guesses.last() == Some(&self.secret)
+    }
+
+    // won, or out of guesses or time
+    pub fn over(&self) -> bool {
+        self.won() || self.left() == 0 || self.out_of_time()
+    }
+
+    pub fn left(&self) -> u32 {
+        self.config
+            .attempts
+            .saturating_sub(self.guesses.len() as u32)
+    }
+
+    pub fn score(&self) -> u32 {
+        if !self.won() {
+            return 0;
+        }
+        // saturating, since --attempts and --time can be big enough to overflow a u32
+        let points = self.config.difficulty.points();
+        let bonus = match self.time_left() {
+            Some(left) => {
+                let seconds = u32::try_from(left.as_secs()).unwrap_or(u32::MAX);
+                seconds.saturating_mul(points) / 10
+            }
+            None => 0,
+        };
+        let penalty = (self.clues as u32).saturating_mul(points);
+        (self.left().saturating_add(1))
+            .saturating_mul(points)
+            .saturating_add(bonus)
+            .saturating_sub(penalty)
+    }
+}
+
+impl Game<u32> {
+    // a secret from 1 to the config's max
+    pub fn random(config: GameConfig, rng: &mut impl Rng) -> Game<u32> {
+        let secret = rng.gen_range(1..=config.max);
+        Game::new(config, secret)
+    }
+
+    // the lowest and highest the secret can still be, going by the guesses so far
+    pub fn bounds(&self) -> (u32, u32) {
+        let mut bounds = (1, self.config.max);
+        for (guess, outcome) in self.history() {
+            match outcome {
+                Outcome::TooSmall => bounds.0 = bounds.0.max(guess.saturating_add(1)),
+                Outcome::TooBig => bounds.1 = bounds.1.min(guess.saturating_sub(1)),
+                _ => return (guess, guess),
+            }
+        }
+        bounds
+    }
+
+    // how the last guess compares with the one before it. nothing to compare on the first guess
+    pub fn hint(&self) -> Option<Hint> {
+        let [.., before, last] = self.guesses.as_slice() else {
+            return None;
+        };
+        let (before, last) = (before.abs_diff(self.secret), last.abs_diff(self.secret));
+        Some(match last.cmp(&before) {
+            Ordering::Less => Hint::Warmer,
+            Ordering::Greater => Hint::Colder,
+            Ordering::Equal => Hint::Same,
+        })
+    }
+}
+
+fn compare<T: Ord>(guess: &T, secret: &T) -> Outcome {
+    match guess.cmp(secret) {
+        Ordering::Less => Outcome::TooSmall,
+        Ordering::Greater => Outcome::TooBig,
+        Ordering::Equal => Outcome::Correct,
+    }
+}
+
+// the summary printed when the game's over
+impl<T: Secret> fmt::Display for Game<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let guesses: Vec<String> = self.guesses.iter().map(|g| g.to_string()).collect();
+        match (self.won(), self.config.time_limit) {
+            (true, None) => writeln!(
+                f,
+                "You got it in {} of {} guesses.",
+                self.guesses.len(),
+                self.config.attempts
+            )?,
+            (true, Some(_)) => writeln!(
+                f,
+                "You got it in {} of {} guesses, in {}.",
+                self.guesses.len(),
+                self.config.attempts,
+                clock(self.elapsed())
+            )?,
+            (false, _) if self.out_of_time() => {
+                writeln!(f, "Out of time! It was {}.", self.secret)?
+            }
+            (false, _) => writeln!(f, "Out of guesses! It was {}.", self.secret)?,
+        }
+        writeln!(f, "Guesses: {}", guesses.join(", "))?;
+        match self.clues {
+            0 => write!(f, "Score: {}", self.score()),
+            1 => write!(f, "Score: {} (1 clue)", self.score()),
+            n => write!(f, "Score: {} ({} clues)", self.score(), n),
+        }
+    }
+}
+
+// `duration` as minutes and seconds, like 1:05
+pub fn clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    // a game against 42 with the default 9 guesses, after `guesses`
+    fn game(guesses: &[u32]) -> Game {
+        Game::new(GameConfig::default(), 42).after(guesses)
+    }
+
+    #[test]
+    fn outcomes() {
+        let mut game = game(&[]);
+        assert_eq!(Outcome::TooSmall, game.guess(41));
+        assert_eq!(Outcome::TooBig, game.guess(43));
+        assert!(!game.over());
+        assert_eq!(Outcome::Correct, game.guess(42));
+        assert!(game.won() && game.over());
+        // nothing counts once it's over
+        assert_eq!(Outcome::Over, game.guess(42));
+        assert_eq!(&[41, 43, 42], game.guesses());
+    }
+
+    #[test]
+    fn checks() {
+        let config = GameConfig::default();
+        assert_eq!(Ok(()), 1.check(&config));
+        assert_eq!(Ok(()), 100.check(&config));
+        assert_eq!(
+            Err("Your guess must be between 1 and 100, got 250".to_string()),
+            250.check(&config)
+        );
+        assert!(0.check(&config).is_err());
+    }
+
+    #[test]
+    fn seeded() {
+        let config = GameConfig::default();
+        let first = Game::random(config.clone(), &mut StdRng::seed_from_u64(7));
+        let again = Game::random(config.clone(), &mut StdRng::seed_from_u64(7));
+        assert_eq!(first.secret(), again.secret());
+        assert!((1..=config.max).contains(first.secret()));
+    }
+
+    #[test]
+    fn scoring() {
+        assert_eq!(180, game(&[42]).score());
+        assert_eq!(140, game(&[50, 25, 42]).score());
+        let last_guess = game(&[1, 2, 3, 4, 5, 6, 7, 8, 42]);
+        assert!(last_guess.over());
+        assert_eq!(20, last_guess.score());
+    }
+
+    #[test]
+    fn out_of_guesses() {
+        let mut lost = game(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert!(lost.over() && !lost.won());
+        assert_eq!(0, lost.score());
+        assert_eq!(Outcome::Over, lost.guess(42));
+        assert!(!lost.won());
+        assert!(!game(&[1, 2]).over());
+    }
+
+    #[test]
+    fn clues() {
+        let mut game = game(&[50]);
+        assert_eq!(Some(Clue::Even(true)), game.clue());
+        assert_eq!(Some(Clue::DivisibleBy(5, false)), game.clue());
+        assert_eq!(Some(Clue::DivisibleBy(3, true)), game.clue());
+        assert_eq!(None, game.clue());
+        assert_eq!(3, game.clues());
+        game.guess(42);
+        // 7 guesses left, less 3 clues
+        assert_eq!(100, game.score());
+        assert!(game.to_string().ends_with("Score: 100 (3 clues)"));
+        assert_eq!(None, game.clue());
+        assert_eq!(
+            "It isn't divisible by 5.",
+            Clue::DivisibleBy(5, false).to_string()
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!((1, 100), game(&[]).bounds());
+        assert_eq!((26, 49), game(&[50, 25, 60, 10]).bounds());
+        assert_eq!((42, 42), game(&[50, 42]).bounds());
+        // guesses outside the range don't tell you anything
+        assert_eq!((1, 100), game(&[0, 500]).bounds());
+    }
+
+    #[test]
+    fn history() {
+        assert_eq!(
+            vec![
+                (50, Outcome::TooBig),
+                (25, Outcome::TooSmall),
+                (42, Outcome::Correct)
+            ],
+            game(&[50, 25, 42]).history()
+        );
+    }
+
+    #[test]
+    fn hints() {
+        assert_eq!(None, game(&[]).hint());
+        assert_eq!(None, game(&[50]).hint());
+        assert_eq!(Some(Hint::Warmer), game(&[60, 50]).hint());
+        assert_eq!(Some(Hint::Colder), game(&[60, 50, 90]).hint());
+        // 40 and 44 are both 2 away
+        assert_eq!(Some(Hint::Same), game(&[40, 44]).hint());
+    }
+
+    #[test]
+    fn timed() {
+        let config = GameConfig {
+            time_limit: Some(Duration::from_secs(60)),
+            ..GameConfig::default()
+        };
+        let ago = |seconds| Instant::now() - Duration::from_secs(seconds);
+
+        let mut late = Game::new(config.clone(), 42).with_start(ago(61));
+        assert!(late.out_of_time() && late.over());
+        assert_eq!(Outcome::Over, late.guess(42));
+        assert!(late.to_string().starts_with("Out of time! It was 42."));
+
+        // 2 guesses with 7 left, and 29 or 30 seconds to spare at 2 points each
+        let mut quick = Game::new(config, 42).with_start(ago(30));
+        quick.guess(50);
+        quick.guess(42);
+        assert!(matches!(quick.score(), 218 | 220));
+        let finished = quick.elapsed();
+        assert_eq!(finished, quick.elapsed());
+        assert!(quick
+            .to_string()
+            .starts_with("You got it in 2 of 9 guesses, in 0:30."));
+        // no time limit, no bonus
+        assert_eq!(160, game(&[50, 42]).score());
+    }
+
+    #[test]
+    fn huge_scores() {
+        let config = GameConfig {
+            attempts: u32::MAX,
+            time_limit: Some(Duration::MAX),
+            ..GameConfig::default()
+        };
+        let mut game = Game::new(config, 42);
+        game.guess(42);
+        assert_eq!(u32::MAX, game.score());
+    }
+
+    #[test]
+    fn clocks() {
+        assert_eq!("0:00", clock(Duration::ZERO));
+        assert_eq!("0:59", clock(Duration::from_millis(59_999)));
+        assert_eq!("1:05", clock(Duration::from_secs(65)));
+        assert_eq!("61:40", clock(Duration::from_secs(3700)));
+    }
+
+    #[test]
+    fn summary() {
+        assert_eq!(
+            "You got it in 3 of 9 guesses.\nGuesses: 50, 25, 42\nScore: 140",
+            game(&[50, 25, 42]).to_string()
+        );
+        assert_eq!(
+            "Out of guesses! It was 42.\nGuesses: 1, 2, 3, 4, 5, 6, 7, 8, 9\nScore: 0",
+            game(&[1, 2, 3, 4, 5, 6, 7, 8, 9]).to_string()
+        );
+    }
+}
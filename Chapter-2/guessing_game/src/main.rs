@@ -1,35 +1,518 @@
-use std::{io, cmp::Ordering};
-use rand::{thread_rng, Rng};
+/*
+ * The game at a terminal: everything here is reading what gets typed and printing what happened.
+ * The rules are in the library, in `Game`.
+ */
+
+use guessing_game::{
+    clock,
+    command::Command,
+    computer::{Answer, Guesser},
+    config::{GameConfig, Mode},
+    daily::Day,
+    net::{self, Connection, Reply},
+    render,
+    scores::{self, HighScores},
+    session::SessionStats,
+    words::Word,
+    Game, Outcome, Secret,
+};
+use rand::{rngs::StdRng, Rng};
+use std::{
+    env,
+    io::{self, ErrorKind, IsTerminal, Write},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        mpsc::{self, Receiver},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+
+const USAGE: &str =
+    "usage: guessing_game [--difficulty easy|normal|hard] [--max <number>] [--attempts <number>] [--hints] [--timed] [--debug] [--seed <number>] [--computer | --hotseat | --words | --daily | --host [address] | --join <address>]";
 
 fn main() {
-    let secret_num = thread_rng().gen_range(1..=100);
+    let config = match GameConfig::from_args(env::args().skip(1)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    match config.mode.clone() {
+        Mode::Player => play(config),
+        Mode::Hotseat => hotseat(config),
+        Mode::Computer => computer(config),
+        Mode::Words => words(config),
+        Mode::Daily => daily(config),
+        Mode::Host(address) => host(config, &address),
+        Mode::Join(address) => join(&address),
+    }
+}
+
+// every line typed, read on a thread of its own so that waiting for one can give up when the time
+// runs out
+fn lines() -> &'static Mutex<Receiver<String>> {
+    static LINES: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+    LINES.get_or_init(|| {
+        let (send, receive) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if send.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Mutex::new(receive)
+    })
+}
+
+// a line from stdin, or None once there's nothing more coming
+fn read_line() -> Option<String> {
+    lines().lock().unwrap().recv().ok()
+}
+
+// like `read_line`, but only waits so long. None if nothing came in time
+fn read_line_within(timeout: Option<Duration>) -> Option<String> {
+    match timeout {
+        Some(timeout) => lines().lock().unwrap().recv_timeout(timeout).ok(),
+        None => read_line(),
+    }
+}
+
+fn play(config: GameConfig) {
+    let (mut high_scores, scores_path) = high_scores();
+    println!("{}\n", high_scores.leaderboard());
+    // one for the whole session, so a seed picks the same run of secrets and not the same one
+    // every game
+    let mut rng = config.rng();
+    session(|| {
+        play_one(
+            config.clone(),
+            &mut rng,
+            &mut high_scores,
+            scores_path.as_deref(),
+        )
+    });
+}
+
+fn words(config: GameConfig) {
+    let mut rng = config.rng();
+    session(|| play_words(config.clone(), &mut rng));
+}
+
+// today's challenge, once
+fn daily(config: GameConfig) {
+    let (mut high_scores, scores_path) = high_scores();
+    let today = Day::today();
+    if let Some(won) = high_scores.daily(today) {
+        match won {
+            true => println!("You've already won today's challenge."),
+            false => println!("You've already had a go at today's challenge."),
+        }
+        println!("There's a new one tomorrow.");
+        return;
+    }
+    println!("The daily challenge for {today}.");
+    let config = GameConfig {
+        seed: Some(today.seed()),
+        ..config
+    };
+    let mut rng = config.rng();
+    let debug = config.debug;
+    // giving up partway through counts as a loss, or it could be played again with what was
+    // learned the first time
+    let won = play_one(config, &mut rng, &mut high_scores, scores_path.as_deref())
+        .is_some_and(|game| game.won());
+    // with the answer showing, it doesn't count
+    if debug {
+        return;
+    }
+    high_scores.record_daily(today, won);
+    if let Some(path) = scores_path {
+        if let Err(e) = scores::save(&high_scores, &path) {
+            eprintln!("couldn't save the scores to {}: {}", path.display(), e);
+        }
+    }
+}
+
+// as many games as the player wants, then how they went
+fn session<T: Secret>(mut play_one: impl FnMut() -> Option<Game<T>>) {
+    let mut stats = SessionStats::default();
+    while let Some(game) = play_one() {
+        stats.record(&game);
+        if !play_again() {
+            break;
+        }
+        println!();
+    }
+    if stats.played > 0 {
+        println!("\n{stats}");
+    }
+}
+
+// None if the player stops answering partway through
+fn play_one(
+    config: GameConfig,
+    rng: &mut StdRng,
+    high_scores: &mut HighScores,
+    scores_path: Option<&Path>,
+) -> Option<Game> {
+    let game = Game::random(config, rng);
+
+    println!("Guess a number between 1-{}:", game.config().max);
+    if game.config().debug {
+        println!("The secret number is ... {}", game.secret());
+    }
+    print_allowance(&game);
+
+    let game = guesses(game, "Enter a number.", number_feedback)?;
+    println!("\n{game}");
+
+    // knowing the answer isn't much of a best
+    if game.won() && game.config().standard() && !game.config().debug {
+        let difficulty = game.config().difficulty;
+        if high_scores.record(difficulty, game.guesses().len() as u32) {
+            println!("That's a new best for {}!", difficulty);
+            if let Some(path) = scores_path {
+                if let Err(e) = scores::save(high_scores, path) {
+                    eprintln!("couldn't save the scores to {}: {}", path.display(), e);
+                }
+            }
+        }
+        println!("\n{}", high_scores.leaderboard());
+    }
+    Some(game)
+}
 
-    println!("Guess a number between 1-100:");
-    println!("The secret number is ... {secret_num}");
+// None if the player stops answering partway through
+fn play_words(config: GameConfig, rng: &mut StdRng) -> Option<Game<Word>> {
+    let game = Game::random_word(config, rng);
+    println!("Guess the word. Each guess tells you if it's earlier or later in the dictionary.");
+    if game.config().debug {
+        println!("The secret word is ... {}", game.secret());
+    }
+    print_allowance(&game);
+
+    let game = guesses(game, "Enter a word.", |_| {})?;
+    println!("\n{game}");
+    Some(game)
+}
+
+fn print_allowance<T: Secret>(game: &Game<T>) {
+    match game.config().time_limit {
+        Some(limit) => println!(
+            "You have {} guesses and {}.",
+            game.config().attempts,
+            clock(limit)
+        ),
+        None => println!("You have {} guesses.", game.config().attempts),
+    }
+}
 
+// asks until it gets a yes or a no. no more input is a no
+fn play_again() -> bool {
     loop {
-        println!("Enter a number.");
-        
-        let mut guess = String::new();
-
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("I'm unable to read your input");
-        
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+        println!("\nPlay again? (y/n)");
+        let Some(line) = read_line() else {
+            return false;
+        };
+        match line.trim().to_lowercase().as_str() {
+            "y" | "yes" => return true,
+            "n" | "no" => return false,
+            _ => {}
+        }
+    }
+}
+
+// one player picks the number, the other guesses it
+fn hotseat(config: GameConfig) {
+    let Some(secret) = pick_secret(&config) else {
+        return;
+    };
+    println!(
+        "Player two, guess player one's number between 1-{}. You have {} guesses.",
+        config.max, config.attempts
+    );
+    let game = Game::new(config, secret);
+    let Some(game) = guesses(game, "Player two, enter a number.", number_feedback) else {
+        return;
+    };
+    println!("\n{game}");
+    match game.won() {
+        true => println!("Player two wins!"),
+        false => println!("Player one wins!"),
+    }
+    println!("Player one's number was {secret}.");
+}
+
+// player one's number, kept off the screen once it's in. None if they never give one
+fn pick_secret(config: &GameConfig) -> Option<u32> {
+    loop {
+        println!(
+            "Player one, pick a number between 1-{} while player two looks away.",
+            config.max
+        );
+        let line = read_line()?;
+        clear_screen();
+        match line.trim().parse() {
+            Ok(n) if (1..=config.max).contains(&n) => return Some(n),
+            _ => println!("That's not a number between 1-{}.", config.max),
+        }
+    }
+}
+
+// wipes the screen once the secret's been typed, so it isn't sitting there for player two to read.
+// only on a terminal: when stdin is a file or a pipe there's nothing on the screen to hide
+fn clear_screen() {
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        // clear the screen and the scrollback, and go back to the top
+        print!("\x1b[2J\x1b[3J\x1b[H");
+        let _ = io::stdout().flush();
+    }
+}
+
+// the guessing part of a game, until it's won or there are no guesses (or time) left. None if the
+// player stops answering before then. `feedback` gets to say more about every wrong guess
+fn guesses<T: Secret>(
+    mut game: Game<T>,
+    prompt: &str,
+    feedback: impl Fn(&Game<T>),
+) -> Option<Game<T>> {
+    'game: while !game.over() {
+        let guess = loop {
+            match game.config().time_limit {
+                Some(limit) => {
+                    // in whole seconds, so the two always add up to the limit
+                    let gone = Duration::from_secs(game.elapsed().as_secs());
+                    println!(
+                        "{prompt} [{} gone, {} left]",
+                        clock(gone),
+                        clock(limit.saturating_sub(gone))
+                    );
+                }
+                None => println!("{prompt}"),
+            }
+
+            let Some(guess) = read_line_within(game.time_left()) else {
+                if game.out_of_time() {
+                    break 'game;
+                }
+                return None;
+            };
+
+            match guess.parse::<Command<T>>() {
+                // one that could never be right doesn't use up a guess
+                Ok(Command::Guess(guess)) => match guess.check(game.config()) {
+                    Ok(()) => break guess,
+                    Err(e) => println!("{e}"),
+                },
+                Ok(Command::Hint) => match game.clue() {
+                    Some(clue) => println!(
+                        "{clue} (that's {} points off)",
+                        game.config().difficulty.points()
+                    ),
+                    None => println!("That's all the clues there are."),
+                },
+                Ok(Command::History) => print_history(&game),
+                Ok(Command::Quit) => {
+                    println!("Giving up? It was {}.", game.secret());
+                    return None;
+                }
+                Err(e) => println!("{e}"),
+            }
         };
 
         println!("You guessed: {guess}");
 
-        match guess.cmp(&secret_num) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("Correct!");
-                break;
+        match game.guess(guess) {
+            Outcome::TooSmall => println!("{}!", T::TOO_SMALL),
+            Outcome::TooBig => println!("{}!", T::TOO_BIG),
+            Outcome::Correct => println!("Correct!"),
+            // it only just got in after the time ran out
+            Outcome::Over => break,
+        }
+        if !game.won() {
+            feedback(&game);
+        }
+        if !game.over() {
+            println!("{} guesses left.", game.left());
+        }
+    }
+    if game.out_of_time() {
+        println!("Time's up!");
+    }
+    Some(game)
+}
+
+// warmer or colder (if they're on), and the number line
+fn number_feedback(game: &Game) {
+    if game.config().hints {
+        if let Some(hint) = game.hint() {
+            println!("{hint}");
+        }
+    }
+    if !game.over() {
+        println!("{}", render::number_line(game, render::WIDTH));
+    }
+}
+
+fn print_history<T: Secret>(game: &Game<T>) {
+    if game.guesses().is_empty() {
+        println!("No guesses yet.");
+    }
+    for (guess, outcome) in game.history() {
+        let outcome = match outcome {
+            Outcome::TooSmall => T::TOO_SMALL,
+            Outcome::TooBig => T::TOO_BIG,
+            _ => "Correct",
+        };
+        println!("{guess:>8}  {outcome}");
+    }
+}
+
+// picks the number for whoever connects, until the process is killed
+fn host(config: GameConfig, address: &str) {
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("couldn't listen on {}: {}", address, e);
+            process::exit(1);
+        }
+    };
+    let secret = config.rng().gen_range(1..=config.max);
+    match listener.local_addr() {
+        Ok(address) => println!("Waiting for players on {address}, join with --join {address}"),
+        Err(_) => println!("Waiting for players on {address}"),
+    }
+    if config.debug {
+        println!("The secret number is ... {secret}");
+    }
+    let served = net::serve(listener, config, secret, |player, game| match game.won() {
+        true => println!(
+            "Player {player} got it in {} guesses.",
+            game.guesses().len()
+        ),
+        false if game.over() => println!("Player {player} ran out of guesses."),
+        false => println!("Player {player} gave up."),
+    });
+    if let Err(e) = served {
+        eprintln!("couldn't take any more players: {}", e);
+        process::exit(1);
+    }
+}
+
+// plays against a host somewhere else
+fn join(address: &str) {
+    let (mut connection, welcome) = match Connection::join(address) {
+        Ok(joined) => joined,
+        Err(e) => {
+            eprintln!("couldn't join the game at {}: {}", address, e);
+            process::exit(1);
+        }
+    };
+    if let Reply::Welcome { max, attempts } = welcome {
+        println!("Guess a number between 1-{max}:");
+        println!("You have {attempts} guesses.");
+    }
+    loop {
+        println!("Enter a number.");
+        let line = match read_line() {
+            Some(line) if line.trim() != "quit" => line,
+            _ => {
+                let _ = connection.quit();
+                return;
+            }
+        };
+        let reply = match connection.send(&line) {
+            Ok(reply) => reply,
+            Err(e) => {
+                eprintln!("lost the connection to the host: {}", e);
+                process::exit(1);
+            }
+        };
+        match reply {
+            Reply::TooSmall { left } => println!("Too small!\n{left} guesses left."),
+            Reply::TooBig { left } => println!("Too big!\n{left} guesses left."),
+            Reply::Won { guesses, score } => {
+                println!("Correct!\nYou got it in {guesses} guesses.\nScore: {score}");
+                return;
+            }
+            Reply::Lost { secret } => {
+                println!("Out of guesses! It was {secret}.");
+                return;
+            }
+            Reply::Error(message) => println!("{message}"),
+            Reply::Welcome { .. } => {}
+        }
+    }
+}
+
+// you think of a number, and the computer guesses it
+fn computer(config: GameConfig) {
+    println!(
+        "Think of a number between 1-{}, and I'll guess it. Tell me if it's (h)igher, (l)ower, or (c)orrect.",
+        config.max
+    );
+    let mut guesser = Guesser::new(config.max);
+    loop {
+        let guess = guesser.guess();
+        let answer = loop {
+            println!("Is it {guess}? (h/l/c)");
+            let Some(line) = read_line() else {
+                return;
+            };
+            match line.parse::<Answer>() {
+                Ok(answer) => break answer,
+                Err(e) => println!("{e}"),
+            }
+        };
+        if let Err(e) = guesser.answer(guess, answer) {
+            println!("Hang on, {e}. Let's try that again.");
+            continue;
+        }
+        if answer == Answer::Correct {
+            match guesser.guesses {
+                1 => println!("Got it in one!"),
+                n => println!("Got it in {n} guesses!"),
             }
+            return;
+        }
+    }
+}
+
+// the scores so far, and where to save them. the game goes on without them when they can't be
+// read, but doesn't save over a file it couldn't read either
+fn high_scores() -> (HighScores, Option<PathBuf>) {
+    let Some(path) = scores::default_path() else {
+        return (HighScores::default(), None);
+    };
+    match scores::load(&path) {
+        Ok(high_scores) => (high_scores, Some(path)),
+        Err(e) if e.kind() == ErrorKind::InvalidData => {
+            match scores::recover(&path) {
+                Ok(bad) => eprintln!(
+                    "the scores in {} were corrupt ({}), so they're starting over. the old file is {}",
+                    path.display(),
+                    e,
+                    bad.display()
+                ),
+                Err(e) => {
+                    eprintln!("couldn't move the corrupt scores in {} aside: {}", path.display(), e);
+                    return (HighScores::default(), None);
+                }
+            }
+            (HighScores::default(), Some(path))
+        }
+        Err(e) => {
+            eprintln!("couldn't read the scores in {}: {}", path.display(), e);
+            (HighScores::default(), None)
         }
     }
-}
\ No newline at end of file
+}
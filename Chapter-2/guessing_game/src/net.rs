@@ -0,0 +1,280 @@
+/*
+ * The game over TCP: `--host` picks the secret and takes connections, and everybody who joins
+ * guesses the same number, each with a `Game` of their own so nobody uses up anybody else's
+ * guesses. Everything is a line of text either way. Players send guesses (or `quit`), and the host
+ * answers each with one of
+ *
+ *     WELCOME <max> <attempts>    once, on connecting
+ *     SMALL <guesses left>
+ *     BIG <guesses left>
+ *     WON <guesses> <score>
+ *     LOST <secret>
+ *     ERROR <message>             that line wasn't a guess (or one in the range), and didn't count
+ *
+ * The connection closes once the player's game is over.
+ */
+
+use crate::{config::GameConfig, Game, Outcome, Secret};
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Welcome { max: u32, attempts: u32 },
+    TooSmall { left: u32 },
+    TooBig { left: u32 },
+    Won { guesses: u32, score: u32 },
+    Lost { secret: u32 },
+    Error(String),
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reply::Welcome { max, attempts } => write!(f, "WELCOME {} {}", max, attempts),
+            Reply::TooSmall { left } => write!(f, "SMALL {}", left),
+            Reply::TooBig { left } => write!(f, "BIG {}", left),
+            Reply::Won { guesses, score } => write!(f, "WON {} {}", guesses, score),
+            Reply::Lost { secret } => write!(f, "LOST {}", secret),
+            // one line, whatever the message
+            Reply::Error(message) => write!(f, "ERROR {}", message.replace('\n', " ")),
+        }
+    }
+}
+
+impl FromStr for Reply {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Reply, String> {
+        let (word, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let numbers: Vec<u32> = match word {
+            "ERROR" => return Ok(Reply::Error(rest.to_string())),
+            _ => rest
+                .split_whitespace()
+                .map(|n| n.parse())
+                .collect::<Result<_, _>>()
+                .map_err(|_| format!("'{}' doesn't make sense", line))?,
+        };
+        match (word, numbers.as_slice()) {
+            ("WELCOME", &[max, attempts]) => Ok(Reply::Welcome { max, attempts }),
+            ("SMALL", &[left]) => Ok(Reply::TooSmall { left }),
+            ("BIG", &[left]) => Ok(Reply::TooBig { left }),
+            ("WON", &[guesses, score]) => Ok(Reply::Won { guesses, score }),
+            ("LOST", &[secret]) => Ok(Reply::Lost { secret }),
+            _ => Err(format!("'{}' doesn't make sense", line)),
+        }
+    }
+}
+
+impl Reply {
+    // whether there's nothing more after this one
+    pub fn last(&self) -> bool {
+        matches!(self, Reply::Won { .. } | Reply::Lost { .. })
+    }
+}
+
+// takes players until the process is killed, each on a thread of their own. only fails if it
+// can't take connections at all. `finished` hears about every game that ends, with the number of
+// the player who played it (counting from 1, in the order they joined)
+pub fn serve(
+    listener: TcpListener,
+    config: GameConfig,
+    secret: u32,
+    finished: fn(usize, &Game),
+) -> io::Result<()> {
+    let players = AtomicUsize::new(0);
+    // a connection that falls over before it gets going isn't worth stopping for
+    for stream in listener.incoming().flatten() {
+        let number = players.fetch_add(1, Ordering::Relaxed) + 1;
+        let game = Game::new(config.clone(), secret);
+        thread::spawn(move || {
+            // a player who hangs up halfway through just doesn't finish
+            if let Ok(game) = play(&stream, &stream, game) {
+                finished(number, &game);
+            }
+        });
+    }
+    Ok(())
+}
+
+// one player's whole game, reading guesses from `input` and answering on `output`. the game comes
+// back once it's over, or when the player quits or hangs up
+pub fn play(input: impl io::Read, mut output: impl Write, mut game: Game) -> io::Result<Game> {
+    let welcome = Reply::Welcome {
+        max: game.config().max,
+        attempts: game.config().attempts,
+    };
+    writeln!(output, "{}", welcome)?;
+    for line in BufReader::new(input).lines() {
+        let line = line?;
+        if line.trim() == "quit" {
+            break;
+        }
+        let reply = match line.trim().parse::<u32>() {
+            Ok(guess) => match guess.check(game.config()) {
+                Ok(()) => reply(&mut game, guess),
+                Err(e) => Reply::Error(e),
+            },
+            Err(_) => Reply::Error(format!("'{}' isn't a number", line.trim())),
+        };
+        writeln!(output, "{}", reply)?;
+        if reply.last() {
+            break;
+        }
+    }
+    Ok(game)
+}
+
+fn reply(game: &mut Game, guess: u32) -> Reply {
+    let outcome = game.guess(guess);
+    if game.won() {
+        return Reply::Won {
+            guesses: game.guesses().len() as u32,
+            score: game.score(),
+        };
+    }
+    if game.over() {
+        return Reply::Lost {
+            secret: *game.secret(),
+        };
+    }
+    match outcome {
+        Outcome::TooSmall => Reply::TooSmall { left: game.left() },
+        _ => Reply::TooBig { left: game.left() },
+    }
+}
+
+// a connection to a host, for `--join`
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    // connects, and waits to be welcomed
+    pub fn join(address: &str) -> io::Result<(Connection, Reply)> {
+        let stream = TcpStream::connect(address)?;
+        let mut connection = Connection {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        };
+        let welcome = connection.reply()?;
+        Ok((connection, welcome))
+    }
+
+    pub fn send(&mut self, line: &str) -> io::Result<Reply> {
+        writeln!(self.writer, "{}", line.trim())?;
+        self.reply()
+    }
+
+    // the host doesn't answer a quit, it just hangs up
+    pub fn quit(mut self) -> io::Result<()> {
+        writeln!(self.writer, "quit")
+    }
+
+    fn reply(&mut self) -> io::Result<Reply> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the host hung up",
+            ));
+        }
+        line.parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies() {
+        for reply in [
+            Reply::Welcome {
+                max: 100,
+                attempts: 9,
+            },
+            Reply::TooSmall { left: 8 },
+            Reply::TooBig { left: 1 },
+            Reply::Won {
+                guesses: 3,
+                score: 140,
+            },
+            Reply::Lost { secret: 42 },
+            Reply::Error("'x' isn't a number".to_string()),
+        ] {
+            assert_eq!(Ok(reply.clone()), reply.to_string().parse());
+        }
+        assert!("SMALL".parse::<Reply>().is_err());
+        assert!("WON 3 lots".parse::<Reply>().is_err());
+        assert!("HELLO 1".parse::<Reply>().is_err());
+    }
+
+    #[test]
+    fn one_player() {
+        let mut output = Vec::new();
+        let game = Game::new(GameConfig::default(), 42);
+        let input = "50\nfifty\n250\n25\n42\n99\n";
+        let game = play(input.as_bytes(), &mut output, game).unwrap();
+        assert_eq!(
+            "WELCOME 100 9\nBIG 8\nERROR 'fifty' isn't a number\n\
+             ERROR Your guess must be between 1 and 100, got 250\nSMALL 7\nWON 3 140\n",
+            String::from_utf8(output).unwrap()
+        );
+        // nothing after the win gets read
+        assert_eq!(&[50, 25, 42], game.guesses());
+    }
+
+    #[test]
+    fn out_of_guesses() {
+        let mut output = Vec::new();
+        let config = GameConfig {
+            attempts: 2,
+            ..GameConfig::default()
+        };
+        play("1\n2\n".as_bytes(), &mut output, Game::new(config, 42)).unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .ends_with("SMALL 1\nLOST 42\n"));
+    }
+
+    #[test]
+    fn players_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener, GameConfig::default(), 42, |_, _| {}));
+
+        let (mut first, welcome) = Connection::join(&address).unwrap();
+        assert_eq!(
+            Reply::Welcome {
+                max: 100,
+                attempts: 9
+            },
+            welcome
+        );
+        let (mut second, _) = Connection::join(&address).unwrap();
+        assert_eq!(Reply::TooBig { left: 8 }, first.send("50").unwrap());
+        assert_eq!(Reply::TooBig { left: 7 }, first.send("60").unwrap());
+        // guesses are counted per player
+        assert_eq!(Reply::TooSmall { left: 8 }, second.send("10").unwrap());
+        assert_eq!(
+            Reply::Won {
+                guesses: 2,
+                score: 160
+            },
+            second.send("42").unwrap()
+        );
+        assert_eq!(Reply::TooSmall { left: 6 }, first.send("41").unwrap());
+        first.quit().unwrap();
+    }
+}
@@ -0,0 +1,98 @@
+/*
+ * The range drawn as a line, to see at a glance what's left to guess. Every guess so far is an `X`
+ * on it, and what it could still be is `=`; everything the guesses have ruled out is `.`:
+ *
+ *     1 ............X===========X......................... 100
+ *       26 to 49 left
+ *
+ * A range longer than the line gets squeezed, so each character can stand for a handful of
+ * numbers. It counts as still possible if any of them are, and as a guess if any of them were
+ * guessed, so the narrowest range left never disappears off the line. A correct guess is a `*`.
+ */
+
+use crate::{Game, Outcome};
+
+pub const WIDTH: usize = 50;
+
+// `game` as a line of at most `width` characters, with the ends of the range on either side and
+// what's left underneath
+pub fn number_line(game: &Game, width: usize) -> String {
+    let max = game.config().max;
+    let width = width.min(max as usize).max(1);
+    // which character `n` falls on
+    let cell = |n: u32| (n as usize - 1) * width / max as usize;
+
+    let (low, high) = game.bounds();
+    let mut line = vec!['.'; width];
+    if low <= high {
+        for c in &mut line[cell(low)..=cell(high)] {
+            *c = '=';
+        }
+    }
+    for (guess, outcome) in game.history() {
+        if (1..=max).contains(&guess) {
+            line[cell(guess)] = match outcome {
+                Outcome::Correct => '*',
+                _ => 'X',
+            };
+        }
+    }
+
+    let line: String = line.into_iter().collect();
+    let left = if game.won() {
+        format!("it was {}", game.secret())
+    } else if low == high {
+        format!("it can only be {}", low)
+    } else {
+        format!("{} to {} left", low, high)
+    };
+    // the second line starts under the first character of the line itself
+    format!("1 {} {}\n  {}", line, max, left)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+
+    fn game(max: u32, secret: u32, guesses: &[u32]) -> Game {
+        let config = GameConfig {
+            max,
+            attempts: 10,
+            ..GameConfig::default()
+        };
+        Game::new(config, secret).after(guesses)
+    }
+
+    #[test]
+    fn one_to_a_number() {
+        assert_eq!(
+            "1 ==================== 20\n  1 to 20 left",
+            number_line(&game(20, 7, &[]), WIDTH)
+        );
+        assert_eq!(
+            "1 ...X======X......... 20\n  5 to 10 left",
+            number_line(&game(20, 7, &[4, 11]), WIDTH)
+        );
+        assert_eq!(
+            "1 ...X..*.X........... 20\n  it was 7",
+            number_line(&game(20, 7, &[4, 9, 7]), WIDTH)
+        );
+        assert_eq!(
+            "1 .....X=X............ 20\n  it can only be 7",
+            number_line(&game(20, 7, &[6, 8]), WIDTH)
+        );
+    }
+
+    #[test]
+    fn squeezed() {
+        assert_eq!(
+            "1 ............X===========X......................... 100\n  26 to 49 left",
+            number_line(&game(100, 42, &[25, 50]), WIDTH)
+        );
+        assert_eq!(
+            "1 .................X==X......... 1000\n  601 to 699 left",
+            number_line(&game(1000, 642, &[600, 700]), 30)
+        );
+    }
+}
@@ -0,0 +1,197 @@
+/*
+ * The best scores so far: the fewest guesses each difficulty has been won in. They're kept in
+ * `scores.txt` in a `guessing_game` directory under wherever the system keeps data for programs
+ * (`$XDG_DATA_HOME`, or `~/.local/share` when that isn't set, or `%APPDATA%` on Windows). The file
+ * is a line per difficulty, and one for every daily challenge played and whether it was won:
+ *
+ *     easy 4
+ *     hard 9
+ *     daily 2024-03-09 won
+ *     daily 2024-03-10 lost
+ *
+ * Only games played on the difficulty's own range count: winning 1-10 in two guesses with
+ * `--max 10` isn't a record for normal. A file that doesn't make sense any more (hand-edited,
+ * half-written) gets moved out of the way to `scores.txt.bad` and the scores start over, instead of
+ * the game refusing to start over it.
+ */
+
+use crate::{config::Difficulty, daily::Day};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HighScores {
+    // fewest guesses, by difficulty
+    best: BTreeMap<Difficulty, u32>,
+    // whether each daily challenge played was won
+    daily: BTreeMap<Day, bool>,
+}
+
+impl HighScores {
+    pub fn best(&self, difficulty: Difficulty) -> Option<u32> {
+        self.best.get(&difficulty).copied()
+    }
+
+    // records a win in `guesses`, and says whether it's a new best
+    pub fn record(&mut self, difficulty: Difficulty, guesses: u32) -> bool {
+        match self.best(difficulty) {
+            Some(best) if best <= guesses => false,
+            _ => {
+                self.best.insert(difficulty, guesses);
+                true
+            }
+        }
+    }
+
+    // None if `day`'s challenge hasn't been played
+    pub fn daily(&self, day: Day) -> Option<bool> {
+        self.daily.get(&day).copied()
+    }
+
+    pub fn record_daily(&mut self, day: Day, won: bool) {
+        self.daily.insert(day, won);
+    }
+
+    // every difficulty, won or not, for showing off
+    pub fn leaderboard(&self) -> String {
+        let mut text = String::from("Best scores:");
+        for difficulty in Difficulty::ALL {
+            match self.best(difficulty) {
+                Some(1) => text.push_str(&format!("\n  {:<6}  1 guess", difficulty)),
+                Some(n) => text.push_str(&format!("\n  {:<6}  {} guesses", difficulty, n)),
+                None => text.push_str(&format!("\n  {:<6}  -", difficulty)),
+            }
+        }
+        text
+    }
+}
+
+pub fn to_text(scores: &HighScores) -> String {
+    let best = scores
+        .best
+        .iter()
+        .map(|(difficulty, guesses)| format!("{} {}\n", difficulty, guesses));
+    let daily = scores.daily.iter().map(|(day, won)| match won {
+        true => format!("daily {} won\n", day),
+        false => format!("daily {} lost\n", day),
+    });
+    best.chain(daily).collect()
+}
+
+pub fn from_text(text: &str) -> Result<HighScores, String> {
+    let mut scores = HighScores::default();
+    for (i, line) in text.lines().enumerate() {
+        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] => {}
+            ["daily", day, won] => {
+                let day = day.parse().map_err(|e| format!("line {}: {}", i + 1, e))?;
+                let won = match *won {
+                    "won" => true,
+                    "lost" => false,
+                    _ => return Err(format!("line {}: '{}' isn't won or lost", i + 1, won)),
+                };
+                scores.record_daily(day, won);
+            }
+            [difficulty, guesses] => {
+                let difficulty = difficulty
+                    .parse()
+                    .map_err(|e| format!("line {}: {}", i + 1, e))?;
+                let guesses = match guesses.parse() {
+                    Ok(n) if n >= 1 => n,
+                    _ => return Err(format!("line {}: '{}' isn't a score", i + 1, guesses)),
+                };
+                scores.record(difficulty, guesses);
+            }
+            _ => return Err(format!("line {}: '{}' doesn't make sense", i + 1, line)),
+        }
+    }
+    Ok(scores)
+}
+
+// where the scores live, if there's anywhere to put them
+pub fn default_path() -> Option<PathBuf> {
+    let data = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(data.join("guessing_game").join("scores.txt"))
+}
+
+pub fn save(scores: &HighScores, path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, to_text(scores))
+}
+
+// no file yet is no scores yet. a file that's there but can't be made sense of is an InvalidData
+// error, which `recover` can deal with
+pub fn load(path: &Path) -> io::Result<HighScores> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(HighScores::default()),
+        Err(e) => return Err(e),
+    };
+    from_text(&text).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+// renames scores that `load` couldn't make sense of to scores.txt.bad, so starting over with no
+// scores doesn't lose whatever was in there. returns the new name
+pub fn recover(path: &Path) -> io::Result<PathBuf> {
+    let mut bad = path.as_os_str().to_owned();
+    bad.push(".bad");
+    let bad = PathBuf::from(bad);
+    fs::rename(path, &bad)?;
+    Ok(bad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fewest_guesses() {
+        let mut scores = HighScores::default();
+        assert!(scores.record(Difficulty::Normal, 6));
+        assert!(!scores.record(Difficulty::Normal, 7));
+        assert!(!scores.record(Difficulty::Normal, 6));
+        assert!(scores.record(Difficulty::Normal, 4));
+        assert_eq!(Some(4), scores.best(Difficulty::Normal));
+        assert_eq!(None, scores.best(Difficulty::Hard));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut scores = HighScores::default();
+        scores.record(Difficulty::Hard, 9);
+        scores.record(Difficulty::Easy, 1);
+        scores.record_daily("2024-03-10".parse().unwrap(), false);
+        scores.record_daily("2024-03-09".parse().unwrap(), true);
+        let text = to_text(&scores);
+        assert_eq!(
+            "easy 1\nhard 9\ndaily 2024-03-09 won\ndaily 2024-03-10 lost\n",
+            text
+        );
+        assert_eq!(Ok(scores.clone()), from_text(&text));
+        assert_eq!(
+            "Best scores:\n  easy    1 guess\n  normal  -\n  hard    9 guesses",
+            scores.leaderboard()
+        );
+    }
+
+    #[test]
+    fn corrupt() {
+        assert!(from_text("easy four\n").is_err());
+        assert!(from_text("brutal 3\n").is_err());
+        assert!(from_text("easy 0\n").is_err());
+        assert!(from_text("daily 2024-02-30 won\n").is_err());
+        assert!(from_text("daily 2024-02-03 maybe\n").is_err());
+        assert!(from_text("\u{0}\u{0}\u{0}").is_err());
+        assert_eq!(Ok(HighScores::default()), from_text("\n\n"));
+    }
+}
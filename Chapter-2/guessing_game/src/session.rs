@@ -0,0 +1,79 @@
+/*
+ * How a sitting went, over however many games got played before calling it a day. The average only
+ * counts games that were won: a lost game always takes every guess there is, which says more about
+ * the difficulty than about the player.
+ */
+
+use crate::{Game, Secret};
+use std::fmt;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionStats {
+    pub played: u32,
+    pub wins: u32,
+    // across every game won
+    pub winning_guesses: u32,
+}
+
+impl SessionStats {
+    // adds a finished game
+    pub fn record<T: Secret>(&mut self, game: &Game<T>) {
+        self.played += 1;
+        if game.won() {
+            self.wins += 1;
+            self.winning_guesses += game.guesses().len() as u32;
+        }
+    }
+
+    // None until something's been won
+    pub fn average_guesses(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.winning_guesses as f64 / self.wins as f64)
+    }
+}
+
+impl fmt::Display for SessionStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Games played: {}", self.played)?;
+        let percent = match self.played {
+            0 => 0,
+            played => self.wins * 100 / played,
+        };
+        write!(f, "Won: {} ({}%)", self.wins, percent)?;
+        if let Some(average) = self.average_guesses() {
+            write!(f, "\nAverage guesses to win: {:.1}", average)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+
+    #[test]
+    fn stats() {
+        let mut stats = SessionStats::default();
+        assert_eq!(None, stats.average_guesses());
+        let game = || Game::new(GameConfig::default(), 42);
+        stats.record(&game().after(&[50, 42]));
+        stats.record(&game().after(&[1, 2, 3, 4, 5, 6, 7, 8, 9]));
+        stats.record(&game().after(&[50, 25, 37, 42]));
+        assert_eq!(
+            SessionStats {
+                played: 3,
+                wins: 2,
+                winning_guesses: 6,
+            },
+            stats
+        );
+        assert_eq!(
+            "Games played: 3\nWon: 2 (66%)\nAverage guesses to win: 3.0",
+            stats.to_string()
+        );
+        assert_eq!(
+            "Games played: 0\nWon: 0 (0%)",
+            SessionStats::default().to_string()
+        );
+    }
+}
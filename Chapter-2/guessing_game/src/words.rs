@@ -0,0 +1,117 @@
+/*
+ * Words to guess instead of numbers. A `Word` is in lowercase and only letters, so it sorts the
+ * way a dictionary does and "Apple" is the same guess as "apple". Clues give away the first letter,
+ * then how long it is, then the last letter.
+ *
+ * Any word can be guessed, not just the ones in the list: "earlier" and "later" still make sense
+ * for a word the game has never heard of.
+ */
+
+use crate::{config::GameConfig, Clue, Game, Secret};
+use rand::{seq::SliceRandom, Rng};
+use std::{fmt, str::FromStr};
+
+// what the secret gets picked from
+pub const WORDS: &[&str] = &[
+    "acorn", "anchor", "apple", "arrow", "badge", "banjo", "barrel", "beacon", "blanket", "bottle",
+    "bridge", "bucket", "cactus", "camera", "candle", "canyon", "carpet", "castle", "cherry",
+    "circus", "cloud", "comet", "compass", "cotton", "crayon", "desert", "dragon", "eagle",
+    "engine", "falcon", "feather", "forest", "fossil", "galaxy", "garden", "glacier", "guitar",
+    "hammer", "harbor", "helmet", "honey", "island", "jacket", "jungle", "kettle", "ladder",
+    "lantern", "lemon", "lizard", "magnet", "maple", "marble", "meadow", "mirror", "monkey",
+    "needle", "nickel", "oyster", "paddle", "palace", "parrot", "pebble", "pencil", "pepper",
+    "pillow", "planet", "pocket", "puzzle", "quartz", "rabbit", "ribbon", "river", "rocket",
+    "saddle", "salmon", "shadow", "spider", "squirrel", "statue", "summit", "sunset", "tablet",
+    "teapot", "thunder", "ticket", "tiger", "tomato", "tunnel", "turtle", "umbrella", "valley",
+    "velvet", "violin", "volcano", "wagon", "walnut", "whistle", "window", "wizard", "zebra",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Word(String);
+
+impl FromStr for Word {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Word, String> {
+        let s = s.trim();
+        match !s.is_empty() && s.chars().all(|c| c.is_alphabetic()) {
+            true => Ok(Word(s.to_lowercase())),
+            false => Err(format!("'{}' isn't a word", s)),
+        }
+    }
+}
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+impl Secret for Word {
+    const NAME: &'static str = "word";
+    const TOO_SMALL: &'static str = "Later in the dictionary";
+    const TOO_BIG: &'static str = "Earlier in the dictionary";
+
+    fn clue(&self, n: usize) -> Option<Clue> {
+        match n {
+            0 => self.0.chars().next().map(Clue::StartsWith),
+            1 => Some(Clue::Letters(self.0.chars().count())),
+            2 => self.0.chars().last().map(Clue::EndsWith),
+            _ => None,
+        }
+    }
+}
+
+impl Game<Word> {
+    // a word from the list
+    pub fn random_word(config: GameConfig, rng: &mut impl Rng) -> Game<Word> {
+        let word = WORDS.choose(rng).expect("there's always a word to pick");
+        Game::new(config, Word(word.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Outcome;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn word(s: &str) -> Word {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(Ok(Word("apple".to_string())), " Apple\n".parse());
+        assert!("".parse::<Word>().is_err());
+        assert!("ice cream".parse::<Word>().is_err());
+        assert!(word("apple") < word("banana") && word("bank") < word("banker"));
+        for w in WORDS {
+            assert_eq!(Ok(Word(w.to_string())), w.parse::<Word>());
+        }
+    }
+
+    #[test]
+    fn guessing_words() {
+        let mut game = Game::new(GameConfig::default(), word("maple"));
+        assert_eq!(Outcome::TooSmall, game.guess(word("lemon")));
+        assert_eq!(Outcome::TooBig, game.guess(word("Zebra")));
+        assert_eq!(Some(Clue::StartsWith('m')), game.clue());
+        assert_eq!(Some(Clue::Letters(5)), game.clue());
+        assert_eq!(Some(Clue::EndsWith('e')), game.clue());
+        assert_eq!(None, game.clue());
+        assert_eq!(Outcome::Correct, game.guess(word("maple")));
+        assert_eq!(
+            "You got it in 3 of 9 guesses.\nGuesses: lemon, zebra, maple\nScore: 80 (3 clues)",
+            game.to_string()
+        );
+    }
+
+    #[test]
+    fn seeded() {
+        let pick =
+            |seed| Game::random_word(GameConfig::default(), &mut StdRng::seed_from_u64(seed));
+        assert_eq!(pick(3).secret(), pick(3).secret());
+        assert!(WORDS.contains(&pick(3).secret().to_string().as_str()));
+    }
+}
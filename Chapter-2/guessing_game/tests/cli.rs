@@ -0,0 +1,170 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    // somewhere the high scores can't get mixed up with real ones
+    let data = env::temp_dir().join(format!("guessing-game-cli-{}", std::process::id()));
+    run_in(&data, args, stdin)
+}
+
+// with the scores kept under `data`
+fn run_in(data: &Path, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing_game"))
+        .args(args)
+        .env("XDG_DATA_HOME", data)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn hotseat() {
+    // 0 and 101 aren't in the range, so player one gets asked again
+    let output = run(&["--hotseat"], "0\n101\n42\n50\n25\n42\n");
+    let text = stdout(&output);
+    assert!(output.status.success());
+    assert_eq!(
+        2,
+        text.matches("That's not a number between 1-100.").count()
+    );
+    assert!(text.contains("Too big!\n"));
+    assert!(text.contains("Too small!\n"));
+    assert!(text.contains("You got it in 3 of 9 guesses."));
+    assert!(text.ends_with("Player two wins!\nPlayer one's number was 42.\n"));
+}
+
+#[test]
+fn hotseat_out_of_guesses() {
+    let output = run(&["--hotseat", "--attempts", "2"], "7\n1\n2\n");
+    assert!(stdout(&output).ends_with("Player one wins!\nPlayer one's number was 7.\n"));
+}
+
+#[test]
+fn computer_guesses() {
+    let output = run(&["--computer"], "h\nl\nl\nl\nl\nl\nh\nc\n");
+    let text = stdout(&output);
+    assert!(text
+        .contains("Is it 51? (h/l/c)\nHang on, you said it was higher than 50 and lower than 51"));
+    assert!(text.ends_with("Is it 52? (h/l/c)\nGot it in 7 guesses!\n"));
+}
+
+#[test]
+fn play_again() {
+    // seed 1 picks 83 and then 98, so guessing 1 loses both games in one guess
+    let output = run(&["--seed", "1", "--attempts", "1"], "1\ny\n1\nmaybe\nn\n");
+    let text = stdout(&output);
+    assert_eq!(2, text.matches("Out of guesses!").count());
+    assert_eq!(3, text.matches("Play again? (y/n)").count());
+    assert!(text.ends_with("Games played: 2\nWon: 0 (0%)\n"));
+}
+
+#[test]
+fn commands() {
+    let output = run(
+        &["--hotseat"],
+        "42\nhistory\n250\n50\nhint\nHISTORY\nwhat\nquit\n",
+    );
+    let text = stdout(&output);
+    assert!(text.contains("No guesses yet.\n"));
+    // out of the range, so it's not a guess at all
+    assert!(text.contains("Your guess must be between 1 and 100, got 250\n"));
+    assert!(!text.contains("You guessed: 250"));
+    assert!(text.contains("8 guesses left."));
+    assert!(text.contains("It's even. (that's 20 points off)\n"));
+    assert!(text.contains("      50  Too big\n"));
+    assert!(text.contains("'what' isn't a number, or hint, history, or quit\n"));
+    assert!(text.ends_with("Giving up? It was 42.\n"));
+}
+
+#[test]
+fn words() {
+    let output = run(&["--words"], "Aardvark\n42\nhint\nquit\n");
+    let text = stdout(&output);
+    assert!(text.contains("You have 16 guesses."));
+    // before anything in the list
+    assert!(text.contains("You guessed: aardvark\nLater in the dictionary!\n"));
+    assert!(text.contains("'42' isn't a word, or hint, history, or quit\n"));
+    assert!(text.contains("It starts with '"));
+    assert!(text.contains("Giving up? It was "));
+}
+
+#[test]
+fn seeded() {
+    let secrets = |seed| {
+        let output = run(
+            &["--debug", "--seed", seed, "--attempts", "1"],
+            "1\ny\n1\nn\n",
+        );
+        stdout(&output)
+            .lines()
+            .filter(|line| line.starts_with("The secret number is"))
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+    };
+    let first = secrets("42");
+    assert_eq!(2, first.len());
+    assert_eq!(first, secrets("42"));
+    // and it stays a secret without --debug
+    assert!(!stdout(&run(&["--seed", "42"], "")).contains("The secret number is"));
+}
+
+#[test]
+fn daily() {
+    // giving up counts as a loss
+    let output = run(&["--daily"], "quit\n");
+    let text = stdout(&output);
+    assert!(text.starts_with("The daily challenge for 2"));
+    assert!(text.contains("Giving up? It was "));
+    assert!(!text.contains("Play again?"));
+    let output = run(&["--daily"], "");
+    assert_eq!(
+        "You've already had a go at today's challenge.\nThere's a new one tomorrow.\n",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn corrupt_scores() {
+    let data = env::temp_dir().join(format!("guessing-game-corrupt-{}", std::process::id()));
+    let scores = data.join("guessing_game/scores.txt");
+    let _ = fs::remove_dir_all(&data);
+    fs::create_dir_all(scores.parent().unwrap()).unwrap();
+    fs::write(&scores, "normal lots\n").unwrap();
+
+    let output = run_in(&data, &["--daily"], "quit\n");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("were corrupt"));
+    // the old scores are kept to one side, and the game starts over from none, plus today's loss
+    assert_eq!(
+        "normal lots\n",
+        fs::read_to_string(data.join("guessing_game/scores.txt.bad")).unwrap()
+    );
+    let saved = fs::read_to_string(&scores).unwrap();
+    assert!(saved.starts_with("daily ") && saved.ends_with(" lost\n"));
+    assert_eq!(1, saved.lines().count());
+    fs::remove_dir_all(&data).unwrap();
+}
+
+#[test]
+fn bad_options() {
+    let output = run(&["--difficulty", "brutal"], "");
+    assert_eq!(Some(2), output.status.code());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("usage:"));
+}
@@ -0,0 +1,74 @@
+use std::ops::Add;
+
+// red, green, and blue, each from 0 to 255
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RGBColor(pub u8, pub u8, pub u8);
+
+impl RGBColor {
+    // the opposite on each channel: black for white, cyan for red
+    pub fn invert(&self) -> RGBColor {
+        RGBColor(255 - self.0, 255 - self.1, 255 - self.2)
+    }
+
+    // how bright it looks, from 0 for black to 1 for white. green looks the brightest to people
+    // and blue the darkest, so they don't count the same
+    pub fn luminance(&self) -> f64 {
+        (0.2126 * self.0 as f64 + 0.7152 * self.1 as f64 + 0.0722 * self.2 as f64) / 255.0
+    }
+}
+
+// mixes light, so each channel adds up, as far as 255
+impl Add for RGBColor {
+    type Output = RGBColor;
+
+    fn add(self, other: RGBColor) -> RGBColor {
+        RGBColor(
+            self.0.saturating_add(other.0),
+            self.1.saturating_add(other.1),
+            self.2.saturating_add(other.2),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: RGBColor = RGBColor(0, 0, 0);
+    const WHITE: RGBColor = RGBColor(255, 255, 255);
+
+    #[test]
+    fn add() {
+        assert_eq!(
+            RGBColor(255, 255, 0),
+            RGBColor(255, 0, 0) + RGBColor(0, 255, 0)
+        );
+        assert_eq!(
+            RGBColor(150, 255, 255),
+            RGBColor(100, 200, 250) + RGBColor(50, 100, 10)
+        );
+        assert_eq!(WHITE, WHITE + WHITE);
+        assert_eq!(RGBColor(1, 2, 3), RGBColor(1, 2, 3) + BLACK);
+    }
+
+    #[test]
+    fn invert() {
+        assert_eq!(WHITE, BLACK.invert());
+        assert_eq!(RGBColor(0, 255, 255), RGBColor(255, 0, 0).invert());
+        assert_eq!(RGBColor(155, 55, 5), RGBColor(100, 200, 250).invert());
+        let c = RGBColor(12, 34, 56);
+        assert_eq!(c, c.invert().invert());
+        assert_eq!(WHITE, c + c.invert());
+    }
+
+    #[test]
+    fn luminance() {
+        assert_eq!(0.0, BLACK.luminance());
+        assert!((WHITE.luminance() - 1.0).abs() < 1e-9);
+        let red = RGBColor(255, 0, 0).luminance();
+        let green = RGBColor(0, 255, 0).luminance();
+        let blue = RGBColor(0, 0, 255).luminance();
+        assert!(green > red && red > blue);
+        assert!((red + green + blue - 1.0).abs() < 1e-9);
+    }
+}
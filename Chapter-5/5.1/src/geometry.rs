@@ -0,0 +1,104 @@
+use std::{
+    fmt,
+    ops::{Add, Sub},
+};
+
+// a point in 3D, or the way from one point to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point(pub i32, pub i32, pub i32);
+
+impl Point {
+    // in a straight line. the differences are worked out as f64s, since i32::MAX - i32::MIN
+    // doesn't fit in an i32
+    pub fn distance(&self, other: &Point) -> f64 {
+        let d = |a: i32, b: i32| a as f64 - b as f64;
+        let (x, y, z) = (d(self.0, other.0), d(self.1, other.1), d(self.2, other.2));
+        (x * x + y * y + z * z).sqrt()
+    }
+
+    // `+` and `-`, but None when a coordinate doesn't fit in an i32
+    pub fn checked_add(self, other: Point) -> Option<Point> {
+        Some(Point(
+            self.0.checked_add(other.0)?,
+            self.1.checked_add(other.1)?,
+            self.2.checked_add(other.2)?,
+        ))
+    }
+
+    pub fn checked_sub(self, other: Point) -> Option<Point> {
+        Some(Point(
+            self.0.checked_sub(other.0)?,
+            self.1.checked_sub(other.1)?,
+            self.2.checked_sub(other.2)?,
+        ))
+    }
+}
+
+// these overflow just like adding and subtracting i32s does: a panic in a debug build, and
+// wrapping around in a release one. checked_add and checked_sub are there for when that matters
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(Point(5, 7, 9), Point(1, 2, 3) + Point(4, 5, 6));
+        assert_eq!(Point(-3, -3, -3), Point(1, 2, 3) - Point(4, 5, 6));
+        let p = Point(1, -2, 3);
+        assert_eq!(p, p + Point(0, 0, 0));
+        assert_eq!(Point(0, 0, 0), p - p);
+    }
+
+    #[test]
+    fn distance() {
+        let origin = Point(0, 0, 0);
+        assert_eq!(13.0, Point(3, 4, 12).distance(&origin));
+        assert_eq!(13.0, origin.distance(&Point(-3, -4, -12)));
+        assert_eq!(0.0, origin.distance(&origin));
+        assert_eq!(3f64.sqrt(), Point(1, 1, 1).distance(&Point(2, 2, 2)));
+        let far = Point(i32::MAX, 0, 0).distance(&Point(i32::MIN, 0, 0));
+        assert_eq!(u32::MAX as f64, far);
+    }
+
+    #[test]
+    fn checked() {
+        assert_eq!(
+            Some(Point(5, 7, 9)),
+            Point(1, 2, 3).checked_add(Point(4, 5, 6))
+        );
+        assert_eq!(None, Point(0, i32::MAX, 0).checked_add(Point(0, 1, 0)));
+        assert_eq!(None, Point(0, 0, i32::MIN).checked_sub(Point(0, 0, 1)));
+        assert_eq!(
+            Some(Point(-3, -3, -3)),
+            Point(1, 2, 3).checked_sub(Point(4, 5, 6))
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("(0, 0, 0)", Point(0, 0, 0).to_string());
+        assert_eq!("(1, -2, 3)", Point(1, -2, 3).to_string());
+    }
+}
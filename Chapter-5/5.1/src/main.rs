@@ -1,12 +1,39 @@
-struct RGBColor(u8, u8, u8);
-struct Point(u8, u8, u8);
+mod color;
+mod geometry;
+
+use color::RGBColor;
+use geometry::Point;
 
 fn main() {
     let black = RGBColor(0, 0, 0);
     let RGBColor(r, g, b) = black;
 
     let origin = Point(0, 0, 0);
-    let Point(x,y,z) = origin;
+    let Point(x, y, z) = origin;
 
     println!("The point ({x}, {y}, {z}) is black, which has the RGB-value ({r}, {g}, {b})");
-}
\ No newline at end of file
+
+    let point = Point(3, 4, 12);
+    let moved = point + Point(1, 1, 1) - Point(0, 0, 2);
+    println!(
+        "{point} is {} from {origin}, and moving it gets to {moved}",
+        point.distance(&origin)
+    );
+    let edge = Point(i32::MAX, 0, 0);
+    if edge.checked_add(Point(1, 0, 0)).is_none() {
+        println!("There's no going any further than {edge}");
+    }
+    if let Some(step) = moved.checked_sub(point) {
+        println!("Getting from {point} to {moved} takes {step}");
+    }
+
+    let white = black.invert();
+    let yellow = RGBColor(255, 0, 0) + RGBColor(0, 255, 0);
+    println!(
+        "White is {:?} with luminance {:.3}, and yellow is {:?} with luminance {:.3}",
+        white,
+        white.luminance(),
+        yellow,
+        yellow.luminance()
+    );
+}
@@ -0,0 +1,36 @@
+/*
+ * How much faster median_unsorted is than sorting and then taking the median, on lists big enough
+ * for it to matter. Run it in release mode, or the numbers don't mean much:
+ *
+ *     cargo run --release --example median
+ */
+
+use ex1::stats::{median, median_unsorted};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::time::Instant;
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for len in [100_000, 1_000_000, 10_000_000] {
+        let list: Vec<f64> = (0..len).map(|_| rng.gen_range(-1000.0..1000.0)).collect();
+
+        let start = Instant::now();
+        let mut sorted = list.clone();
+        sorted.sort_by(f64::total_cmp);
+        let by_sorting = median(&sorted).unwrap();
+        let sort_time = start.elapsed();
+
+        let start = Instant::now();
+        let by_selecting = median_unsorted(&list).unwrap();
+        let select_time = start.elapsed();
+
+        assert_eq!(by_sorting, by_selecting);
+        println!(
+            "{:>10} numbers: sort {:>9.2?}, select {:>9.2?} ({:.1}x)",
+            len,
+            sort_time,
+            select_time,
+            sort_time.as_secs_f64() / select_time.as_secs_f64()
+        );
+    }
+}
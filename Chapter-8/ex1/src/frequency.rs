@@ -0,0 +1,151 @@
+/*
+ * How often each value comes up. `frequency_table()` counts every distinct value and lists them
+ * most common first; values that come up equally often stay in the order they first appeared, so
+ * the table for a sorted list is in order within each count. `render()` lays the table out in
+ * columns with each value's share of the whole:
+ *
+ *     value  count  percent
+ *        -4     12    17.6%
+ *         2      9    13.2%
+ *
+ * `modality()` says how many peaks the data has, going by how many values are tied for most common:
+ * one is unimodal, two is bimodal, and more than that is multimodal.
+ */
+
+use crate::{
+    stats::{ByKey, StatsError},
+    Number,
+};
+use std::{cmp::Reverse, collections::HashMap, fmt, hash::Hash};
+
+pub fn frequency_table<T: Eq + Hash + Clone>(items: &[T]) -> Vec<(T, usize)> {
+    // where each one first turned up, and how many times it has
+    let mut counts: HashMap<&T, (usize, usize)> = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        counts.entry(item).or_insert((i, 0)).1 += 1;
+    }
+    let mut table: Vec<(&T, (usize, usize))> = counts.into_iter().collect();
+    table.sort_by_key(|&(_, (first, count))| (Reverse(count), first));
+    table
+        .into_iter()
+        .map(|(item, (_, count))| (item.clone(), count))
+        .collect()
+}
+
+// `frequency_table` for numbers, floats included. NaNs never equal anything, so they aren't counted
+pub fn number_frequency_table<T: Number>(list: &[T]) -> Vec<(T, usize)> {
+    let keyed: Vec<ByKey<T>> = list
+        .iter()
+        .filter(|n| !n.is_nan())
+        .map(|&n| ByKey(n))
+        .collect();
+    frequency_table(&keyed)
+        .into_iter()
+        .map(|(n, count)| (n.0, count))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Modality {
+    Unimodal,
+    Bimodal,
+    // how many values are tied for most common
+    Multimodal(usize),
+}
+
+impl fmt::Display for Modality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Modality::Unimodal => write!(f, "unimodal"),
+            Modality::Bimodal => write!(f, "bimodal"),
+            Modality::Multimodal(n) => write!(f, "multimodal ({} modes)", n),
+        }
+    }
+}
+
+// for a table from `frequency_table`
+pub fn modality<T>(table: &[(T, usize)]) -> Result<Modality, StatsError> {
+    let Some(&(_, most)) = table.first() else {
+        return Err(StatsError::EmptyInput);
+    };
+    Ok(
+        match table.iter().filter(|&&(_, count)| count == most).count() {
+            1 => Modality::Unimodal,
+            2 => Modality::Bimodal,
+            n => Modality::Multimodal(n),
+        },
+    )
+}
+
+pub fn render<T: fmt::Display>(table: &[(T, usize)]) -> String {
+    let total: usize = table.iter().map(|&(_, count)| count).sum();
+    let values: Vec<String> = table.iter().map(|(value, _)| value.to_string()).collect();
+    let value_width = values.iter().map(|v| v.len()).chain([5]).max().unwrap();
+    let count_width = table
+        .iter()
+        .map(|(_, count)| count.to_string().len())
+        .chain([5])
+        .max()
+        .unwrap();
+
+    let mut rendered = format!(
+        "{:>vw$}  {:>cw$}  percent\n",
+        "value",
+        "count",
+        vw = value_width,
+        cw = count_width
+    );
+    for (value, &(_, count)) in values.iter().zip(table) {
+        rendered.push_str(&format!(
+            "{:>vw$}  {:>cw$}  {:>6.1}%\n",
+            value,
+            count,
+            count as f64 / total as f64 * 100.0,
+            vw = value_width,
+            cw = count_width
+        ));
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables() {
+        assert_eq!(
+            vec![("b", 3), ("a", 2), ("c", 1)],
+            frequency_table(&["a", "b", "c", "b", "b", "a"])
+        );
+        // ties stay in the order they came in
+        assert_eq!(
+            vec![(3, 2), (1, 2), (2, 1)],
+            frequency_table(&[3, 1, 2, 3, 1])
+        );
+        assert_eq!(
+            vec![(0.5, 2), (-1.0, 1)],
+            number_frequency_table(&[0.5, f64::NAN, -1.0, 0.5])
+        );
+        assert!(frequency_table::<i32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn modalities() {
+        let modality_of = |list: &[i32]| modality(&frequency_table(list));
+        assert_eq!(Ok(Modality::Unimodal), modality_of(&[1, 2, 2]));
+        assert_eq!(Ok(Modality::Bimodal), modality_of(&[1, 1, 2, 2, 3]));
+        assert_eq!(Ok(Modality::Multimodal(3)), modality_of(&[1, 2, 3]));
+        assert_eq!(Err(StatsError::EmptyInput), modality_of(&[]));
+        assert_eq!("multimodal (3 modes)", Modality::Multimodal(3).to_string());
+    }
+
+    #[test]
+    fn rendered() {
+        let table = frequency_table(&[-4, -4, -4, 2, 2, 10]);
+        assert_eq!(
+            "value  count  percent\n   -4      3    50.0%\n    2      2    33.3%\n   10      1    16.7%\n",
+            render(&table)
+        );
+    }
+}
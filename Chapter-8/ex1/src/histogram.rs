@@ -0,0 +1,166 @@
+/*
+ * A bar chart of how the numbers are spread out. `histogram()` splits the range from the smallest
+ * number to the largest into bins of equal width and counts how many numbers fall in each;
+ * `render()` draws them as bars of block characters, one line per bin:
+ *
+ *     [-4, -2)  ████████████████████▌  21
+ *     [-2,  0)  ██████████▎            10
+ *     [ 0,  2]  ████████████████       16
+ *
+ * Every bin includes its lower edge and not its upper one, except the last, which has to include
+ * the largest number too. The longest bar is always `width` characters long and the rest are
+ * scaled to match, down to an eighth of a character.
+ */
+
+use crate::{
+    stats::{max, min, StatsError},
+    Number,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bin {
+    pub low: f64,
+    pub high: f64,
+    pub count: usize,
+}
+
+// when every number is the same there's no range to split up, so that's one bin however many were
+// asked for
+pub fn histogram<T: Number>(list: &[T], bins: usize) -> Result<Vec<Bin>, StatsError> {
+    if bins == 0 {
+        return Err(StatsError::NoBins);
+    }
+    let (low, high) = (min(list)?.to_f64(), max(list)?.to_f64());
+    if !low.is_finite() || !high.is_finite() {
+        return Err(StatsError::Infinite);
+    }
+    let bins = if low == high { 1 } else { bins };
+    let width = (high - low) / bins as f64;
+    let mut histogram: Vec<Bin> = (0..bins)
+        .map(|i| Bin {
+            low: low + width * i as f64,
+            high: if i + 1 == bins {
+                high
+            } else {
+                low + width * (i + 1) as f64
+            },
+            count: 0,
+        })
+        .collect();
+    for n in list {
+        let i = if width == 0.0 {
+            0
+        } else {
+            ((n.to_f64() - low) / width) as usize
+        };
+        histogram[i.min(bins - 1)].count += 1;
+    }
+    Ok(histogram)
+}
+
+// a reasonable number of bins for `len` numbers (Sturges' rule): log2 of `len`, rounded up, plus
+// one
+pub fn default_bins(len: usize) -> usize {
+    (len.max(1) as f64).log2().ceil() as usize + 1
+}
+
+const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+pub fn render(histogram: &[Bin], width: usize) -> String {
+    let most = histogram.iter().map(|bin| bin.count).max().unwrap_or(0);
+    // whole numbers don't need a decimal point
+    let whole = histogram
+        .iter()
+        .all(|bin| bin.low.fract() == 0.0 && bin.high.fract() == 0.0);
+    let number = |n: f64| match whole {
+        true => format!("{}", n),
+        false => format!("{:.2}", n),
+    };
+    let lows: Vec<String> = histogram.iter().map(|bin| number(bin.low)).collect();
+    let highs: Vec<String> = histogram.iter().map(|bin| number(bin.high)).collect();
+    let low_width = lows.iter().map(|n| n.len()).max().unwrap_or(0);
+    let high_width = highs.iter().map(|n| n.len()).max().unwrap_or(0);
+
+    let mut chart = String::new();
+    for (i, bin) in histogram.iter().enumerate() {
+        let close = if i + 1 == histogram.len() { ']' } else { ')' };
+        let eighths = match most {
+            0 => 0,
+            _ => bin.count * width * 8 / most,
+        };
+        let mut bar = "█".repeat(eighths / 8);
+        if eighths % 8 > 0 {
+            bar.push(EIGHTHS[eighths % 8]);
+        }
+        chart.push_str(&format!(
+            "[{:>lw$}, {:>hw$}{}  {:<width$}  {}\n",
+            lows[i],
+            highs[i],
+            close,
+            bar,
+            bin.count,
+            lw = low_width,
+            hw = high_width,
+            width = width
+        ));
+    }
+    chart
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let bins = histogram(&[-4, -3, -3, -1, 0, 1, 4], 4).unwrap();
+        let edges: Vec<(f64, f64)> = bins.iter().map(|b| (b.low, b.high)).collect();
+        assert_eq!(
+            vec![(-4.0, -2.0), (-2.0, 0.0), (0.0, 2.0), (2.0, 4.0)],
+            edges
+        );
+        // 0 goes with the bin it starts, and 4 with the last one even though it's the upper edge
+        let counts: Vec<usize> = bins.iter().map(|b| b.count).collect();
+        assert_eq!(vec![3, 1, 2, 1], counts);
+    }
+
+    #[test]
+    fn nothing_to_split() {
+        let bins = histogram(&[2.5, 2.5], 5).unwrap();
+        assert_eq!(
+            vec![Bin {
+                low: 2.5,
+                high: 2.5,
+                count: 2
+            }],
+            bins
+        );
+        assert_eq!(Err(StatsError::NoBins), histogram(&[1, 2], 0));
+        assert_eq!(
+            Err(StatsError::Infinite),
+            histogram(&[1.0, f64::INFINITY, 2.0], 3)
+        );
+        assert_eq!(
+            Err(StatsError::Infinite),
+            histogram(&[f64::NEG_INFINITY, 1.0], 3)
+        );
+        assert_eq!(Err(StatsError::EmptyInput), histogram::<i32>(&[], 3));
+    }
+
+    #[test]
+    fn bars() {
+        let bins = histogram(&[-4, -4, -4, -4, -3, -3, 0, 1], 2).unwrap();
+        assert_eq!(
+            "[-4.00, -1.50)  ████████  6\n[-1.50,  1.00]  ██▋       2\n",
+            render(&bins, 8)
+        );
+    }
+
+    #[test]
+    fn sturges() {
+        assert_eq!(1, default_bins(0));
+        assert_eq!(2, default_bins(2));
+        assert_eq!(7, default_bins(50));
+        assert_eq!(8, default_bins(100));
+    }
+}
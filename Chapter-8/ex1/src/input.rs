@@ -0,0 +1,120 @@
+/*
+ * Reading a list of numbers out of text, however it's laid out: one per line, all on one line, or
+ * comma separated (or a mix of all three). Anything between the separators has to be a number;
+ * when one isn't, the error says which one it was and where, so it can be found in a big file.
+ */
+
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub token: String,
+    // which number in the list it was meant to be, counting from 1
+    pub index: usize,
+    // where it starts, counting from 1. the column counts characters, not bytes
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "'{}' isn't a number (number {}, at line {}, column {})",
+            self.token, self.index, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+fn is_separator(ch: char) -> bool {
+    ch.is_whitespace() || ch == ','
+}
+
+pub fn parse_numbers(text: &str) -> Result<Vec<f64>, ParseError> {
+    let mut numbers = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        numbers.extend(parse_line(line, i + 1, numbers.len())?);
+    }
+    Ok(numbers)
+}
+
+// the numbers on one line, for going through a text a line at a time. `line_number` and `before`
+// (how many numbers came before this line) are only for saying where a bad one was
+pub fn parse_line(line: &str, line_number: usize, before: usize) -> Result<Vec<f64>, ParseError> {
+    let mut numbers = Vec::new();
+    for (token, column) in tokens(line) {
+        match token.parse() {
+            Ok(n) => numbers.push(n),
+            Err(_) => {
+                return Err(ParseError {
+                    token: token.to_string(),
+                    index: before + numbers.len() + 1,
+                    line: line_number,
+                    column,
+                })
+            }
+        }
+    }
+    Ok(numbers)
+}
+
+// everything between separators, with the column it starts at. a run of separators (", " or ",,")
+// doesn't make for any empty tokens
+fn tokens(line: &str) -> Vec<(&str, usize)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (column, (index, ch)) in line.char_indices().chain([(line.len(), ',')]).enumerate() {
+        match (start, is_separator(ch)) {
+            (None, false) => start = Some((index, column)),
+            (Some((from, from_column)), true) => {
+                tokens.push((&line[from..index], from_column + 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_layout() {
+        assert_eq!(
+            Ok(vec![1.0, -2.0, 3.5, 4.0, 5.0]),
+            parse_numbers("1, -2,3.5\n\n  4 ,,5\r\n")
+        );
+        assert_eq!(Ok(vec![]), parse_numbers(" ,\n"));
+    }
+
+    #[test]
+    fn says_where_it_went_wrong() {
+        assert_eq!(
+            Err(ParseError {
+                token: "x7".to_string(),
+                index: 4,
+                line: 2,
+                column: 5,
+            }),
+            // a non-breaking space is two bytes, but only one column
+            parse_numbers("1 2\n3, \u{a0}x7 8")
+        );
+        assert_eq!(
+            Err(ParseError {
+                token: "--".to_string(),
+                index: 12,
+                line: 7,
+                column: 3,
+            }),
+            parse_line("1 --", 7, 10)
+        );
+        assert_eq!(
+            "'x7' isn't a number (number 4, at line 2, column 6)",
+            parse_numbers("1 2\n3,   x7 8").unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,19 @@
+/*
+ * Descriptive statistics over a list of numbers, integers or floats. Everything lives in `stats`,
+ * which main.rs uses to summarize whatever numbers it's given; `Number` is what the list can be
+ * made of, and `input` reads one out of text. `histogram` draws a picture of it, `frequency`
+ * counts up how often each value comes up, and `summary` puts all the numbers in one place, ready
+ * to write out as JSON or CSV. `parallel` is how the stats keep up with lists of millions of
+ * numbers, and `rolling` with numbers that keep on coming.
+ */
+
+pub mod frequency;
+pub mod histogram;
+pub mod input;
+pub mod number;
+pub mod parallel;
+pub mod rolling;
+pub mod stats;
+pub mod summary;
+
+pub use number::Number;
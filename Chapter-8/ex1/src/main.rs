@@ -1,58 +1,397 @@
 /*
-Problem: Given a list of integers, use a vector and return the median (when sorted, the value in the middle position) and mode (the value that occurs most often; a hash map will be helpful here) of the list.
-*/
-
-use rand::{thread_rng, Rng};
-use std::collections::HashMap;
-
-fn median(list: &[i32]) -> f64 {
-    // if list size even, then return the average of the two middle numbers
-    let len = list.len();
-    if len % 2 == 0 {
-        let a = list[(len / 2) - 1];
-        let b = list[len / 2];
-        (a + b) as f64 / 2.0
-    } else {
-        list[len / 2] as f64
+ * Summarizes a list of numbers with everything in `stats`. The numbers come from the arguments,
+ * from `--file <path>`, or from stdin, separated by whitespace or commas:
+ *
+ *     ex1 3 1 4 1 5
+ *     ex1 --file measurements.csv
+ *     seq 1 100 | ex1
+ *
+ * With none of those (or with `--random`), it makes up a list of 50 to 100 random whole numbers
+ * from -4 to 4. `--random <count>` decides how many, `--min` and `--max` how small and big they can
+ * get, and `--seed` makes a run that can be repeated exactly:
+ *
+ *     ex1 --random 1000 --min 0 --max 100 --seed 42
+ *
+ * `--window <size>` follows the mean, median and standard deviation of the last `size` numbers
+ * instead, printing a line for every number as it comes in, so it can sit at the end of a pipe
+ * that never closes:
+ *
+ *     sensor-readings | ex1 --window 60
+ *
+ * `--histogram` draws a bar chart of the numbers after the summary, `--frequencies` lists how often
+ * each one comes up and whether there's more than one peak, and `--format json` or
+ * `--format csv` writes the summary out for another program to read instead.
+ */
+
+use ex1::{
+    frequency::{self, modality, number_frequency_table},
+    histogram::{self, default_bins, histogram},
+    input::{parse_line, parse_numbers},
+    rolling::RollingWindow,
+    stats::StatsError,
+    summary::Summary,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    env, fs,
+    io::{self, BufRead, IsTerminal, Read},
+    process,
+};
+
+const USAGE: &str =
+    "usage: ex1 [--random [count] [--min <n>] [--max <n>] [--seed <n>] | --file <path> | <numbers>...]
+           [--histogram] [--frequencies] [--format text|json|csv] [--window <size>]";
+
+// how long the longest bar in the histogram is
+const BAR_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+// how to make up numbers when there aren't any to read
+#[derive(Debug, PartialEq)]
+struct Random {
+    // None picks a count from 50 to 100
+    count: Option<usize>,
+    min: i64,
+    max: i64,
+    seed: Option<u64>,
+}
+
+impl Default for Random {
+    fn default() -> Random {
+        Random {
+            count: None,
+            min: -4,
+            max: 4,
+            seed: None,
+        }
     }
 }
 
-fn mode(list: &[i32]) -> Vec<(i32, i32)> {
-    // returns a vector containing the mode of the list. if there's multiple modes, then they're all returned
-    let mut map = HashMap::new();
-    for &n in list {
-        map.entry(n).and_modify(|count| *count += 1).or_insert(0);
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    random: bool,
+    generate: Random,
+    histogram: bool,
+    frequencies: bool,
+    format: Format,
+    // the size of the rolling window, if there is one
+    window: Option<usize>,
+    file: Option<String>,
+    // whatever's left over is the numbers themselves
+    numbers: Vec<String>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--random" => {
+                options.random = true;
+                // the count is optional, so only take what comes next if it is one
+                if let Some(count) = args.peek().and_then(|a| a.parse().ok()) {
+                    options.generate.count = Some(count);
+                    args.next();
+                }
+            }
+            "--min" | "--max" | "--seed" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a number after it", arg))?;
+                let bad = || format!("{} needs a whole number after it, not '{}'", arg, value);
+                match arg.as_str() {
+                    "--min" => options.generate.min = value.parse().map_err(|_| bad())?,
+                    "--max" => options.generate.max = value.parse().map_err(|_| bad())?,
+                    _ => options.generate.seed = Some(value.parse().map_err(|_| bad())?),
+                }
+                // none of them mean anything without random numbers
+                options.random = true;
+            }
+            "--histogram" => options.histogram = true,
+            "--frequencies" => options.frequencies = true,
+            "--file" => {
+                let path = args.next().ok_or("--file needs a path after it")?;
+                options.file = Some(path);
+            }
+            "--window" => {
+                let size = args.next().and_then(|size| size.parse().ok());
+                match size {
+                    Some(size) if size > 0 => options.window = Some(size),
+                    _ => return Err("--window needs a size of at least 1 after it".to_string()),
+                }
+            }
+            "--format" => {
+                options.format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    Some("csv") => Format::Csv,
+                    _ => return Err("--format needs text, json, or csv after it".to_string()),
+                }
+            }
+            // a negative number isn't a flag
+            flag if flag.starts_with("--") => return Err(format!("there's no {} option", flag)),
+            _ => options.numbers.push(arg),
+        }
     }
+    let sources = [
+        options.random,
+        options.file.is_some(),
+        !options.numbers.is_empty(),
+    ];
+    if sources.iter().filter(|&&given| given).count() > 1 {
+        return Err("give random numbers, a file, or some numbers, just one of them".to_string());
+    }
+    if options.generate.min > options.generate.max {
+        return Err("--min can't be bigger than --max".to_string());
+    }
+    if (options.histogram || options.frequencies) && options.format != Format::Text {
+        return Err("histograms and frequencies only go with the text format".to_string());
+    }
+    if options.window.is_some()
+        && (options.histogram || options.frequencies || options.format != Format::Text)
+    {
+        return Err("a rolling window prints its own numbers, and nothing else".to_string());
+    }
+    Ok(options)
+}
 
-    // map.into_iter().max_by_key(|&(_, v)| v)
-    let max_count = map.values().cloned().max().unwrap_or(0);
-    map.into_iter()
-        .filter(|&(_, val)| val == max_count)
+fn random_numbers(random: &Random) -> Vec<f64> {
+    let mut rng = match random.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let count = random.count.unwrap_or_else(|| rng.gen_range(50..=100));
+    (0..count)
+        .map(|_| rng.gen_range(random.min..=random.max) as f64)
         .collect()
 }
 
+// whether the numbers come from whatever's piped in
+fn from_stdin(options: &Options) -> bool {
+    options.file.is_none()
+        && options.numbers.is_empty()
+        && !options.random
+        && !io::stdin().is_terminal()
+}
+
+// the numbers to summarize, or what went wrong getting them
+fn numbers(options: &Options) -> Result<Vec<f64>, String> {
+    let text = if let Some(path) = &options.file {
+        fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path, e))?
+    } else if !options.numbers.is_empty() {
+        options.numbers.join(" ")
+    } else if !from_stdin(options) {
+        // nobody's typing numbers in by hand
+        return Ok(random_numbers(&options.generate));
+    } else {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("couldn't read stdin: {}", e))?;
+        text
+    };
+    parse_numbers(&text).map_err(|e| e.to_string())
+}
+
 fn main() {
-    let mut rng = thread_rng();
-    let mut list = Vec::new();
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    if let Some(size) = options.window {
+        if let Err(e) = roll(&options, size) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        return;
+    }
+    let mut list = match numbers(&options) {
+        Ok(list) => list,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
 
-    for _ in 0..rng.gen_range(50..=100) {
-        list.push(rng.gen_range(-4..=4));
+    list.sort_by(f64::total_cmp);
+    let summary = match Summary::of(&list) {
+        Ok(summary) => summary,
+        Err(e) => {
+            eprintln!("couldn't summarize the numbers: {}", e);
+            process::exit(1);
+        }
+    };
+    match options.format {
+        Format::Json => print!("{}", summary.to_json()),
+        Format::Csv => print!("{}", summary.to_csv()),
+        Format::Text => {
+            // a list somebody typed in or piped in, they've already seen
+            if options.random {
+                println!("{:?}", list);
+            }
+            print_summary(&summary);
+            if options.histogram {
+                if let Err(e) = print_histogram(&list) {
+                    eprintln!("couldn't draw a histogram: {}", e);
+                    process::exit(1);
+                }
+            }
+            if options.frequencies {
+                if let Err(e) = print_frequencies(&list) {
+                    eprintln!("couldn't count the numbers up: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
     }
+}
 
-    list.sort();
-    let med = median(&list);
-    let mode = mode(&list);
+// the rolling window's stats after every number. numbers from stdin get read a line at a time, so
+// each one is printed as soon as it's come in
+fn roll(options: &Options, size: usize) -> Result<(), String> {
+    let mut window = RollingWindow::new(size);
+    println!(
+        "{:>12} {:>12} {:>12} {:>12}",
+        "value", "mean", "median", "std dev"
+    );
+    let mut push = |x: f64| {
+        window.push(x);
+        // the window's only empty while nothing but NaNs have come in
+        if let (Ok(mean), Ok(median), Ok(std_dev)) =
+            (window.mean(), window.median(), window.std_dev())
+        {
+            println!(
+                "{:>12} {:>12.3} {:>12.3} {:>12.3}",
+                x, mean, median, std_dev
+            );
+        }
+    };
+    if !from_stdin(options) {
+        numbers(options)?.into_iter().for_each(push);
+        return Ok(());
+    }
+    let mut count = 0;
+    for (i, line) in io::stdin().lock().lines().enumerate() {
+        let line = line.map_err(|e| format!("couldn't read stdin: {}", e))?;
+        let numbers = parse_line(&line, i + 1, count).map_err(|e| e.to_string())?;
+        count += numbers.len();
+        numbers.into_iter().for_each(&mut push);
+    }
+    Ok(())
+}
 
-    println!("{:?}", list);
-    if mode.len() > 1 {
-        println!("Median: {med}\nModes: (occur {} times)", mode[0].1);
-        for (n, _count) in mode {
-            println!("\tMode: {}", n);
+fn print_summary(summary: &Summary) {
+    println!("Median: {}", summary.median);
+    match summary.modes.as_slice() {
+        [n] => println!("Mode: {} (occurs {} times)", n, summary.mode_count),
+        modes => {
+            println!("Modes: (occur {} times)", summary.mode_count);
+            for n in modes {
+                println!("\tMode: {}", n);
+            }
         }
-    } else {
-        println!(
-            "Median: {}\nMode: {} (occurs {} times)",
-            med, mode[0].0, mode[0].1
+    }
+
+    println!("Mean: {:.3}", summary.mean);
+    println!("Variance: {:.3}", summary.variance);
+    println!("Std dev: {:.3}", summary.std_dev);
+    println!(
+        "Min: {}, Max: {}, Range: {}",
+        summary.min,
+        summary.max,
+        summary.range()
+    );
+    println!(
+        "Five-number summary: {} / {} / {} / {} / {} (IQR {})",
+        summary.min, summary.q1, summary.median, summary.q3, summary.max, summary.iqr
+    );
+}
+
+fn print_histogram(list: &[f64]) -> Result<(), StatsError> {
+    let histogram = histogram(list, default_bins(list.len()))?;
+    print!("\n{}", histogram::render(&histogram, BAR_WIDTH));
+    Ok(())
+}
+
+fn print_frequencies(list: &[f64]) -> Result<(), StatsError> {
+    let table = number_frequency_table(list);
+    let modality = modality(&table)?;
+    print!("\n{}", frequency::render(&table));
+    println!("The numbers are {}", modality);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(
+            Ok(Options {
+                numbers: args("3 -1 4,1"),
+                ..Options::default()
+            }),
+            parse_args(args("3 -1 4,1"))
+        );
+        assert_eq!(
+            Ok(Options {
+                file: Some("data.csv".to_string()),
+                histogram: true,
+                ..Options::default()
+            }),
+            parse_args(args("--file data.csv --histogram"))
+        );
+        assert!(parse_args(args("--file")).is_err());
+        assert_eq!(
+            Ok(Options {
+                random: true,
+                format: Format::Csv,
+                ..Options::default()
+            }),
+            parse_args(args("--random --format csv"))
+        );
+        assert_eq!(
+            Ok(Options {
+                random: true,
+                generate: Random {
+                    count: Some(1000),
+                    min: -10,
+                    max: 10,
+                    seed: Some(42),
+                },
+                ..Options::default()
+            }),
+            parse_args(args("--random 1000 --min -10 --max 10 --seed 42"))
+        );
+        // asking for a seed is asking for random numbers
+        assert_eq!(Ok(true), parse_args(args("--seed 7")).map(|o| o.random));
+        assert!(parse_args(args("--random 1 2")).is_err());
+        assert!(parse_args(args("--min 5 --max 1")).is_err());
+        assert!(parse_args(args("--seed lots")).is_err());
+        assert!(parse_args(args("--format xml")).is_err());
+        assert!(parse_args(args("--histogram --format json")).is_err());
+        assert!(parse_args(args("--frequencies --format csv")).is_err());
+        assert_eq!(
+            Ok(Some(5)),
+            parse_args(args("--window 5 1 2 3")).map(|o| o.window)
         );
+        assert!(parse_args(args("--window 0")).is_err());
+        assert!(parse_args(args("--window 5 --histogram")).is_err());
+        assert!(parse_args(args("--loud")).is_err());
     }
 }
@@ -0,0 +1,79 @@
+/*
+ * What counts as a number to `stats`: any of Rust's integer or float types. They all convert to
+ * f64 for the arithmetic (which is exact for everything up to 32 bits, and close enough past that),
+ * and they all have a key that can go in a HashMap for counting, which f32 and f64 don't on their
+ * own since NaN != NaN.
+ *
+ * NaN is the one value that doesn't fit in anywhere: it isn't bigger or smaller than anything, so
+ * a list with one in it has no middle, no smallest value and no largest. `is_nan()` is how `stats`
+ * checks for it; for the integer types it's never true.
+ */
+
+use std::hash::Hash;
+
+// Send and Sync so a big list can be split up across threads
+pub trait Number: Copy + PartialOrd + Send + Sync {
+    type Key: Eq + Hash;
+
+    fn to_f64(self) -> f64;
+
+    // equal numbers have equal keys
+    fn key(self) -> Self::Key;
+
+    fn is_nan(self) -> bool {
+        false
+    }
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            type Key = $t;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn key(self) -> $t {
+                self
+            }
+        }
+    )*};
+}
+
+macro_rules! float {
+    ($($t:ty => $bits:ty),*) => {$(
+        impl Number for $t {
+            type Key = $bits;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            // -0.0 == 0.0, but their bits aren't the same
+            fn key(self) -> $bits {
+                if self == 0.0 { 0.0 as $t } else { self }.to_bits()
+            }
+
+            fn is_nan(self) -> bool {
+                <$t>::is_nan(self)
+            }
+        }
+    )*};
+}
+
+integer!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+float!(f32 => u32, f64 => u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        assert_eq!(0.0f64.key(), (-0.0f64).key());
+        assert_ne!(1.0f64.key(), 2.0f64.key());
+        assert_eq!(7u32.key(), 7);
+        assert!(f32::NAN.is_nan() && !Number::is_nan(3i64));
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * Spreading work over a big list across threads. `map_chunks()` cuts the list into one piece per
+ * thread, runs the same function over each piece on its own thread, and hands back the results in
+ * order, for the caller to merge: sums add up, extremes get compared again.
+ *
+ * Starting threads costs more than adding up a few thousand numbers, so `stats` only bothers for
+ * lists of at least `THRESHOLD` numbers, and uses as many threads as the machine has cores. The
+ * answers are the same either way, except that floats added up in a different order can round
+ * differently in the last digit or so.
+ */
+
+use std::thread;
+
+pub const THRESHOLD: usize = 1_000_000;
+
+// how many threads `stats` uses for a list of `len` numbers
+pub fn threads_for(len: usize) -> usize {
+    if len < THRESHOLD {
+        return 1;
+    }
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// `f` over `threads` pieces of `list` of about the same size, in order. never gives `f` an empty
+// piece, so there can be fewer results than threads for a short list (and none for an empty one)
+pub fn map_chunks<T: Sync, R: Send>(
+    list: &[T],
+    threads: usize,
+    f: impl Fn(&[T]) -> R + Sync,
+) -> Vec<R> {
+    if list.is_empty() {
+        return Vec::new();
+    }
+    let size = list.len().div_ceil(threads.max(1));
+    if size == list.len() {
+        // no need for a thread to do the whole thing
+        return vec![f(list)];
+    }
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = list
+            .chunks(size)
+            .map(|chunk| scope.spawn(move || f(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_in_order() {
+        let list: Vec<u32> = (1..=10).collect();
+        assert_eq!(
+            vec![6, 15, 24, 10],
+            map_chunks(&list, 4, |c| c.iter().sum::<u32>())
+        );
+        assert_eq!(vec![55], map_chunks(&list, 1, |c| c.iter().sum::<u32>()));
+        // more threads than numbers
+        assert_eq!(10, map_chunks(&list, 64, |c| c.len()).len());
+        assert!(map_chunks(&[] as &[u32], 4, |c| c.len()).is_empty());
+    }
+
+    #[test]
+    fn threshold() {
+        assert_eq!(1, threads_for(THRESHOLD - 1));
+        assert!(threads_for(THRESHOLD) >= 1);
+    }
+}
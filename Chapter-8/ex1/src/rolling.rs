@@ -0,0 +1,164 @@
+/*
+ * Statistics over the last so many numbers of a stream that doesn't end, like readings from a
+ * sensor. `RollingWindow::new(size)` holds on to the most recent `size` numbers pushed into it, and
+ * `mean()`, `median()` and `std_dev()` are always about just those.
+ *
+ * None of them go back over the whole window. The window keeps a running sum and sum of squares
+ * for the mean and standard deviation, adding each number as it comes in and taking it back out as
+ * it leaves. Taking a number back out of a float sum doesn't always land exactly where it was
+ * before, so the sums are worked out again from scratch every `size` pushes, before the error can
+ * build up; that works out to O(1) a push on average. For the median, it keeps a sorted copy of the
+ * window too, and finds where each number goes (or was) with a binary search. Moving everything
+ * after it up or down by one is O(size), but it's one quick copy of memory, and a sorted window's
+ * middle is right there to look at.
+ *
+ * A NaN has no place in the order, and would make the sums NaN until the next recount, so `push()`
+ * skips NaNs (a sensor that sends one has nothing to say).
+ */
+
+use crate::stats::StatsError;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+pub struct RollingWindow {
+    size: usize,
+    // oldest first, so the one to drop is at the front
+    values: VecDeque<f64>,
+    sorted: Vec<f64>,
+    sum: f64,
+    sum_of_squares: f64,
+    // pushes since the sums were last worked out from scratch
+    since_recount: usize,
+}
+
+impl RollingWindow {
+    // panics if `size` is 0, since a window that can't hold anything has nothing to say
+    pub fn new(size: usize) -> RollingWindow {
+        assert!(size > 0, "a rolling window has to hold at least one number");
+        RollingWindow {
+            size,
+            values: VecDeque::with_capacity(size),
+            sorted: Vec::with_capacity(size),
+            sum: 0.0,
+            sum_of_squares: 0.0,
+            since_recount: 0,
+        }
+    }
+
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        if self.values.len() == self.size {
+            let oldest = self.values.pop_front().unwrap();
+            let at = self.position(oldest).unwrap();
+            self.sorted.remove(at);
+            self.sum -= oldest;
+            self.sum_of_squares -= oldest * oldest;
+        }
+        self.values.push_back(x);
+        let at = self.position(x).unwrap_or_else(|at| at);
+        self.sorted.insert(at, x);
+        self.sum += x;
+        self.sum_of_squares += x * x;
+
+        self.since_recount += 1;
+        if self.since_recount == self.size {
+            self.sum = self.values.iter().sum();
+            self.sum_of_squares = self.values.iter().map(|x| x * x).sum();
+            self.since_recount = 0;
+        }
+    }
+
+    // where `x` is (or would go) in the sorted window
+    fn position(&self, x: f64) -> Result<usize, usize> {
+        self.sorted.binary_search_by(|n| n.total_cmp(&x))
+    }
+
+    // how many numbers are in the window, which is `size` once that many have been pushed
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.size
+    }
+
+    pub fn mean(&self) -> Result<f64, StatsError> {
+        if self.is_empty() {
+            return Err(StatsError::EmptyInput);
+        }
+        Ok(self.sum / self.len() as f64)
+    }
+
+    pub fn median(&self) -> Result<f64, StatsError> {
+        crate::stats::median(&self.sorted)
+    }
+
+    // the population standard deviation, like `stats::std_dev`
+    pub fn std_dev(&self) -> Result<f64, StatsError> {
+        let mean = self.mean()?;
+        let variance = self.sum_of_squares / self.len() as f64 - mean * mean;
+        // rounding can take a variance of 0 a hair below it
+        Ok(variance.max(0.0).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::{mean, median_unsorted, std_dev};
+
+    #[test]
+    fn fills_up_then_slides() {
+        let mut window = RollingWindow::new(3);
+        assert_eq!(Err(StatsError::EmptyInput), window.mean());
+        window.push(4.0);
+        window.push(1.0);
+        assert_eq!((2, false), (window.len(), window.is_full()));
+        assert_eq!(Ok(2.5), window.median());
+
+        window.push(7.0);
+        window.push(10.0);
+        // 4 has dropped out
+        assert_eq!((3, true), (window.len(), window.is_full()));
+        assert_eq!(Ok(6.0), window.mean());
+        assert_eq!(Ok(7.0), window.median());
+        assert_eq!(Ok(14.0f64.sqrt()), window.std_dev());
+    }
+
+    #[test]
+    fn same_as_starting_over() {
+        let stream: Vec<f64> = (0..500)
+            .map(|i| ((i * 37) % 101) as f64 / 7.0 - 5.0)
+            .collect();
+        let mut window = RollingWindow::new(25);
+        for (i, &x) in stream.iter().enumerate() {
+            window.push(x);
+            let last = &stream[(i + 1).saturating_sub(25)..=i];
+            assert!((mean(last).unwrap() - window.mean().unwrap()).abs() < 1e-9);
+            assert_eq!(median_unsorted(last), window.median());
+            assert!((std_dev(last).unwrap() - window.std_dev().unwrap()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn nans_and_repeats() {
+        let mut window = RollingWindow::new(2);
+        for x in [3.0, 3.0, f64::NAN, 3.0] {
+            window.push(x);
+        }
+        assert_eq!(2, window.len());
+        assert_eq!(Ok(0.0), window.std_dev());
+    }
+
+    #[test]
+    #[should_panic]
+    fn nothing_to_hold() {
+        RollingWindow::new(0);
+    }
+}
@@ -0,0 +1,606 @@
+/*
+ * Problem: Given a list of integers, use a vector and return the median (when sorted, the value
+ * in the middle position) and mode (the value that occurs most often; a hash map will be helpful
+ * here) of the list.
+ *
+ * Along with those two, the usual summary: the mean, variance and standard deviation, the
+ * smallest and biggest values and the range between them, weighted means and medians, and
+ * percentiles. They work on a slice of any `Number`, floats included, and none of them panic: an
+ * empty list, a NaN where things need putting in order, and the other ways a question can have no
+ * answer each get a `StatsError` of their own.
+ */
+
+use crate::{
+    parallel::{map_chunks, threads_for},
+    Number,
+};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    error::Error,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatsError {
+    EmptyInput,
+    // a NaN got in the way of putting the list in order
+    NotANumber,
+    // how many values are tied for most common
+    NoUniqueMode(usize),
+    // percentiles go from 0 to 100
+    BadPercentile(f64),
+    // a histogram needs at least one bin to put things in
+    NoBins,
+    // a histogram can't have a bin that ends at infinity
+    Infinite,
+    // weights can't be negative, infinite, or NaN, or add up to infinity
+    BadWeight(f64),
+    // every weight was 0, so nothing counts
+    ZeroWeight,
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatsError::EmptyInput => write!(f, "there aren't any numbers"),
+            StatsError::NotANumber => write!(f, "there's a NaN in the numbers"),
+            StatsError::NoUniqueMode(n) => write!(f, "{} values are tied for most common", n),
+            StatsError::BadPercentile(p) => {
+                write!(f, "{} isn't a percentile, they go from 0 to 100", p)
+            }
+            StatsError::NoBins => write!(f, "a histogram needs at least one bin"),
+            StatsError::Infinite => write!(
+                f,
+                "there's an infinity in the numbers, and no bin can hold it"
+            ),
+            StatsError::BadWeight(w) => {
+                write!(
+                    f,
+                    "{} can't be a weight, they have to be 0 or more and finite",
+                    w
+                )
+            }
+            StatsError::ZeroWeight => write!(f, "every weight is 0"),
+        }
+    }
+}
+
+impl Error for StatsError {}
+
+pub fn median<T: Number>(list: &[T]) -> Result<f64, StatsError> {
+    ordered(list)?;
+    // if list size even, then return the average of the two middle numbers
+    let len = list.len();
+    if len.is_multiple_of(2) {
+        let a = list[(len / 2) - 1].to_f64();
+        let b = list[len / 2].to_f64();
+        Ok((a + b) / 2.0)
+    } else {
+        Ok(list[len / 2].to_f64())
+    }
+}
+
+// the list can be in any order. rather than sorting a copy, `select_nth_unstable_by` moves the
+// middle value into place with everything smaller before it and everything bigger after, which is
+// O(n) on average instead of O(n log n). for an even length, the other middle value is the biggest
+// one in the smaller half. examples/median.rs times the two against each other
+pub fn median_unsorted<T: Number>(list: &[T]) -> Result<f64, StatsError> {
+    ordered(list)?;
+    let mut copy = list.to_vec();
+    let mid = copy.len() / 2;
+    // without NaNs every pair compares
+    let (smaller, &mut middle, _) =
+        copy.select_nth_unstable_by(mid, |a, b| a.partial_cmp(b).unwrap());
+    if list.len().is_multiple_of(2) {
+        // not empty, since there are at least two numbers
+        let below = max(smaller)?;
+        Ok((below.to_f64() + middle.to_f64()) / 2.0)
+    } else {
+        Ok(middle.to_f64())
+    }
+}
+
+// counting doesn't need numbers, so this works on anything that can be a HashMap key
+pub fn mode<T: Eq + Hash + Clone>(items: &[T]) -> Result<Vec<(T, usize)>, StatsError> {
+    // returns a vector containing the mode of the list. if there's multiple modes, then they're
+    // all returned
+    let mut map = HashMap::new();
+    for item in items {
+        *map.entry(item).or_insert(0) += 1;
+    }
+
+    let Some(&max_count) = map.values().max() else {
+        return Err(StatsError::EmptyInput);
+    };
+    Ok(map
+        .into_iter()
+        .filter(|&(_, count)| count == max_count)
+        .map(|(item, count)| (item.clone(), count))
+        .collect())
+}
+
+// the mode, as long as there's only one
+pub fn unique_mode<T: Eq + Hash + Clone>(items: &[T]) -> Result<(T, usize), StatsError> {
+    let mut modes = mode(items)?;
+    match modes.len() {
+        1 => Ok(modes.remove(0)),
+        n => Err(StatsError::NoUniqueMode(n)),
+    }
+}
+
+// `mode` for numbers, floats included, smallest first. floats can't be HashMap keys, so this goes
+// by each number's `key()`. NaNs aren't equal to anything, themselves included, so they never
+// count
+pub fn number_mode<T: Number>(list: &[T]) -> Result<Vec<(T, usize)>, StatsError> {
+    let keyed: Vec<ByKey<T>> = list
+        .iter()
+        .filter(|n| !n.is_nan())
+        .map(|&n| ByKey(n))
+        .collect();
+    let mut modes: Vec<(T, usize)> = mode(&keyed)?
+        .into_iter()
+        .map(|(n, count)| (n.0, count))
+        .collect();
+    modes.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+    Ok(modes)
+}
+
+// a number that can go in a HashMap, going by its key
+#[derive(Clone, Copy)]
+pub(crate) struct ByKey<T>(pub(crate) T);
+
+impl<T: Number> PartialEq for ByKey<T> {
+    fn eq(&self, other: &ByKey<T>) -> bool {
+        self.0.key() == other.0.key()
+    }
+}
+
+impl<T: Number> Eq for ByKey<T> {}
+
+impl<T: Number> Hash for ByKey<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.key().hash(state);
+    }
+}
+
+// a NaN anywhere makes the mean (and everything built on it) NaN, the same as adding it up by hand.
+// lists of a million numbers or more are split across threads, like min and max; see parallel.rs
+pub fn mean<T: Number>(list: &[T]) -> Result<f64, StatsError> {
+    mean_on(list, threads_for(list.len()))
+}
+
+fn mean_on<T: Number>(list: &[T], threads: usize) -> Result<f64, StatsError> {
+    if list.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    // summed as f64 so a long list of big numbers can't overflow
+    let sums = map_chunks(list, threads, |chunk| {
+        chunk.iter().map(|n| n.to_f64()).sum::<f64>()
+    });
+    Ok(sums.into_iter().sum::<f64>() / list.len() as f64)
+}
+
+// the population variance: the average squared distance from the mean, dividing by the length of
+// the list rather than one less. that's the right one when the list is all the data there is,
+// rather than a sample of something bigger
+pub fn variance<T: Number>(list: &[T]) -> Result<f64, StatsError> {
+    variance_on(list, threads_for(list.len()))
+}
+
+fn variance_on<T: Number>(list: &[T], threads: usize) -> Result<f64, StatsError> {
+    let mean = mean_on(list, threads)?;
+    let squares = map_chunks(list, threads, |chunk| {
+        chunk
+            .iter()
+            .map(|n| (n.to_f64() - mean).powi(2))
+            .sum::<f64>()
+    });
+    Ok(squares.into_iter().sum::<f64>() / list.len() as f64)
+}
+
+// in the same units as the list itself, unlike the variance
+pub fn std_dev<T: Number>(list: &[T]) -> Result<f64, StatsError> {
+    variance(list).map(f64::sqrt)
+}
+
+pub fn min<T: Number>(list: &[T]) -> Result<T, StatsError> {
+    extreme(list, Ordering::Less, threads_for(list.len()))
+}
+
+pub fn max<T: Number>(list: &[T]) -> Result<T, StatsError> {
+    extreme(list, Ordering::Greater, threads_for(list.len()))
+}
+
+// as an f64, since i32::MAX - i32::MIN doesn't fit in an i32
+pub fn range<T: Number>(list: &[T]) -> Result<f64, StatsError> {
+    Ok(max(list)?.to_f64() - min(list)?.to_f64())
+}
+
+fn extreme<T: Number>(list: &[T], wanted: Ordering, threads: usize) -> Result<T, StatsError> {
+    ordered(list)?;
+    let pick = |best: T, n: T| match n.partial_cmp(&best) == Some(wanted) {
+        true => n,
+        false => best,
+    };
+    // each piece's extreme, then the extreme of those. the pieces are never empty
+    let extremes = map_chunks(list, threads, |chunk| {
+        chunk.iter().copied().reduce(pick).unwrap()
+    });
+    // ordered() already turned away an empty list
+    Ok(extremes.into_iter().reduce(pick).unwrap())
+}
+
+// whether there's anything to put in order, and nothing that can't be
+fn ordered<T: Number>(list: &[T]) -> Result<(), StatsError> {
+    if list.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    let nans = map_chunks(list, threads_for(list.len()), |chunk| {
+        chunk.iter().any(|n| n.is_nan())
+    });
+    match nans.contains(&true) {
+        true => Err(StatsError::NotANumber),
+        false => Ok(()),
+    }
+}
+
+fn sorted<T: Number>(list: &[T]) -> Result<Vec<T>, StatsError> {
+    ordered(list)?;
+    let mut sorted = list.to_vec();
+    // without NaNs every pair compares
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(sorted)
+}
+
+// the total weight, once it's been checked there's something to weigh
+fn total_weight<T: Number>(list: &[(T, f64)]) -> Result<f64, StatsError> {
+    if list.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if let Some(&(_, w)) = list.iter().find(|(_, w)| !w.is_finite() || *w < 0.0) {
+        return Err(StatsError::BadWeight(w));
+    }
+    // finite weights can still add up to infinity, and then there's no telling where half of it is
+    match list.iter().map(|(_, w)| w).sum() {
+        0.0 => Err(StatsError::ZeroWeight),
+        total if total == f64::INFINITY => Err(StatsError::BadWeight(total)),
+        total => Ok(total),
+    }
+}
+
+// (value, weight) pairs, for when some samples count for more than others. a weight of 0 means
+// that sample doesn't count; with every weight 1 it's the same as `mean`
+pub fn weighted_mean<T: Number>(list: &[(T, f64)]) -> Result<f64, StatsError> {
+    let total = total_weight(list)?;
+    let sum: f64 = list.iter().map(|(n, w)| n.to_f64() * w).sum();
+    Ok(sum / total)
+}
+
+// the pairs can be in any order. it's the value where the samples up to and including it carry at
+// least half the total weight; if they carry exactly half, it's halfway between that value and the
+// next one that carries any weight, like an even-length list's median
+pub fn weighted_median<T: Number>(list: &[(T, f64)]) -> Result<f64, StatsError> {
+    let total = total_weight(list)?;
+    if list.iter().any(|(n, _)| n.is_nan()) {
+        return Err(StatsError::NotANumber);
+    }
+    // the ones that don't count can't be the median, or the next value after it
+    let mut sorted: Vec<(T, f64)> = list.iter().copied().filter(|&(_, w)| w > 0.0).collect();
+    sorted.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+    let mut so_far = 0.0;
+    for (i, &(n, w)) in sorted.iter().enumerate() {
+        so_far += w;
+        if so_far * 2.0 == total {
+            // exactly half, so it's between this one and the next. there's always a next one
+            // unless rounding says otherwise
+            if let Some(&(next, _)) = sorted.get(i + 1) {
+                return Ok((n.to_f64() + next.to_f64()) / 2.0);
+            }
+        }
+        if so_far * 2.0 > total {
+            return Ok(n.to_f64());
+        }
+    }
+    // rounding can leave the last sum a hair short of the total it was added up from another way
+    Ok(sorted[sorted.len() - 1].0.to_f64())
+}
+
+// the value `p` percent of the way through the sorted list, which can be in any order. the rank is
+// `p / 100 * (len - 1)` counting from 0, interpolating linearly between the values either side
+// when it falls in between, the same method as Excel's PERCENTILE.INC and numpy's default. so the
+// 0th percentile is the smallest value, the 100th the largest, and the 50th the median
+pub fn percentile<T: Number>(list: &[T], p: f64) -> Result<f64, StatsError> {
+    percentile_of_sorted(&sorted(list)?, p)
+}
+
+fn percentile_of_sorted<T: Number>(sorted: &[T], p: f64) -> Result<f64, StatsError> {
+    if !(0.0..=100.0).contains(&p) {
+        return Err(StatsError::BadPercentile(p));
+    }
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let below = sorted[rank.floor() as usize].to_f64();
+    let above = sorted[rank.ceil() as usize].to_f64();
+    Ok(below + (above - below) * rank.fract())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quartiles {
+    pub q1: f64,
+    // the median
+    pub q2: f64,
+    pub q3: f64,
+    // q3 - q1, how spread out the middle half is
+    pub iqr: f64,
+}
+
+// the 25th, 50th and 75th percentiles
+pub fn quartiles<T: Number>(list: &[T]) -> Result<Quartiles, StatsError> {
+    let sorted = sorted(list)?;
+    let q1 = percentile_of_sorted(&sorted, 25.0)?;
+    let q3 = percentile_of_sorted(&sorted, 75.0)?;
+    Ok(Quartiles {
+        q1,
+        q2: percentile_of_sorted(&sorted, 50.0)?,
+        q3,
+        iqr: q3 - q1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY: [i32; 0] = [];
+
+    #[test]
+    fn medians() {
+        assert_eq!(Ok(2.0), median(&[1, 2, 3]));
+        assert_eq!(Ok(2.5), median(&[1, 2, 3, 4]));
+        assert_eq!(Ok(-1.5), median(&[-4, -2, -1, 3]));
+        assert_eq!(Err(StatsError::EmptyInput), median(&EMPTY));
+    }
+
+    #[test]
+    fn unsorted_medians() {
+        assert_eq!(Ok(2.0), median_unsorted(&[3, 1, 2]));
+        assert_eq!(Ok(2.5), median_unsorted(&[4, 1, 3, 2]));
+        assert_eq!(Ok(7.0), median_unsorted(&[7]));
+        assert_eq!(Err(StatsError::EmptyInput), median_unsorted(&EMPTY));
+        assert_eq!(
+            Err(StatsError::NotANumber),
+            median_unsorted(&[f64::NAN, 1.0])
+        );
+
+        // the same as sorting first, whatever order things come in
+        let list: Vec<i32> = (0..1001).map(|i| (i * 7919) % 1013 - 500).collect();
+        for len in [1000, 1001] {
+            let mut sorted = list[..len].to_vec();
+            sorted.sort();
+            assert_eq!(median(&sorted), median_unsorted(&list[..len]));
+        }
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(Ok(vec![(3, 2)]), mode(&[1, 3, 3, 2]));
+        assert_eq!(Ok(vec![(1, 1)]), mode(&[1]));
+        let mut modes = mode(&[1, 3, 1, 3, 2]).unwrap();
+        modes.sort();
+        assert_eq!(vec![(1, 2), (3, 2)], modes);
+        assert_eq!(Err(StatsError::EmptyInput), mode(&EMPTY));
+    }
+
+    #[test]
+    fn modes_of_anything() {
+        assert_eq!(Ok(vec![("b", 3)]), mode(&["a", "b", "c", "b", "b", "a"]));
+        let words: Vec<String> = "the cat and the hat".split(' ').map(String::from).collect();
+        assert_eq!(Ok(("the".to_string(), 2)), unique_mode(&words));
+
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        enum Weather {
+            Sun,
+            Rain,
+        }
+        assert_eq!(
+            Ok((Weather::Rain, 2)),
+            unique_mode(&[Weather::Rain, Weather::Sun, Weather::Rain])
+        );
+    }
+
+    #[test]
+    fn number_modes() {
+        assert_eq!(Ok(vec![(1, 2), (3, 2)]), number_mode(&[3, 1, 3, 2, 1]));
+        assert_eq!(Ok(vec![(2.5, 2)]), number_mode(&[2.5, 1.0, 2.5]));
+        // -0.0 and 0.0 are the same number
+        assert_eq!(Ok(vec![(0.0, 2)]), number_mode(&[-0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn unique_modes() {
+        assert_eq!(Ok((3, 2)), unique_mode(&[1, 3, 3, 2]));
+        assert_eq!(
+            Err(StatsError::NoUniqueMode(2)),
+            unique_mode(&[1, 3, 1, 3, 2])
+        );
+        assert_eq!(Err(StatsError::EmptyInput), unique_mode(&EMPTY));
+        assert_eq!(
+            "2 values are tied for most common",
+            StatsError::NoUniqueMode(2).to_string()
+        );
+    }
+
+    #[test]
+    fn means() {
+        assert_eq!(Ok(2.5), mean(&[1, 2, 3, 4]));
+        assert_eq!(Ok(-1.0), mean(&[-4, 2]));
+        assert_eq!(Ok(i32::MAX as f64), mean(&[i32::MAX, i32::MAX]));
+        assert_eq!(Err(StatsError::EmptyInput), mean(&EMPTY));
+    }
+
+    #[test]
+    fn spread() {
+        let list = [2, 4, 4, 4, 5, 5, 7, 9];
+        assert_eq!(Ok(4.0), variance(&list));
+        assert_eq!(Ok(2.0), std_dev(&list));
+        assert_eq!(Ok(0.0), variance(&[7]));
+        assert_eq!(Err(StatsError::EmptyInput), std_dev(&EMPTY));
+    }
+
+    #[test]
+    fn extremes() {
+        let list = [3, -4, 9, 0];
+        assert_eq!(Ok(-4), min(&list));
+        assert_eq!(Ok(9), max(&list));
+        assert_eq!(Ok(13.0), range(&list));
+        assert_eq!(Ok(u32::MAX as f64), range(&[i32::MIN, i32::MAX]));
+        let empty = Err(StatsError::EmptyInput);
+        assert_eq!((empty, empty), (min(&EMPTY), max(&EMPTY)));
+        assert_eq!(Err(StatsError::EmptyInput), range(&EMPTY));
+    }
+
+    #[test]
+    fn across_threads() {
+        // -5000 to 5000, shuffled. the mean's a whole number, so everything adds up exactly in any
+        // order and the answers have to be exactly the same
+        let list: Vec<i64> = (0..10_001).map(|i| (i * 7919) % 10_001 - 5000).collect();
+        for threads in [2, 3, 8] {
+            assert_eq!(mean_on(&list, 1), mean_on(&list, threads));
+            assert_eq!(variance_on(&list, 1), variance_on(&list, threads));
+            assert_eq!(min(&list), extreme(&list, Ordering::Less, threads));
+            assert_eq!(max(&list), extreme(&list, Ordering::Greater, threads));
+        }
+        assert_eq!(Err(StatsError::EmptyInput), mean_on(&EMPTY, 4));
+    }
+
+    #[test]
+    fn past_the_threshold() {
+        let list: Vec<i32> = (0..crate::parallel::THRESHOLD as i32 * 2).collect();
+        assert_eq!(Ok(999_999.5), mean(&list));
+        assert_eq!(Ok(0), min(&list));
+        assert_eq!(Ok(1_999_999), max(&list));
+    }
+
+    #[test]
+    fn weighted() {
+        let list = [(1, 1.0), (2, 1.0), (3, 2.0)];
+        assert_eq!(Ok(2.25), weighted_mean(&list));
+        assert_eq!(Ok(2.5), weighted_median(&list));
+        assert_eq!(Ok(3.0), weighted_median(&[(3, 3.0), (1, 1.0), (2, 1.0)]));
+        // a weight of 0 means it doesn't count at all
+        assert_eq!(
+            Ok(1.5),
+            weighted_mean(&[(1.0, 1.0), (2.0, 1.0), (100.0, 0.0)])
+        );
+        assert_eq!(Ok(1.5), weighted_median(&[(1, 1.0), (100, 0.0), (2, 1.0)]));
+
+        // all the same weight is just the mean and median
+        let plain = [5, 1, 4, 2];
+        let even: Vec<(i32, f64)> = plain.iter().map(|&n| (n, 1.0)).collect();
+        assert_eq!(mean(&plain), weighted_mean(&even));
+        assert_eq!(median_unsorted(&plain), weighted_median(&even));
+    }
+
+    #[test]
+    fn bad_weights() {
+        assert_eq!(
+            Err(StatsError::BadWeight(-1.0)),
+            weighted_mean(&[(1, 2.0), (2, -1.0)])
+        );
+        assert!(matches!(
+            weighted_median(&[(1, f64::NAN)]),
+            Err(StatsError::BadWeight(w)) if w.is_nan()
+        ));
+        assert_eq!(
+            Err(StatsError::BadWeight(f64::INFINITY)),
+            weighted_median(&[(5, f64::INFINITY)])
+        );
+        assert_eq!(
+            Err(StatsError::BadWeight(f64::INFINITY)),
+            weighted_mean(&[(1, 1.0), (2, f64::INFINITY)])
+        );
+        // finite weights that add up to infinity
+        assert_eq!(
+            Err(StatsError::BadWeight(f64::INFINITY)),
+            weighted_median(&[(1, f64::MAX), (2, 1.0), (3, f64::MAX)])
+        );
+        assert_eq!(
+            Err(StatsError::BadWeight(f64::INFINITY)),
+            weighted_mean(&[(1, f64::MAX), (2, f64::MAX)])
+        );
+        assert_eq!(
+            Err(StatsError::ZeroWeight),
+            weighted_median(&[(1, 0.0), (2, 0.0)])
+        );
+        assert_eq!(Err(StatsError::EmptyInput), weighted_mean::<i32>(&[]));
+        assert_eq!(
+            Err(StatsError::NotANumber),
+            weighted_median(&[(f64::NAN, 1.0)])
+        );
+    }
+
+    #[test]
+    fn percentiles() {
+        let list = [15, 20, 35, 40, 50];
+        assert_eq!(Ok(15.0), percentile(&list, 0.0));
+        assert_eq!(Ok(50.0), percentile(&list, 100.0));
+        assert_eq!(Ok(35.0), percentile(&list, 50.0));
+        // rank 0.4 * 4 = 1.6, so 60% of the way from 20 to 35
+        assert_eq!(Ok(29.0), percentile(&list, 40.0));
+        // order doesn't matter
+        assert_eq!(Ok(29.0), percentile(&[50, 35, 15, 40, 20], 40.0));
+        assert_eq!(
+            Err(StatsError::BadPercentile(101.0)),
+            percentile(&list, 101.0)
+        );
+        assert_eq!(Err(StatsError::EmptyInput), percentile(&EMPTY, 50.0));
+    }
+
+    #[test]
+    fn quartiles_and_iqr() {
+        assert_eq!(
+            Ok(Quartiles {
+                q1: 2.75,
+                q2: 4.5,
+                q3: 6.25,
+                iqr: 3.5
+            }),
+            quartiles(&[8, 1, 2, 3, 4, 5, 6, 7])
+        );
+        let sorted = [1, 2, 2, 3, 9];
+        assert_eq!(median(&sorted), quartiles(&sorted).map(|q| q.q2));
+        assert_eq!(Err(StatsError::EmptyInput), quartiles(&EMPTY));
+    }
+
+    #[test]
+    fn other_number_types() {
+        assert_eq!(Ok(2.25), median(&[1.5, 2.0, 2.5, 9.0]));
+        assert_eq!(
+            Ok(5_000_000_000.0),
+            mean(&[4_000_000_000i64, 6_000_000_000])
+        );
+        assert_eq!(Ok(u32::MAX), max(&[0, u32::MAX, 7]));
+        assert_eq!(Ok(-0.5), min(&[3.0, -0.5, 0.0]));
+        assert_eq!(Ok(0.5), percentile(&[1.0f32, 0.0], 50.0));
+    }
+
+    #[test]
+    fn nan_policy() {
+        let list = [1.0, f64::NAN, 3.0];
+        let nan = Err(StatsError::NotANumber);
+        assert_eq!(nan, median(&list));
+        assert_eq!((nan, nan, nan), (min(&list), max(&list), range(&list)));
+        assert_eq!(nan, percentile(&list, 50.0));
+        assert_eq!(Err(StatsError::NotANumber), quartiles(&list));
+        assert!(mean(&list).unwrap().is_nan());
+        assert!(std_dev(&list).unwrap().is_nan());
+        // the NaNs don't count, so there's a tie between the other two
+        assert_eq!(
+            Ok(vec![(1.0, 1), (3.0, 1)]),
+            number_mode(&[f64::NAN, f64::NAN, 1.0, 3.0])
+        );
+        // and with nothing but NaNs, there's nothing to count
+        assert_eq!(Err(StatsError::EmptyInput), number_mode(&[f64::NAN]));
+    }
+}
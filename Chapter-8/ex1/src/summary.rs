@@ -0,0 +1,140 @@
+/*
+ * Everything `stats` can say about a list of numbers, worked out once, in a form other programs can
+ * read: `to_json()` gives an object with a field per statistic, and `to_csv()` a `statistic,value`
+ * row per statistic (with a row for each mode when there's more than one). Both are put together
+ * by hand, there being nothing in them that needs escaping.
+ *
+ * JSON has no NaN or infinity, so a statistic that comes out as either is written as null. In the
+ * CSV it's left empty.
+ */
+
+use crate::stats::{max, mean, median_unsorted, min, number_mode, quartiles, std_dev, variance};
+use crate::{stats::StatsError, Number};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub median: f64,
+    // every value tied for most common, smallest first, and how many times each of them occurs
+    pub modes: Vec<f64>,
+    pub mode_count: usize,
+    pub mean: f64,
+    pub variance: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub q1: f64,
+    pub q3: f64,
+    pub iqr: f64,
+}
+
+impl Summary {
+    // the list can be in any order
+    pub fn of<T: Number>(list: &[T]) -> Result<Summary, StatsError> {
+        let modes = number_mode(list)?;
+        let quartiles = quartiles(list)?;
+        Ok(Summary {
+            count: list.len(),
+            median: median_unsorted(list)?,
+            mode_count: modes[0].1,
+            modes: modes.into_iter().map(|(n, _)| n.to_f64()).collect(),
+            mean: mean(list)?,
+            variance: variance(list)?,
+            std_dev: std_dev(list)?,
+            min: min(list)?.to_f64(),
+            max: max(list)?.to_f64(),
+            q1: quartiles.q1,
+            q3: quartiles.q3,
+            iqr: quartiles.iqr,
+        })
+    }
+
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    // the name and value of every statistic that's a single number, in the order they're written
+    fn fields(&self) -> [(&'static str, f64); 12] {
+        [
+            ("count", self.count as f64),
+            ("median", self.median),
+            ("mode_count", self.mode_count as f64),
+            ("mean", self.mean),
+            ("variance", self.variance),
+            ("std_dev", self.std_dev),
+            ("min", self.min),
+            ("max", self.max),
+            ("range", self.range()),
+            ("q1", self.q1),
+            ("q3", self.q3),
+            ("iqr", self.iqr),
+        ]
+    }
+
+    pub fn to_json(&self) -> String {
+        let modes: Vec<String> = self.modes.iter().map(|&n| json_number(n)).collect();
+        let mut fields = vec![format!("  \"modes\": [{}]", modes.join(", "))];
+        for (name, value) in self.fields() {
+            fields.push(format!("  \"{}\": {}", name, json_number(value)));
+        }
+        format!("{{\n{}\n}}\n", fields.join(",\n"))
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("statistic,value\n");
+        for mode in &self.modes {
+            csv.push_str(&format!("mode,{}\n", csv_number(*mode)));
+        }
+        for (name, value) in self.fields() {
+            csv.push_str(&format!("{},{}\n", name, csv_number(value)));
+        }
+        csv
+    }
+}
+
+fn json_number(n: f64) -> String {
+    match n.is_finite() {
+        true => n.to_string(),
+        false => "null".to_string(),
+    }
+}
+
+fn csv_number(n: f64) -> String {
+    match n.is_finite() {
+        true => n.to_string(),
+        false => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let summary = Summary::of(&[4, 1, 2, 2, 5]).unwrap();
+        assert_eq!(5, summary.count);
+        assert_eq!(2.0, summary.median);
+        assert_eq!((vec![2.0], 2), (summary.modes.clone(), summary.mode_count));
+        assert_eq!(2.8, summary.mean);
+        assert_eq!(4.0, summary.range());
+        assert_eq!((2.0, 4.0, 2.0), (summary.q1, summary.q3, summary.iqr));
+        assert_eq!(Err(StatsError::EmptyInput), Summary::of::<i32>(&[]));
+    }
+
+    #[test]
+    fn json() {
+        let json = Summary::of(&[1, 3]).unwrap().to_json();
+        assert!(json.starts_with("{\n  \"modes\": [1, 3],\n  \"count\": 2,\n  \"median\": 2,\n"));
+        assert!(json.ends_with("  \"iqr\": 1\n}\n"));
+        let nan = Summary::of(&[f64::MAX, -f64::MAX]).unwrap().to_json();
+        assert!(nan.contains("\"range\": null,"));
+    }
+
+    #[test]
+    fn csv() {
+        let csv = Summary::of(&[1.5, 3.0]).unwrap().to_csv();
+        assert!(csv.starts_with("statistic,value\nmode,1.5\nmode,3\ncount,2\nmedian,2.25\n"));
+        assert!(csv.ends_with("q3,2.625\niqr,0.75\n"));
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    env, fs,
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ex1"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn numbers_as_arguments() {
+    let output = run(&["3", "1,4", "1", "5"], "");
+    assert!(output.status.success());
+    let summary = stdout(&output);
+    assert!(summary.contains("Median: 3\n"));
+    assert!(summary.contains("Mode: 1 "));
+    assert!(summary.contains("Min: 1, Max: 5, Range: 4\n"));
+}
+
+#[test]
+fn piped_through_stdin() {
+    let output = run(&[], "2.5\n-1, 4\n\n");
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Median: 2.5\n"));
+}
+
+#[test]
+fn from_a_file() {
+    let path = env::temp_dir().join(format!("stats-cli-{}.csv", std::process::id()));
+    fs::write(&path, "10,20\n30,40\n").unwrap();
+    let output = run(&["--file", path.to_str().unwrap()], "");
+    fs::remove_file(&path).unwrap();
+    assert!(stdout(&output).contains("Mean: 25.000\n"));
+}
+
+#[test]
+fn histogram() {
+    let output = run(&["--histogram", "0", "0", "1", "3"], "");
+    let summary = stdout(&output);
+    assert!(
+        summary.ends_with(&format!(
+            "\n[0, 1)  {}  2\n[1, 2)  {:<40}  1\n[2, 3]  {:<40}  1\n",
+            "█".repeat(40),
+            "█".repeat(20),
+            "█".repeat(20)
+        )),
+        "{}",
+        summary
+    );
+}
+
+#[test]
+fn structured_output() {
+    let json = stdout(&run(&["--format", "json", "1", "2", "2"], ""));
+    assert!(json.starts_with("{\n  \"modes\": [2],\n  \"count\": 3,\n"));
+    let csv = stdout(&run(&["--format", "csv"], "1 2 2"));
+    assert!(csv.starts_with("statistic,value\nmode,2\ncount,3\nmedian,2\n"));
+}
+
+#[test]
+fn seeded_random_numbers() {
+    let args = [
+        "--random", "20", "--min", "10", "--max", "12", "--seed", "42",
+    ];
+    let first = stdout(&run(&args, ""));
+    assert_eq!(first, stdout(&run(&args, "")));
+    // the list comes first, and every number in it is in range
+    let list = first.lines().next().unwrap();
+    let numbers: Vec<&str> = list.trim_matches(['[', ']']).split(", ").collect();
+    assert_eq!(20, numbers.len());
+    assert!(numbers.iter().all(|n| ["10.0", "11.0", "12.0"].contains(n)));
+}
+
+#[test]
+fn frequencies() {
+    let output = stdout(&run(&["--frequencies", "3", "1", "3", "1", "2"], ""));
+    assert!(
+        output.ends_with(
+            "\nvalue  count  percent\n    1      2    40.0%\n    3      2    40.0%\n    2      1    20.0%\n\
+             The numbers are bimodal\n"
+        ),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn rolling_window() {
+    let output = stdout(&run(&["--window", "2"], "1\n3, 8\n"));
+    let lines: Vec<Vec<&str>> = output
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(
+        vec![
+            vec!["value", "mean", "median", "std", "dev"],
+            vec!["1", "1.000", "1.000", "0.000"],
+            vec!["3", "2.000", "2.000", "1.000"],
+            vec!["8", "5.500", "5.500", "2.500"],
+        ],
+        lines
+    );
+
+    let output = run(&["--window", "2"], "1\n2 x\n");
+    assert_eq!(
+        "'x' isn't a number (number 3, at line 2, column 3)\n",
+        String::from_utf8(output.stderr).unwrap()
+    );
+}
+
+#[test]
+fn bad_numbers() {
+    let output = run(&[], "1 2\n3 three\n");
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "'three' isn't a number (number 4, at line 2, column 3)\n",
+        String::from_utf8(output.stderr).unwrap()
+    );
+
+    let output = run(&[], "");
+    assert_eq!(Some(1), output.status.code());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("there aren't any numbers"));
+
+    assert_eq!(Some(2), run(&["--file"], "").status.code());
+}
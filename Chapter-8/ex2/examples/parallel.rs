@@ -0,0 +1,34 @@
+/*
+ * How much faster translate_parallel is than plain translate on a big text, for a few thread
+ * counts. Run it in release mode, or the numbers don't mean much:
+ *
+ *     cargo run --release --example parallel
+ */
+
+use ex2::{translate, translate_parallel};
+use std::time::Instant;
+
+fn main() {
+    let paragraph = "Don't stop -- it's a well-known, state-of-the-art rock'n'roll band! \
+                     Strong queens sing ñandú élégant songs in the First light.\n";
+    let text = paragraph.repeat(200_000);
+    println!("{} MB of text", text.len() / 1_000_000);
+
+    let start = Instant::now();
+    let serial = translate(&text);
+    let serial_time = start.elapsed();
+    println!("serial:     {:>8.2?}", serial_time);
+
+    for threads in [2, 4, 8, 16] {
+        let start = Instant::now();
+        let parallel = translate_parallel(&text, threads);
+        let time = start.elapsed();
+        assert_eq!(serial, parallel);
+        println!(
+            "{:>2} threads: {:>8.2?} ({:.1}x)",
+            threads,
+            time,
+            serial_time.as_secs_f64() / time.as_secs_f64()
+        );
+    }
+}
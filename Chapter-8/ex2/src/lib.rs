@@ -0,0 +1,679 @@
+/*
+ * Convert strings to pig latin. The first consonant of each word is moved to the end of the word and “ay” is added, so “first” becomes “irst-fay.” Words that start with a vowel have “hay” added to the end instead (“apple” becomes “apple-hay”). Keep in mind the details about UTF-8 encoding!
+ *
+ * `to_list()`` takes in a string literal and outputs a vector of each component of the string; this includes properly separating words from everything around them. How it does that, and how to change what counts as part of a word, is up to the `Tokenizer` in tokenizer.rs.
+ *
+ * An apostrophe or hyphen with letters on both sides is part of the word rather than punctuation. A contraction is translated as one word with the apostrophe left where it was ("don't" becomes "on't-day"), and each part of a hyphenated word is translated on its own ("well-known" becomes "ell-way-nown-kay").
+ *
+ * The translated word keeps the casing of the original: "First" becomes "Irst-fay" and "HELLO" becomes "ELLO-HAY". Words with casing all over the place ("gOT") are left to fend for themselves.
+ *
+ * By default only the first consonant moves. `Translator::new().with_onset(Onset::Cluster)` moves every consonant before the first vowel instead, the way pig latin is usually spoken ("string" becomes "ing-stray" rather than "tring-say").
+ *
+ * `from_pig()` goes the other way, taking the letters between the '-' and the "ay" back to the front of the word. That works for either style, but some things can't be undone: "at-hay" could be "at" or "hat" (it always comes back as "at"), and "McDonald" comes back as "mcDonald". A translator `with_lossless(true)` writes pig latin that leaves nothing to guess, so its own `from_pig()` gets back exactly what went in.
+ *
+ * A line break always ends a word, so a line translates the same on its own as it does in the middle of a document. `translate_stream()` and `from_pig_stream()` lean on that to go through a `BufRead` one line at a time, which keeps memory flat however big the input is.
+ *
+ * Whitespace always ends a word too, so `translate_parallel()` can cut a big text into chunks right after a whitespace character, translate the chunks on separate threads, and glue the results back together in order, getting exactly what `translate()` would have.
+ *
+ * Markdown can go through a translator with `Tokenizer::new().with_markup(true)`: code spans, fenced code blocks, HTML tags, and link targets come out exactly as they went in, and only the prose around them gets translated. That holds line by line and chunk by chunk too, since the streams and `translate_parallel()` keep track of whether a line or chunk starts inside a code block.
+ *
+ * `to_pig()` moves the first letter together with any accents stacked on it, rather than slicing off one byte like it used to (which panicked on anything non-ASCII). A word with anything but letters in it ("abc123") is left as it is.
+ */
+
+pub mod tokenizer;
+
+pub use tokenizer::Tokenizer;
+
+use std::{
+    io::{self, BufRead, Write},
+    thread,
+};
+use tokenizer::{is_combining, is_joiner, Markup};
+
+// lowercase, accented ones included, so "élégant" starts with a vowel too
+const VOWELS: &str = "aeiouàáâãäåāăąèéêëēĕėęěìíîïĩīĭįòóôõöøōŏőùúûüũūŭůűų";
+
+fn is_vowel(ch: char) -> bool {
+    ch.to_lowercase().all(|c| VOWELS.contains(c))
+}
+
+// how a word is capitalized, so the translation can be capitalized the same way
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Case {
+    Lower,
+    // "First". a single capital letter, like "I", counts as this too
+    Title,
+    Upper,
+    Mixed,
+}
+
+fn case_of(word: &str) -> Case {
+    let letters: Vec<char> = word.chars().filter(|ch| ch.is_alphabetic()).collect();
+    match letters.as_slice() {
+        [first, rest @ ..]
+            if first.is_uppercase()
+                && !rest.is_empty()
+                && rest.iter().all(|ch| ch.is_uppercase()) =>
+        {
+            Case::Upper
+        }
+        [first, rest @ ..] if first.is_uppercase() && !rest.iter().any(|ch| ch.is_uppercase()) => {
+            Case::Title
+        }
+        letters if !letters.iter().any(|ch| ch.is_uppercase()) => Case::Lower,
+        _ => Case::Mixed,
+    }
+}
+
+fn with_case(pig: &str, case: Case) -> String {
+    match case {
+        Case::Upper => pig.to_uppercase(),
+        Case::Title => {
+            let lower = pig.to_lowercase();
+            match lower.char_indices().find(|(_, ch)| ch.is_alphabetic()) {
+                Some((index, ch)) => {
+                    let rest = &lower[index + ch.len_utf8()..];
+                    format!("{}{}{}", &lower[..index], ch.to_uppercase(), rest)
+                }
+                None => lower,
+            }
+        }
+        Case::Lower | Case::Mixed => pig.to_string(),
+    }
+}
+
+// splits with the default `Tokenizer`
+pub fn to_list(text: &str) -> Vec<&str> {
+    Tokenizer::default().tokens(text)
+}
+
+// which letters move to the end of a word that starts with a consonant
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Onset {
+    // just the first one: "string" -> "tring-say"
+    #[default]
+    FirstLetter,
+    // every consonant up to the first vowel, with "qu" sticking together: "string" -> "ing-stray",
+    // "queen" -> "een-quay". a "y" after the first letter counts as a vowel, so "rhythm" -> "ythm-rhay"
+    Cluster,
+}
+
+// the byte index where the letters that get moved end. accents stay with their letter
+fn onset_end(word: &str, onset: Onset) -> usize {
+    let mut end = 0;
+    let mut prev = None;
+    for (index, ch) in word.char_indices() {
+        if !is_combining(ch) {
+            let qu = matches!((prev, ch), (Some('q' | 'Q'), 'u' | 'U'));
+            // the apostrophe in "c'mon" ends the cluster too
+            let vowel = is_vowel(ch) || matches!(ch, 'y' | 'Y') || is_joiner(ch);
+            if index > 0 && (onset == Onset::FirstLetter || (vowel && !qu)) {
+                break;
+            }
+            prev = Some(ch);
+        }
+        end = index + ch.len_utf8();
+    }
+    end
+}
+
+fn to_pig(word: &str, onset: Onset, lossless: bool) -> String {
+    // each half of "well-known" is a word of its own: "ell-way-nown-kay". a part that moves all
+    // of itself, like the "t" in "t-shirt", would start with a hyphen of its own and come apart
+    // from the rest, so it's just "tay": "tay-hirt-say"
+    if word.contains('-') {
+        let parts: Vec<String> = word
+            .split('-')
+            .map(|part| {
+                let pig = to_pig(part, onset, lossless);
+                match pig.strip_prefix('-') {
+                    Some(joined) => joined.to_string(),
+                    None => pig,
+                }
+            })
+            .collect();
+        return parts.join("-");
+    }
+    let first = match word.chars().next() {
+        Some(first) if first.is_alphabetic() => first,
+        _ => return word.to_string(),
+    };
+    // a contraction keeps its apostrophe where it was: "don't" -> "on't-day"
+    match word
+        .chars()
+        .all(|ch| ch.is_alphabetic() || is_combining(ch) || is_joiner(ch))
+    {
+        true if lossless => {
+            // nothing moved means there was a vowel in front, or that everything would have ("x"
+            // is "x-ay"), and what did move keeps its case
+            let (mut head, mut rest) = match is_vowel(first) {
+                true => ("", word),
+                false => word.split_at(onset_end(word, onset)),
+            };
+            if rest.is_empty() {
+                (head, rest) = ("", word);
+            }
+            // the apostrophe in "c'mon" goes with the "c" ("mon-c'ay"), or it'd be left hanging
+            // off the front where the tokenizer takes it for punctuation
+            if let Some(joiner) = rest.chars().next().filter(|&ch| is_joiner(ch)) {
+                (head, rest) = word.split_at(head.len() + joiner.len_utf8());
+            }
+            format!("{}-{}ay", rest, head)
+        }
+        true => {
+            let pig = if is_vowel(first) {
+                format!("{}-hay", word)
+            } else {
+                let (head, rest) = word.split_at(onset_end(word, onset));
+                format!("{}-{}ay", rest, head.to_lowercase())
+            };
+            with_case(&pig, case_of(word))
+        }
+        false => word.to_string(),
+    }
+}
+
+fn is_word(token: &str) -> bool {
+    token.chars().next().is_some_and(char::is_alphabetic)
+        && token
+            .chars()
+            .all(|ch| ch.is_alphabetic() || is_combining(ch) || is_joiner(ch))
+}
+
+// a token with hyphens in it is pairs of "stem-end", one per word that was translated, joined by
+// more hyphens if the original was hyphenated itself, with the odd part that moved all of itself
+// ("tay" in "tay-hirt-say"). anything else that doesn't pair up stays as it is
+fn unpig_hyphenated(token: &str, lossless: bool) -> String {
+    let parts: Vec<&str> = token.split('-').collect();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < parts.len() {
+        match parts
+            .get(i + 1)
+            .and_then(|end| unpig(parts[i], end, lossless))
+        {
+            Some(word) => {
+                words.push(word);
+                i += 2;
+            }
+            None => {
+                let whole = unpig_whole(parts[i]).filter(|_| !lossless);
+                words.push(whole.unwrap_or_else(|| parts[i].to_string()));
+                i += 1;
+            }
+        }
+    }
+    words.join("-")
+}
+
+// "tay" back to "t": nothing but the consonants that moved, and the "ay"
+fn unpig_whole(part: &str) -> Option<String> {
+    let moved = part
+        .strip_suffix("ay")
+        .or_else(|| part.strip_suffix("AY"))?;
+    let consonants = moved.chars().next().is_some_and(char::is_alphabetic)
+        && moved
+            .chars()
+            .all(|ch| (ch.is_alphabetic() && !is_vowel(ch)) || is_combining(ch));
+    consonants.then(|| moved.to_string())
+}
+
+// "irst" and "fay" back to "first". None when `end` isn't the end of a pig latin word
+fn unpig(stem: &str, end: &str, lossless: bool) -> Option<String> {
+    if lossless {
+        return end
+            .strip_suffix("ay")
+            .map(|moved| format!("{}{}", moved, stem));
+    }
+    let moved = end.strip_suffix("ay").or_else(|| end.strip_suffix("AY"))?;
+    let starts_with_vowel = stem.chars().next().is_some_and(is_vowel);
+    let english = match moved {
+        "" => return None,
+        "h" | "H" if starts_with_vowel => stem.to_string(),
+        moved => format!("{}{}", moved, stem),
+    };
+    Some(with_case(&english, case_of(&format!("{}{}", stem, end))))
+}
+
+pub fn from_pig(text: &str) -> String {
+    Translator::default().from_pig(text)
+}
+
+fn unpig_tokens(tokens: &[&str], lossless: bool) -> String {
+    let mut english = String::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let unpigged = match tokens[i..] {
+            [word, ..] if is_word(word) && word.contains('-') => {
+                Some((unpig_hyphenated(word, lossless), 1))
+            }
+            // the whole word moved, like "-ᾥay"
+            ["-", end, ..]
+                if is_word(end) && !end.contains('-') && (i == 0 || !is_word(tokens[i - 1])) =>
+            {
+                unpig("", end, lossless).map(|word| (word, 2))
+            }
+            _ => None,
+        };
+        match unpigged {
+            Some((word, used)) => {
+                english.push_str(&word);
+                i += used;
+            }
+            None => {
+                english.push_str(tokens[i]);
+                i += 1;
+            }
+        }
+    }
+    english
+}
+
+// how to translate. `Translator::default()` is the classic one-letter style
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Translator {
+    pub onset: Onset,
+    pub tokenizer: Tokenizer,
+    // see `with_lossless`
+    pub lossless: bool,
+}
+
+impl Translator {
+    pub fn new() -> Translator {
+        Translator::default()
+    }
+
+    pub fn with_onset(mut self, onset: Onset) -> Translator {
+        self.onset = onset;
+        self
+    }
+
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> Translator {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    // pig latin that `from_pig` can always turn back into exactly what went in. the letters that
+    // move keep their case instead of the whole word being recapitalized ("First" -> "irst-Fay"),
+    // and a word that starts with a vowel gets "-ay" with nothing moved ("apple" -> "apple-ay") so
+    // it can't be confused with one that started with an h. so does a word with no vowel to stop
+    // at ("x" -> "x-ay"), and an apostrophe moves along with the letters in front of it ("c'mon"
+    // -> "mon-c'ay"), so nothing ends up with a hyphen or apostrophe dangling off one end
+    pub fn with_lossless(mut self, lossless: bool) -> Translator {
+        self.lossless = lossless;
+        self
+    }
+
+    // every word in `text` in pig latin, with everything in between left as it was
+    pub fn translate(&self, text: &str) -> String {
+        self.translate_from(text, &mut Markup::default())
+    }
+
+    // `translate`, picking up the markup wherever the text before this left it
+    fn translate_from(&self, text: &str, markup: &mut Markup) -> String {
+        let mut pig = String::new();
+        for (piece, skip) in self.tokenizer.split_markup(text, markup) {
+            match skip {
+                true => pig.push_str(piece),
+                false => pig.extend(
+                    self.tokenizer
+                        .words(piece)
+                        .into_iter()
+                        .map(|w| self.word(w)),
+                ),
+            }
+        }
+        pig
+    }
+
+    // like `translate`, but split across `threads` threads. only worth it for big texts
+    pub fn translate_parallel(&self, text: &str, threads: usize) -> String {
+        let chunks = chunks(text, threads.max(1));
+        // a chunk can start partway through a code block, which only going over the chunks before
+        // it can tell
+        let mut markup = Markup::default();
+        let starts: Vec<Markup> = chunks
+            .iter()
+            .map(|chunk| {
+                let start = markup;
+                self.tokenizer.split_markup(chunk, &mut markup);
+                start
+            })
+            .collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = chunks
+                .into_iter()
+                .zip(starts)
+                .map(|(chunk, mut markup)| {
+                    scope.spawn(move || self.translate_from(chunk, &mut markup))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+
+    // like `translate`, but reads and writes a line at a time
+    pub fn translate_stream(&self, input: impl BufRead, output: impl Write) -> io::Result<()> {
+        let mut markup = Markup::default();
+        each_line(input, output, |line| self.translate_from(line, &mut markup))
+    }
+
+    // `from_pig`, a line at a time
+    pub fn from_pig_stream(&self, input: impl BufRead, output: impl Write) -> io::Result<()> {
+        let mut markup = Markup::default();
+        each_line(input, output, |line| self.unpig_from(line, &mut markup))
+    }
+
+    // just the one word. anything that isn't all letters comes back unchanged
+    pub fn word(&self, word: &str) -> String {
+        to_pig(word, self.onset, self.lossless)
+    }
+
+    // back from pig latin, split up the same way `translate` would have
+    pub fn from_pig(&self, text: &str) -> String {
+        self.unpig_from(text, &mut Markup::default())
+    }
+
+    fn unpig_from(&self, text: &str, markup: &mut Markup) -> String {
+        let mut english = String::new();
+        for (piece, skip) in self.tokenizer.split_markup(text, markup) {
+            match skip {
+                true => english.push_str(piece),
+                false => {
+                    english.push_str(&unpig_tokens(&self.tokenizer.words(piece), self.lossless))
+                }
+            }
+        }
+        english
+    }
+}
+
+pub fn translate(text: &str) -> String {
+    Translator::default().translate(text)
+}
+
+pub fn translate_parallel(text: &str, threads: usize) -> String {
+    Translator::default().translate_parallel(text, threads)
+}
+
+// `text` in `n` pieces of about the same size, each one ending just after a whitespace character
+// (except the last) so no word gets cut in half. fewer than `n` if there isn't enough whitespace
+fn chunks(text: &str, n: usize) -> Vec<&str> {
+    let size = text.len() / n + 1;
+    let mut chunks = Vec::with_capacity(n);
+    let mut start = 0;
+    while start < text.len() {
+        let mut from = (start + size).min(text.len());
+        while !text.is_char_boundary(from) {
+            from += 1;
+        }
+        let end = text[from..]
+            .char_indices()
+            .find(|(_, ch)| ch.is_whitespace())
+            .map(|(i, ch)| from + i + ch.len_utf8())
+            .unwrap_or(text.len());
+        chunks.push(&text[start..end]);
+        start = end;
+    }
+    chunks
+}
+
+// so any string can do `"hello world".to_pig_latin()`, or `text.split(' ').map(str::to_pig_latin)`
+pub trait PigLatin {
+    fn to_pig_latin(&self) -> String;
+}
+
+impl PigLatin for str {
+    fn to_pig_latin(&self) -> String {
+        translate(self)
+    }
+}
+
+// `from_pig` a line at a time
+pub fn from_pig_stream(input: impl BufRead, output: impl Write) -> io::Result<()> {
+    Translator::default().from_pig_stream(input, output)
+}
+
+// runs `f` over every line of `input`, line endings included, and writes what it gives back
+fn each_line(
+    mut input: impl BufRead,
+    mut output: impl Write,
+    mut f: impl FnMut(&str) -> String,
+) -> io::Result<()> {
+    let mut line = String::new();
+    while input.read_line(&mut line)? > 0 {
+        output.write_all(f(&line).as_bytes())?;
+        line.clear();
+    }
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii() {
+        assert_eq!("irst-fay apple-hay!", translate("first apple!"));
+    }
+
+    #[test]
+    fn keeps_capitalization() {
+        assert_eq!("Irst-fay", to_pig("First", Onset::FirstLetter, false));
+        assert_eq!("ELLO-HAY", to_pig("HELLO", Onset::FirstLetter, false));
+        assert_eq!("APPLE-HAY", to_pig("APPLE", Onset::FirstLetter, false));
+        assert_eq!("I-hay", to_pig("I", Onset::FirstLetter, false));
+        assert_eq!("Élégant-hay", to_pig("Élégant", Onset::FirstLetter, false));
+        assert_eq!("OT-gay", to_pig("gOT", Onset::FirstLetter, false));
+    }
+
+    #[test]
+    fn translator_options() {
+        let text = "Strong queens, strong words";
+        assert_eq!(translate(text), Translator::new().translate(text));
+        assert_eq!(
+            "Ong-stray eens-quay, ong-stray ords-way",
+            Translator::new().with_onset(Onset::Cluster).translate(text)
+        );
+    }
+
+    #[test]
+    fn consonant_clusters() {
+        let cluster = |word| to_pig(word, Onset::Cluster, false);
+        assert_eq!("ing-stray", cluster("string"));
+        assert_eq!("een-quay", cluster("queen"));
+        assert_eq!("eal-squay", cluster("squeal"));
+        assert_eq!("Ythm-rhay", cluster("Rhythm"));
+        assert_eq!("ellow-yay", cluster("yellow"));
+        assert_eq!("apple-hay", cluster("apple"));
+        assert_eq!("ILL-CHAY", cluster("CHILL"));
+        assert_eq!("u-n\u{303}ay", cluster("n\u{303}u"));
+        assert_eq!("tring-say", to_pig("string", Onset::FirstLetter, false));
+    }
+
+    #[test]
+    fn back_from_pig_latin() {
+        assert_eq!("first apple!", from_pig("irst-fay apple-hay!"));
+        assert_eq!("First WORLD gOT", from_pig("Irst-fay ORLD-WAY OT-gay"));
+        assert_eq!("at", from_pig("at-hay"));
+        assert_eq!("string queen", from_pig("ing-stray een-quay"));
+        assert_eq!("ᾥ", from_pig("-ᾥay"));
+        // not pig latin, so left alone
+        assert_eq!("well-known - ok", from_pig("well-known - ok"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "I think; I gOT, it. ::'figured' out!!! Strong queens élégant ñandú";
+        for onset in [Onset::FirstLetter, Onset::Cluster] {
+            let pig = Translator::new().with_onset(onset).translate(text);
+            assert_eq!(text, from_pig(&pig));
+        }
+    }
+
+    #[test]
+    fn extension_trait() {
+        assert_eq!("ello-hay orld-way", "hello world".to_pig_latin());
+        assert_eq!("Irst-fay", String::from("First").to_pig_latin());
+        let words: Vec<String> = "hello world".split(' ').map(str::to_pig_latin).collect();
+        assert_eq!(vec!["ello-hay", "orld-way"], words);
+    }
+
+    #[test]
+    fn parallel() {
+        let text =
+            "Don't stop -- it's a well-known, state-of-the-art rock'n'roll band!\n".repeat(50);
+        for threads in [0, 1, 3, 8, 1000] {
+            assert_eq!(translate(&text), translate_parallel(&text, threads));
+        }
+        assert_eq!("", translate_parallel("", 4));
+        assert_eq!(vec!["ab cd ", "ef gh ", "ij"], chunks("ab cd ef gh ij", 3));
+        assert_eq!(vec!["ñandú élégant"], chunks("ñandú élégant", 2));
+    }
+
+    #[test]
+    fn translator_tokenizer() {
+        let text = "abc123 fire🔥";
+        assert_eq!("abc123 ire-fay🔥", translate(text));
+        let tokenizer = Tokenizer::new().with_digits(false).with_emoji(true);
+        assert_eq!(
+            "abc-hay123 fire🔥",
+            Translator::new().with_tokenizer(tokenizer).translate(text)
+        );
+    }
+
+    #[test]
+    fn lossless() {
+        let translator = Translator::new().with_lossless(true);
+        assert_eq!(
+            "irst-Fay apple-ay at-hay ELLO-Hay cDonald-May",
+            translator.translate("First apple hat HELLO McDonald")
+        );
+        assert_eq!(
+            "mon-c'ay all-y'ay t-ay-hirt-say x-ay-ay-ray",
+            translator.translate("c'mon y'all t-shirt x-ray")
+        );
+
+        let text = "First apple hat at HELLO hello McDonald FIrst gOT -- don't well-known, \
+                    art-deco x -nth a-b Strong QUEENS élégant ñandú ᾭ abc123 I'm c'mon y'all \
+                    t-shirt x-ray T-Shirt";
+        for onset in [Onset::FirstLetter, Onset::Cluster] {
+            let translator = translator.with_onset(onset);
+            assert_eq!(text, translator.from_pig(&translator.translate(text)));
+        }
+        let tokenizer = Tokenizer::new().with_digits(false).with_emoji(true);
+        let translator = translator.with_tokenizer(tokenizer);
+        assert_eq!(text, translator.from_pig(&translator.translate(text)));
+    }
+
+    #[test]
+    fn markdown() {
+        let text = "# Notes\n\nCall `to_pig()` on a <b>word</b>, see [the book](https://doc.rust-lang.org/book).\n\n```rust\nlet hello = \"world\";\n```\n\nThat's all\n";
+        let translator = Translator::new().with_tokenizer(Tokenizer::new().with_markup(true));
+        let pig = "# Otes-nay\n\nAll-cay `to_pig()` on-hay a-hay <b>ord-way</b>, ee-say [he-tay ook-bay](https://doc.rust-lang.org/book).\n\n```rust\nlet hello = \"world\";\n```\n\nHat's-tay all-hay\n";
+        assert_eq!(pig, translator.translate(text));
+        assert_eq!(pig, translator.translate_parallel(text, 7));
+        let mut streamed = Vec::new();
+        translator
+            .translate_stream(text.as_bytes(), &mut streamed)
+            .unwrap();
+        assert_eq!(pig.as_bytes(), streamed);
+
+        let mut english = Vec::new();
+        translator
+            .from_pig_stream(pig.as_bytes(), &mut english)
+            .unwrap();
+        assert_eq!(text.as_bytes(), english);
+        // without markup, the code gets translated along with everything else
+        assert!(translate(text).contains("et-lay ello-hay"));
+    }
+
+    #[test]
+    fn streams() {
+        let text = "Strong queens\r\nfirst apple\nno newline at the end";
+        let mut pig = Vec::new();
+        let translator = Translator::new().with_onset(Onset::Cluster);
+        translator
+            .translate_stream(text.as_bytes(), &mut pig)
+            .unwrap();
+        assert_eq!(translator.translate(text).as_bytes(), pig);
+
+        let mut english = Vec::new();
+        from_pig_stream(pig.as_slice(), &mut english).unwrap();
+        assert_eq!(text.as_bytes(), english);
+    }
+
+    #[test]
+    fn contractions_and_hyphens() {
+        assert_eq!(
+            vec![
+                "don't",
+                " ",
+                "stop",
+                " ",
+                "-",
+                "-",
+                " ",
+                "well-known",
+                " ",
+                "'",
+                "quoted",
+                "'"
+            ],
+            to_list("don't stop -- well-known 'quoted'")
+        );
+        assert_eq!("On't-day", translate("Don't"));
+        assert_eq!("I'm-hay", translate("I'm"));
+        assert_eq!("ell-way-nown-kay", translate("well-known"));
+        assert_eq!("'uoted-qay'", translate("'quoted'"));
+        let cluster = Translator::new().with_onset(Onset::Cluster);
+        assert_eq!("ell-way-own-knay", cluster.translate("well-known"));
+        assert_eq!("'mon-cay", cluster.translate("c'mon"));
+        assert_eq!(
+            "ate-stay-of-hay-e-thay-art-hay",
+            cluster.translate("state-of-the-art")
+        );
+        // a part that's one consonant stays joined to the rest
+        assert_eq!("tay-hirt-say", translate("t-shirt"));
+        assert_eq!("tay-irt-shay", cluster.translate("t-shirt"));
+        assert_eq!("xay-ay-ray", translate("x-ray"));
+        assert_eq!("Tay-Hirt-say", translate("T-Shirt"));
+        assert_eq!(
+            "t-shirt x-ray T-Shirt",
+            from_pig("tay-hirt-say xay-ay-ray Tay-Hirt-say")
+        );
+
+        let text = "Don't stop -- it's a well-known, state-of-the-art rock'n'roll band! \
+                    A t-shirt, an x-ray, and a T-Shirt.";
+        for onset in [Onset::FirstLetter, Onset::Cluster] {
+            let pig = Translator::new().with_onset(onset).translate(text);
+            assert_eq!(text, from_pig(&pig));
+        }
+    }
+
+    #[test]
+    fn non_ascii() {
+        assert_eq!("-ᾥay est-hay élégant-hay", translate("ᾭ est élégant"));
+        assert_eq!("andú-ñay 🔥", translate("ñandú 🔥"));
+    }
+
+    #[test]
+    fn combining_accents_stay_put() {
+        // "élan" and "ñu" spelled with combining marks instead of precomposed letters
+        assert_eq!(
+            vec!["e\u{301}lan", " ", "n\u{303}u"],
+            to_list("e\u{301}lan n\u{303}u")
+        );
+        assert_eq!(
+            "e\u{301}lan-hay",
+            to_pig("e\u{301}lan", Onset::FirstLetter, false)
+        );
+        assert_eq!(
+            "u-n\u{303}ay",
+            to_pig("n\u{303}u", Onset::FirstLetter, false)
+        );
+    }
+}
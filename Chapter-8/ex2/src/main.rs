@@ -1,91 +1,156 @@
-/* 
- * Convert strings to pig latin. The first consonant of each word is moved to the end of the word and “ay” is added, so “first” becomes “irst-fay.” Words that start with a vowel have “hay” added to the end instead (“apple” becomes “apple-hay”). Keep in mind the details about UTF-8 encoding!
+/*
+ * Pig latin as a text filter. The text to translate comes from the arguments, from `--file <path>`,
+ * or from stdin when there's neither, and the translation goes to stdout or to `--output <path>`:
  *
- * `to_list()`` takes in a string literal and outputs a vector of each component of the string; this includes properly separating alphanumeric and non-alphanumeric parts of the string.
- * - `result` is the output vector
- * - `last` is an index tracker for when there's a non-alphanumeric character. 
- * - In the loop, if `last` doesn't equal the `index` of the matched character, then the program appends a slice of text with range last..index in `text` to `result`; this slice will be a non-breaking sequence of alphanumeric characters (a word or something). Now we can consider the non-alphanumeric character at `index`, and append it to `result`. Finally, the loop adds the length of the character (1) to `last` so that we can add to `result` any alphanumeric sequences between non-alphanumerics again (using `result.push(&text[last..index])`)
+ *     ex2 Hello there                  -> Ello-hay ere-thay
+ *     ex2 --file notes.txt --output notes.pig
+ *     cat notes.txt | ex2 --clusters
  *
+ * `--clusters` moves whole consonant clusters instead of just the first letter, and `--reverse`
+ * translates pig latin back to English. `--lossless` writes (or reads back) pig latin that keeps
+ * enough to get the exact original text back out of it. `--markup` leaves the code and links in a
+ * Markdown document alone.
  */
 
-const VOWELS: &str = "AEIOUaeiou";
+use ex2::{Onset, Tokenizer, Translator};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    process,
+};
 
-fn to_list(text: &str) -> Vec<&str> {
-    let mut result = Vec::new();
-    let mut last = 0;
-    for (index, matched) in text.match_indices(|c: char| !(c.is_alphanumeric())) {
-        if last != index {
-            result.push(&text[last..index]);
-        }
-        result.push(matched);
-        last = index + matched.len();
-    }
-    if last < text.len() {
-        result.push(&text[last..]);
-    }
-    result
+const USAGE: &str =
+    "usage: ex2 [--clusters] [--reverse] [--lossless] [--markup] [--file <path> | <text>...] [--output <path>]";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    onset: Onset,
+    reverse: bool,
+    lossless: bool,
+    markup: bool,
+    file: Option<String>,
+    output: Option<String>,
+    // whatever's left over is the text itself
+    words: Vec<String>,
 }
 
-fn to_pig(word: &str) -> String {
-    match word.chars().all(|ch| ch.is_alphabetic()) {
-        true => {
-            if VOWELS.contains(&word[0..1]) {
-                format!("{}-hay", word)
-            } else {
-                let ch = word.chars().next().unwrap().to_lowercase();
-                format!("{}-{}ay", &word[1..], ch)
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--clusters" => options.onset = Onset::Cluster,
+            "--reverse" => options.reverse = true,
+            "--lossless" => options.lossless = true,
+            "--markup" => options.markup = true,
+            "--file" | "--output" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a path after it", arg))?;
+                match arg.as_str() {
+                    "--file" => options.file = Some(path),
+                    _ => options.output = Some(path),
+                }
             }
+            flag if flag.starts_with("--") => return Err(format!("there's no {} option", flag)),
+            _ => options.words.push(arg),
         }
-        false => word.to_string()
     }
+    if options.file.is_some() && !options.words.is_empty() {
+        return Err("translate a file or some words, not both".to_string());
+    }
+    Ok(options)
 }
 
-fn main() {
-    let string = "I think; I gOT, it. ::'figured' out!!!🔥✅🔥";
-    
-    let text_list = to_list(string);
-    let mut pig = Vec::new();
-    for word in text_list {
-        pig.push(to_pig(word));
+// where the text comes from. nothing gets read all at once, so big files are fine
+fn input(options: &Options) -> io::Result<Box<dyn BufRead>> {
+    if let Some(path) = &options.file {
+        return Ok(Box::new(BufReader::new(File::open(path)?)));
     }
-
-    // println!("{:?}", pig);
-    println!("{}", pig.join(""));
+    if !options.words.is_empty() {
+        // an argument list doesn't end in a newline, but output should
+        let text = format!("{}\n", options.words.join(" "));
+        return Ok(Box::new(io::Cursor::new(text)));
+    }
+    Ok(Box::new(io::stdin().lock()))
 }
 
-/* // the following to functions are the unicode handling version. they don't work right though lol. things print weird. this string gets a runtime error using the above code, but doesn't print right with the below code "the symbol ᾭ is alphabetical; but, it's non-ASCII"
+fn output(options: &Options) -> io::Result<Box<dyn Write>> {
+    match &options.output {
+        Some(path) => Ok(Box::new(BufWriter::new(File::create(path)?))),
+        // stdout flushes every line by itself, which is slow going for a big file
+        None => Ok(Box::new(BufWriter::new(io::stdout().lock()))),
+    }
+}
 
-fn to_list(text: &str) -> Vec<&str> {
-    let mut result = Vec::new();
-    let mut last = 0;
-    for (index, _) in text.char_indices().filter(|&(_, c)| !(c.is_alphanumeric() || c == '\'')) {
-        if last != index {
-            result.push(&text[last..index]);
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
         }
-        let matched = &text[index..index + text[index..].chars().next().unwrap().len_utf8()];
-        result.push(matched);
-        last = index + matched.len();
-    }
-    if last < text.len() {
-        result.push(&text[last..]);
+    };
+    let input = match input(&options) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("couldn't open the text to translate: {}", e);
+            process::exit(1);
+        }
+    };
+    let output = match output(&options) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("couldn't open somewhere to write the translation: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let translator = Translator::new()
+        .with_onset(options.onset)
+        .with_lossless(options.lossless)
+        .with_tokenizer(Tokenizer::new().with_markup(options.markup));
+    let translated = match options.reverse {
+        true => translator.from_pig_stream(input, output),
+        false => translator.translate_stream(input, output),
+    };
+    if let Err(e) = translated {
+        eprintln!("couldn't translate: {}", e);
+        process::exit(1);
     }
-    result
 }
 
-fn to_pig(word: &str) -> String {
-    match word.chars().all(|ch| ch.is_alphabetic()) {
-        true => {
-            let first_char = word.char_indices().next().unwrap();
-            // println!("{:?}", first_char);
-            if VOWELS.contains(&first_char.1.to_string()) {
-                format!("{}-hay", word)
-            } else {
-                // let ch = first_char.1.to_lowercase();
-                // format!("{}-{}ay", &word[first_char.0 + first_char.1.len_utf8()..], ch)
-                format!("{}", &first_char.1)
-            }
-        }
-        false => format!("{}", word),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(
+            Ok(Options {
+                onset: Onset::Cluster,
+                output: Some("out.txt".to_string()),
+                words: args("Hello there"),
+                ..Options::default()
+            }),
+            parse_args(args("--clusters Hello --output out.txt there"))
+        );
+        assert_eq!(
+            Ok(Options {
+                reverse: true,
+                lossless: true,
+                markup: true,
+                ..Options::default()
+            }),
+            parse_args(args("--lossless --markup --reverse"))
+        );
+        assert!(parse_args(args("--file")).is_err());
+        assert!(parse_args(args("--file in.txt Hello")).is_err());
+        assert!(parse_args(args("--loud")).is_err());
     }
 }
- */
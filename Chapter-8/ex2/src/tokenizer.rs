@@ -0,0 +1,322 @@
+/*
+ * Splitting text into words and everything in between. Letters always count as word characters,
+ * along with any combining marks after them, so a letter written as "e" followed by a combining
+ * accent stays one word instead of being split at the accent. Whether digits and emoji count too
+ * is up to the `Tokenizer`: as part of a word they keep it together ("abc123" is one token, and gets
+ * left alone by the translator), and as separators they split it ("abc", "1", "2", "3").
+ *
+ * `tokens()` goes over `char_indices()`, so every index it slices at is on a character boundary no
+ * matter how many bytes the characters before it took up. When it hits a character that isn't part
+ * of a word, the run of word characters before it (if there is one) gets pushed as a slice, then
+ * the separator itself, and the next run starts past the separator's `len_utf8()` bytes (which
+ * isn't always 1!). Every token is a slice of the text, so gluing them back together gives back the
+ * text byte for byte, whitespace and all.
+ *
+ * With `markup` on, the text first goes through `split_markup()`, a small state machine that picks
+ * out the parts of a Markdown document that aren't prose: `code spans`, fenced code blocks, <html>
+ * tags, and the (url) of a [link](url). Each of those comes out as a single token that the translator
+ * leaves alone, and only what's between them gets split into words. Where it's up to is kept in a
+ * `Markup`, so a code block that starts on one line still counts as code on the next, even when the
+ * lines are tokenized one at a time.
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tokenizer {
+    pub digits: bool,
+    pub emoji: bool,
+    pub markup: bool,
+}
+
+// digits in words, emoji on their own, markup treated like any other text
+impl Default for Tokenizer {
+    fn default() -> Tokenizer {
+        Tokenizer {
+            digits: true,
+            emoji: false,
+            markup: false,
+        }
+    }
+}
+
+impl Tokenizer {
+    pub fn new() -> Tokenizer {
+        Tokenizer::default()
+    }
+
+    pub fn with_digits(mut self, digits: bool) -> Tokenizer {
+        self.digits = digits;
+        self
+    }
+
+    pub fn with_emoji(mut self, emoji: bool) -> Tokenizer {
+        self.emoji = emoji;
+        self
+    }
+
+    pub fn with_markup(mut self, markup: bool) -> Tokenizer {
+        self.markup = markup;
+        self
+    }
+
+    pub fn is_word_char(&self, ch: char) -> bool {
+        ch.is_alphabetic()
+            || is_combining(ch)
+            || (self.digits && ch.is_numeric())
+            || (self.emoji && is_emoji(ch))
+    }
+
+    pub fn tokens<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let mut result = Vec::new();
+        for (piece, skip) in self.split_markup(text, &mut Markup::default()) {
+            match skip {
+                true => result.push(piece),
+                false => result.extend(self.words(piece)),
+            }
+        }
+        result
+    }
+
+    // `text` split into words and what's between them, without looking for markup
+    pub(crate) fn words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let mut result = Vec::new();
+        let mut last = 0;
+        let mut prev = None;
+        for (index, ch) in text.char_indices() {
+            let next = text[index + ch.len_utf8()..].chars().next();
+            let joins = is_joiner(ch)
+                && prev.is_some_and(|p| self.is_word_char(p))
+                && next.is_some_and(|n| self.is_word_char(n));
+            prev = Some(ch);
+            if self.is_word_char(ch) || joins {
+                continue;
+            }
+            if last != index {
+                result.push(&text[last..index]);
+            }
+            let end = index + ch.len_utf8();
+            result.push(&text[index..end]);
+            last = end;
+        }
+        if last < text.len() {
+            result.push(&text[last..]);
+        }
+        result
+    }
+
+    // `text` cut into pieces of prose (false) and markup to leave alone (true), starting from
+    // wherever `markup` left off and leaving it wherever the text ends. everything is prose with
+    // `self.markup` off
+    pub(crate) fn split_markup<'a>(
+        &self,
+        text: &'a str,
+        markup: &mut Markup,
+    ) -> Vec<(&'a str, bool)> {
+        if !self.markup {
+            return vec![(text, false)];
+        }
+        // every character the state machine cares about is ASCII, so any index where it finds one
+        // is on a character boundary
+        let bytes = text.as_bytes();
+        let run = |from: usize| bytes[from..].iter().take_while(|&&b| b == b'`').count();
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let line_start = markup.line_start;
+            markup.line_start = bytes[i] == b'\n';
+            let (next, end) = match (markup.inside, bytes[i]) {
+                // three or more backticks at the start of a line open a fenced block, fewer (or
+                // anywhere else) a code span
+                (Inside::Text, b'`') => {
+                    let n = run(i);
+                    match line_start && n >= 3 {
+                        true => (Inside::Fence(n), i),
+                        false => (Inside::Code(n), i),
+                    }
+                }
+                (Inside::Text, b'<')
+                    if bytes
+                        .get(i + 1)
+                        .is_some_and(|b| b.is_ascii_alphabetic() || matches!(b, b'/' | b'!')) =>
+                {
+                    (Inside::Tag, i)
+                }
+                (Inside::Text, b'(') if i > 0 && bytes[i - 1] == b']' => (Inside::Link, i),
+                // a span ends at the same number of backticks it started with
+                (Inside::Code(n), b'`') => match run(i) {
+                    m if m == n => (Inside::Text, i + m),
+                    m => {
+                        i += m;
+                        continue;
+                    }
+                },
+                // or at the end of the line, if it never closed: better to translate the rest of
+                // the line than to leave the rest of the document alone over a stray backtick
+                (Inside::Code(_), b'\n') => (Inside::Text, i),
+                (Inside::Fence(n), b'`') if line_start => match run(i) {
+                    m if m >= n => (Inside::Text, i + m),
+                    m => {
+                        i += m;
+                        continue;
+                    }
+                },
+                (Inside::Tag, b'>') | (Inside::Link, b')') => (Inside::Text, i + 1),
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            // whatever came before `end` is the piece that just finished
+            if start < end {
+                pieces.push((&text[start..end], markup.inside != Inside::Text));
+            }
+            start = end;
+            // a run of backticks opening a span or block gets skipped over as a whole, so the
+            // first one isn't taken for the one that closes it
+            i = match next {
+                Inside::Code(n) | Inside::Fence(n) => i + n,
+                _ => end.max(i + 1),
+            };
+            markup.inside = next;
+        }
+        if start < bytes.len() {
+            pieces.push((&text[start..], markup.inside != Inside::Text));
+        }
+        pieces
+    }
+}
+
+// where `split_markup` is up to: in prose, or partway through something to leave alone. it carries
+// over from one piece of text to the next, so a document can be split up (into lines, say) without
+// losing track of which parts are code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Markup {
+    inside: Inside,
+    // whether the last character was a line break. fences only count at the start of a line
+    line_start: bool,
+}
+
+impl Default for Markup {
+    fn default() -> Markup {
+        Markup {
+            inside: Inside::Text,
+            line_start: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Inside {
+    Text,
+    // how many backticks it started with
+    Code(usize),
+    Fence(usize),
+    Tag,
+    Link,
+}
+
+// accents and other marks that get drawn on top of the character before them
+pub(crate) fn is_combining(ch: char) -> bool {
+    matches!(ch,
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE20}'..='\u{FE2F}')
+}
+
+// apostrophes and hyphens that hold a word together ("don't", "well-known") rather than being
+// punctuation next to one
+pub(crate) fn is_joiner(ch: char) -> bool {
+    matches!(ch, '\'' | '’' | '-')
+}
+
+// close enough without pulling in a crate for it: the pictures, the symbols and dingbats (✅ is
+// one), flags, skin tones, and the invisible characters that glue multi-part emoji together
+fn is_emoji(ch: char) -> bool {
+    matches!(ch,
+        '\u{1F000}'..='\u{1FAFF}'
+        | '\u{2600}'..='\u{27BF}'
+        | '\u{2B00}'..='\u{2BFF}'
+        | '\u{200D}'
+        | '\u{FE0F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_classes() {
+        assert_eq!(
+            vec!["abc123", " ", "🔥", "✅", " ", "don't"],
+            Tokenizer::new().tokens("abc123 🔥✅ don't")
+        );
+    }
+
+    #[test]
+    fn configured_classes() {
+        let tokenizer = Tokenizer::new().with_digits(false).with_emoji(true);
+        assert_eq!(
+            vec!["abc", "1", "2", " ", "fire🔥", " ", "👍🏽"],
+            tokenizer.tokens("abc12 fire🔥 👍🏽")
+        );
+    }
+
+    #[test]
+    fn markup() {
+        let tokenizer = Tokenizer::new().with_markup(true);
+        assert_eq!(
+            vec![
+                "use", " ", "`Vec<T>`", " ", "in", " ", "<a href=\"x\">", "Rust", "</a>", " ",
+                "[", "docs", "]", "(https://doc.rust-lang.org)", ",", " ", "``not `this` one``",
+            ],
+            tokenizer.tokens(
+                "use `Vec<T>` in <a href=\"x\">Rust</a> [docs](https://doc.rust-lang.org), ``not `this` one``"
+            )
+        );
+        assert_eq!(
+            vec!["see", ":", "\n", "```rust\nlet x = 1;\n```", "\n", "done"],
+            tokenizer.tokens("see:\n```rust\nlet x = 1;\n```\ndone")
+        );
+        // comparisons aren't tags, and a backtick that never closes only lasts the line
+        assert_eq!(
+            vec!["a", " ", "<", " ", "b", " ", "`oops", "\n", "c"],
+            tokenizer.tokens("a < b `oops\nc")
+        );
+        assert_eq!(vec!["`", "code", "`"], Tokenizer::new().tokens("`code`"));
+    }
+
+    #[test]
+    fn markup_across_lines() {
+        let tokenizer = Tokenizer::new().with_markup(true);
+        let mut markup = Markup::default();
+        let lines = ["```\n", "let x;\n", "```\n", "text\n"];
+        let skipped: Vec<Vec<bool>> = lines
+            .iter()
+            .map(|line| {
+                tokenizer
+                    .split_markup(line, &mut markup)
+                    .into_iter()
+                    .map(|(_, skip)| skip)
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            vec![vec![true], vec![true], vec![true, false], vec![false]],
+            skipped
+        );
+    }
+
+    #[test]
+    fn spacing_survives() {
+        let text = "  tabs\tand\r\nnon-breaking\u{a0}spaces  ,,  ";
+        for tokenizer in [
+            Tokenizer::new(),
+            Tokenizer::new().with_emoji(true).with_digits(false),
+            Tokenizer::new().with_markup(true),
+        ] {
+            assert_eq!(text, tokenizer.tokens(text).concat());
+        }
+    }
+}
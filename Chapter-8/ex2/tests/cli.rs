@@ -0,0 +1,94 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("pig-latin-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ex2"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn words_as_arguments() {
+    let output = run(&["Hello", "there"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        "Ello-hay here-tay\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
+#[test]
+fn piped_through_stdin() {
+    let output = run(&["--clusters"], "strong words\nfirst apple\n");
+    assert_eq!(
+        "ong-stray ords-way\nirst-fay apple-hay\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
+#[test]
+fn file_in_file_out() {
+    let dir = scratch_dir("files");
+    let input = dir.join("in.txt");
+    let output = dir.join("out.txt");
+    fs::write(&input, "Strong queens\n").unwrap();
+
+    let status = run(
+        &[
+            "--file",
+            input.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert!(status.status.success());
+    assert!(status.stdout.is_empty());
+    assert_eq!(
+        "Trong-say ueens-qay\n",
+        fs::read_to_string(&output).unwrap()
+    );
+
+    let back = run(&["--reverse", "--file", output.to_str().unwrap()], "");
+    assert_eq!("Strong queens\n", String::from_utf8(back.stdout).unwrap());
+}
+
+#[test]
+fn lossless_round_trip() {
+    let text = "McDonald had a hat\n";
+    let pig = run(&["--lossless"], text);
+    let pig = String::from_utf8(pig.stdout).unwrap();
+    assert_eq!("cDonald-May ad-hay a-ay at-hay\n", pig);
+
+    let back = run(&["--lossless", "--reverse"], &pig);
+    assert_eq!(text, String::from_utf8(back.stdout).unwrap());
+}
+
+#[test]
+fn bad_arguments() {
+    let output = run(&["--file"], "");
+    assert_eq!(Some(2), output.status.code());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("usage"));
+}
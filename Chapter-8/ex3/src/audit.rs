@@ -0,0 +1,137 @@
+/*
+ * The audit log: one line per change to the company, each stamped with when it happened, appended
+ * to a file that only ever grows. It outlives the session (and the undo history), so it's where to
+ * look when nobody remembers who moved Sally to Marketing. Lines look like
+ *
+ *     2026-10-16T09:30:00Z Add Sally to Engineering
+ *     2026-10-16T09:31:12Z Undo Add Sally to Engineering
+ *
+ * Times are UTC.
+ */
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const DEFAULT_PATH: &str = "audit.log";
+
+// without a path nothing gets written down, which is what tests (and dry runs) want
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    pub fn new(path: impl AsRef<Path>) -> AuditLog {
+        AuditLog {
+            path: Some(path.as_ref().to_path_buf()),
+        }
+    }
+
+    // adds `entry` to the end of the log, stamped with the current time
+    pub fn record(&self, entry: &str) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        // in one write, so lines from sessions logging at the same time can't get mixed up
+        let line = format!("{} {}\n", timestamp(SystemTime::now()), entry);
+        file.write_all(line.as_bytes())
+    }
+
+    // the last `n` lines of the log, oldest first. a log that hasn't been written yet is empty
+    pub fn recent(&self, n: usize) -> io::Result<Vec<String>> {
+        let Some(path) = &self.path else {
+            return Ok(Vec::new());
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+        Ok(lines[lines.len().saturating_sub(n)..].to_vec())
+    }
+}
+
+// `time` as YYYY-MM-DDTHH:MM:SSZ
+pub fn timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    let seconds = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+// days since 1970-01-01 to a calendar date. counts in 400 year eras, each of which has exactly the
+// same number of days, with years starting in March so the leap day comes last
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, time::Duration};
+
+    #[test]
+    fn timestamps() {
+        assert_eq!("1970-01-01T00:00:00Z", timestamp(UNIX_EPOCH));
+        let leap_day = UNIX_EPOCH + Duration::from_secs(951_827_696);
+        assert_eq!("2000-02-29T12:34:56Z", timestamp(leap_day));
+        let new_year = UNIX_EPOCH + Duration::from_secs(1_704_067_199);
+        assert_eq!("2023-12-31T23:59:59Z", timestamp(new_year));
+    }
+
+    #[test]
+    fn appends_and_reads_back() {
+        let path = env::temp_dir().join(format!("admincli-audit-{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let log = AuditLog::new(&path);
+        assert_eq!(Vec::<String>::new(), log.recent(5).unwrap());
+
+        log.record("Add Sally to Engineering").unwrap();
+        log.record("Add Amir to Sales").unwrap();
+        log.record("Undo Add Amir to Sales").unwrap();
+        let recent = log.recent(2).unwrap();
+        assert_eq!(2, recent.len());
+        assert!(recent[0].ends_with("Z Add Amir to Sales"));
+        assert!(recent[1].ends_with("Z Undo Add Amir to Sales"));
+        assert_eq!(3, log.recent(10).unwrap().len());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn no_path_no_log() {
+        let log = AuditLog::default();
+        assert!(log.record("Add Sally to Engineering").is_ok());
+        assert!(log.recent(5).unwrap().is_empty());
+    }
+}
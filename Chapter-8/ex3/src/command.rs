@@ -0,0 +1,403 @@
+/*
+ * Everything AdminCLI can be asked to do, parsed out of a line of input. Parsing only looks at the
+ * words, never at the company, so `"Remove Zed from Sales".parse::<Command>()` works fine even if
+ * there's no Zed; finding that out is the caller's job.
+ */
+
+use crate::{
+    messages::{Language, Msg},
+    parse::{tokenize, COMMANDS},
+    suggest, Employee, Sort, SortBy,
+};
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    All,
+    Department(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Tree,
+    Table,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Add {
+        employee: Employee,
+        dept: String,
+        force: bool,
+    },
+    Remove {
+        name: String,
+        dept: String,
+    },
+    Move {
+        name: String,
+        from: String,
+        to: String,
+    },
+    Dedup(String),
+    // None lifts the limit
+    SetCapacity {
+        dept: String,
+        capacity: Option<usize>,
+    },
+    List {
+        target: Target,
+        sort: Sort,
+    },
+    Find {
+        query: String,
+        partial: bool,
+    },
+    Count(String),
+    Stats,
+    Undo,
+    Redo,
+    // the last so many entries of the audit log
+    History(usize),
+    Export {
+        format: ExportFormat,
+        path: String,
+    },
+    // no path means the default one
+    Save(Option<String>),
+    Load(Option<String>),
+    DryRun(bool),
+    SetLanguage(Language),
+    Exit,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    Empty,
+    // not a command at all. `guess` is the command it's probably a typo of
+    Unknown {
+        word: String,
+        guess: Option<&'static str>,
+    },
+    // a real command with the wrong words after it
+    Usage {
+        command: &'static str,
+        usage: &'static str,
+    },
+    // words left over after a command that was otherwise fine
+    ExtraWords(String),
+    BadSort(String),
+    BadExportFormat(String),
+    BadDryRun,
+    BadCapacity(String),
+    BadCount(String),
+    UnknownLanguage(String),
+    // something further down (a date, a quote) didn't make sense. the message says what
+    Invalid(String),
+}
+
+// in English. messages::Msg::Parse has it in other languages
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Msg::Parse(self).text(Language::English))
+    }
+}
+
+impl Error for ParseError {}
+
+// how each command is meant to be written, for when it wasn't
+fn usage(command: &str) -> &'static str {
+    match command {
+        "add" => "Add <Name> to <Department> [as <Title>] [since <YYYY-MM-DD>] [--force]",
+        "remove" => "Remove <Name> from <Department>",
+        "move" => "Move <Name> from <Department> to <Department>",
+        "dedup" => "Dedup <Department>",
+        "setcapacity" => "SetCapacity <Department> <number|none>",
+        "list" => "List <Department>|all [by name|size] [asc|desc]",
+        "find" => "Find <Name> [--partial]",
+        "count" => "Count <Department>",
+        "export" => "Export tree|table <path>",
+        "save" => "Save [path]",
+        "load" => "Load [path]",
+        "dryrun" => "DryRun on|off",
+        "set" => "Set language en|es",
+        "stats" => "Stats",
+        "undo" => "Undo",
+        "redo" => "Redo",
+        "history" => "History [number of entries]",
+        _ => "Exit",
+    }
+}
+
+impl Command {
+    // whether this only looks at things. anything else changes the company or writes a file
+    pub fn read_only(&self) -> bool {
+        matches!(
+            self,
+            Command::List { .. }
+                | Command::Find { .. }
+                | Command::Count(_)
+                | Command::Stats
+                | Command::History(_)
+                | Command::DryRun(_)
+                | Command::SetLanguage(_)
+                | Command::Exit
+        )
+    }
+}
+
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Command, ParseError> {
+        let words = tokenize(input).map_err(ParseError::Invalid)?;
+        let words: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+        let Some(&first) = words.first() else {
+            return Err(ParseError::Empty);
+        };
+
+        let command = match words.as_slice() {
+            ["add", name, "to", dept, options @ ..] => add(name, dept, options)?,
+            ["remove", name, "from", dept] => Command::Remove {
+                name: name.to_string(),
+                dept: dept.to_string(),
+            },
+            ["move", name, "from", from, "to", to] => Command::Move {
+                name: name.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+            ["dedup", dept] => Command::Dedup(dept.to_string()),
+            ["setcapacity", dept, capacity] => Command::SetCapacity {
+                dept: dept.to_string(),
+                capacity: match capacity.to_lowercase().as_str() {
+                    "none" => None,
+                    number => Some(
+                        number
+                            .parse()
+                            .map_err(|_| ParseError::BadCapacity(capacity.to_string()))?,
+                    ),
+                },
+            },
+            ["list", "all", options @ ..] => Command::List {
+                target: Target::All,
+                sort: sort(options)?,
+            },
+            ["list", dept, options @ ..] => Command::List {
+                target: Target::Department(dept.to_string()),
+                sort: sort(options)?,
+            },
+            ["find", query] => Command::Find {
+                query: query.to_string(),
+                partial: false,
+            },
+            ["find", query, "--partial"] => Command::Find {
+                query: query.to_string(),
+                partial: true,
+            },
+            ["count", dept] => Command::Count(dept.to_string()),
+            ["stats"] => Command::Stats,
+            ["undo"] => Command::Undo,
+            ["redo"] => Command::Redo,
+            ["history"] => Command::History(10),
+            ["history", count] => Command::History(
+                count
+                    .parse()
+                    .map_err(|_| ParseError::BadCount(count.to_string()))?,
+            ),
+            ["export", format, path] => Command::Export {
+                format: format.parse()?,
+                path: path.to_string(),
+            },
+            ["save"] => Command::Save(None),
+            ["save", path] => Command::Save(Some(path.to_string())),
+            ["load"] => Command::Load(None),
+            ["load", path] => Command::Load(Some(path.to_string())),
+            ["dryrun", setting] => match setting.to_lowercase().as_str() {
+                "on" => Command::DryRun(true),
+                "off" => Command::DryRun(false),
+                _ => return Err(ParseError::BadDryRun),
+            },
+            ["set", "language", code] => match Language::from_code(code) {
+                Some(language) => Command::SetLanguage(language),
+                None => return Err(ParseError::UnknownLanguage(code.to_string())),
+            },
+            ["exit"] => Command::Exit,
+            _ => {
+                return Err(match COMMANDS.iter().copied().find(|c| *c == first) {
+                    Some(command) => ParseError::Usage {
+                        command,
+                        usage: usage(command),
+                    },
+                    None => ParseError::Unknown {
+                        word: first.to_string(),
+                        guess: suggest::closest(first, COMMANDS),
+                    },
+                })
+            }
+        };
+        Ok(command)
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<ExportFormat, ParseError> {
+        match s.to_lowercase().as_str() {
+            "tree" => Ok(ExportFormat::Tree),
+            "table" => Ok(ExportFormat::Table),
+            _ => Err(ParseError::BadExportFormat(s.to_string())),
+        }
+    }
+}
+
+// `Add <Name> to <Department>` plus whatever came after it: `as <Title>`, `since <YYYY-MM-DD>`,
+// and `--force`, in that order, each one optional
+fn add(name: &str, dept: &str, mut options: &[&str]) -> Result<Command, ParseError> {
+    let mut employee = Employee::new(name);
+    if let ["as", title, rest @ ..] = options {
+        employee.title = Some(title.to_string());
+        options = rest;
+    }
+    if let ["since", start, rest @ ..] = options {
+        employee.start = Some(start.parse().map_err(ParseError::Invalid)?);
+        options = rest;
+    }
+    let force = match options {
+        [] => false,
+        ["--force"] => true,
+        _ => return Err(ParseError::ExtraWords(options.join(" "))),
+    };
+    Ok(Command::Add {
+        employee,
+        dept: dept.to_string(),
+        force,
+    })
+}
+
+// the modifiers after `List <Department|all>`: `by name` or `by size`, then `asc` or `desc`
+fn sort(options: &[&str]) -> Result<Sort, ParseError> {
+    let options: Vec<String> = options.iter().map(|o| o.to_lowercase()).collect();
+    let options: Vec<&str> = options.iter().map(|o| o.as_str()).collect();
+    let (by, rest) = match options.as_slice() {
+        ["by", "name", rest @ ..] => (SortBy::Name, rest),
+        ["by", "size", rest @ ..] => (SortBy::Size, rest),
+        rest => (SortBy::Name, rest),
+    };
+    let descending = match rest {
+        [] | ["asc"] => false,
+        ["desc"] => true,
+        _ => return Err(ParseError::BadSort(rest.join(" "))),
+    };
+    Ok(Sort { by, descending })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_add() {
+        assert_eq!(
+            Ok(Command::Add {
+                employee: Employee::new("Mary Ann").with_title("Boss"),
+                dept: "Human Resources".to_string(),
+                force: true,
+            }),
+            r#"add "Mary Ann" to "Human Resources" as Boss --force"#.parse()
+        );
+        assert_eq!(
+            Err(ParseError::Invalid(
+                "'soon' isn't a date, use YYYY-MM-DD".to_string()
+            )),
+            "Add Bob to Sales since soon".parse::<Command>()
+        );
+    }
+
+    #[test]
+    fn parse_list() {
+        assert_eq!(
+            Ok(Command::List {
+                target: Target::All,
+                sort: Sort {
+                    by: SortBy::Size,
+                    descending: true
+                },
+            }),
+            "ls all BY size desc".parse()
+        );
+        assert_eq!(
+            Ok(Command::List {
+                target: Target::Department("Sales/EMEA".to_string()),
+                sort: Sort::default(),
+            }),
+            "List Sales/EMEA".parse()
+        );
+    }
+
+    #[test]
+    fn parse_set_capacity() {
+        assert_eq!(
+            Ok(Command::SetCapacity {
+                dept: "Engineering".to_string(),
+                capacity: Some(10),
+            }),
+            "SetCapacity Engineering 10".parse()
+        );
+        assert_eq!(
+            Ok(Command::SetCapacity {
+                dept: "Engineering".to_string(),
+                capacity: None,
+            }),
+            "setcapacity Engineering NONE".parse()
+        );
+        assert_eq!(
+            Err(ParseError::BadCapacity("ten".to_string())),
+            "SetCapacity Engineering ten".parse::<Command>()
+        );
+    }
+
+    #[test]
+    fn read_only_commands() {
+        let read_only = |input: &str| input.parse::<Command>().unwrap().read_only();
+        assert!(read_only("List all"));
+        assert!(read_only("Stats"));
+        assert!(!read_only("Add Sally to Engineering"));
+        assert!(!read_only("Undo"));
+        assert!(!read_only("Export tree org.txt"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Err(ParseError::Empty), "   ".parse::<Command>());
+        assert_eq!(
+            Err(ParseError::Unknown {
+                word: "lsit".to_string(),
+                guess: Some("list"),
+            }),
+            "lsit all".parse::<Command>()
+        );
+        assert_eq!(
+            "that's not quite how 'move' works, try 'Move <Name> from <Department> to <Department>'",
+            "Move Bob to Sales"
+                .parse::<Command>()
+                .unwrap_err()
+                .to_string()
+        );
+        assert!("Export pdf org.pdf".parse::<Command>().is_err());
+        assert_eq!(Ok(Command::History(10)), "History".parse());
+        assert_eq!(
+            Err(ParseError::BadCount("-3".to_string())),
+            "History -3".parse::<Command>()
+        );
+        assert_eq!(
+            Err(ParseError::BadDryRun),
+            "DryRun maybe".parse::<Command>()
+        );
+        assert_eq!(
+            Ok(Command::SetLanguage(Language::Spanish)),
+            "set LANGUAGE es".parse()
+        );
+    }
+}
@@ -0,0 +1,197 @@
+/*
+ * Departments form a tree. Each one has the people who work directly in it plus any number of
+ * sub-departments, which are departments all the same. A path names a department from the top down
+ * with '/' in between, so "Sales/EMEA" is the EMEA team inside Sales.
+ */
+
+use crate::{Employee, Sort, SortBy};
+use std::collections::BTreeMap;
+
+// "Sales / EMEA/" -> ["Sales", "EMEA"]
+pub fn segments(path: &str) -> Vec<&str> {
+    let segments: Vec<&str> = path
+        .split('/')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    // a path that's nothing but slashes is still somebody's idea of a department name
+    if segments.is_empty() {
+        vec![path]
+    } else {
+        segments
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Department {
+    name: String,
+    // the whole way down from the top, e.g. "Sales/EMEA". empty for the root the company hangs off
+    path: String,
+    employees: Vec<Employee>,
+    // the most people this department (sub-departments included) is allowed to have
+    capacity: Option<usize>,
+    // a BTreeMap so sub-departments always come out by name
+    subs: BTreeMap<String, Department>,
+}
+
+impl Department {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    // just the people directly in this department, sorted by name
+    pub fn employees(&self) -> &[Employee] {
+        &self.employees
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    // ordered by name
+    pub fn subdepartments(&self) -> impl Iterator<Item = &Department> {
+        self.subs.values()
+    }
+
+    // everyone in this department and every department under it
+    pub fn headcount(&self) -> usize {
+        self.employees.len() + self.subs.values().map(|d| d.headcount()).sum::<usize>()
+    }
+
+    // employees alphabetically, or the other way round with `descending`
+    pub fn sorted_employees(&self, descending: bool) -> Vec<&Employee> {
+        let mut employees: Vec<&Employee> = self.employees.iter().collect();
+        if descending {
+            employees.reverse();
+        }
+        employees
+    }
+
+    // sub-departments ordered however `sort` says. by size means total headcount, sub-departments
+    // included
+    pub fn sorted_subdepartments(&self, sort: Sort) -> Vec<&Department> {
+        let mut subs: Vec<&Department> = self.subs.values().collect();
+        if sort.by == SortBy::Size {
+            // subs is already by name, and a stable sort keeps it that way for equal sizes
+            subs.sort_by_key(|d| d.headcount());
+        }
+        if sort.descending {
+            subs.reverse();
+        }
+        subs
+    }
+
+    pub(crate) fn find(&self, path: &str) -> Option<&Department> {
+        let mut node = self;
+        for segment in segments(path) {
+            node = node.subs.get(segment)?;
+        }
+        Some(node)
+    }
+
+    pub(crate) fn find_mut(&mut self, path: &str) -> Option<&mut Department> {
+        let mut node = self;
+        for segment in segments(path) {
+            node = node.subs.get_mut(segment)?;
+        }
+        Some(node)
+    }
+
+    // the department at `path`, creating it and anything above it that doesn't exist yet
+    pub(crate) fn entry(&mut self, path: &str) -> &mut Department {
+        let mut node = self;
+        for segment in segments(path) {
+            let parent_path = node.path.clone();
+            node = node
+                .subs
+                .entry(segment.to_string())
+                .or_insert_with(|| Department {
+                    name: segment.to_string(),
+                    path: if parent_path.is_empty() {
+                        segment.to_string()
+                    } else {
+                        format!("{}/{}", parent_path, segment)
+                    },
+                    ..Department::default()
+                });
+        }
+        node
+    }
+
+    pub(crate) fn employees_mut(&mut self) -> &mut Vec<Employee> {
+        &mut self.employees
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity;
+    }
+
+    // the first department on the way down to `path` that has no room for anyone else. adding
+    // someone to Sales/EMEA adds them to Sales too, so every level has to have room
+    pub(crate) fn full_on_path(&self, path: &str) -> Option<&Department> {
+        let mut node = self;
+        for segment in segments(path) {
+            node = node.subs.get(segment)?;
+            if node.capacity.is_some_and(|c| node.headcount() >= c) {
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    // drops every sub-department that has nobody in it (and no capacity set), all the way down
+    pub(crate) fn prune(&mut self) {
+        for sub in self.subs.values_mut() {
+            sub.prune();
+        }
+        self.subs
+            .retain(|_, d| !d.employees.is_empty() || !d.subs.is_empty() || d.capacity.is_some());
+    }
+
+    // every department below this one, parents before their children
+    pub(crate) fn walk<'a>(&'a self, out: &mut Vec<&'a Department>) {
+        for sub in self.subs.values() {
+            out.push(sub);
+            sub.walk(out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_segments() {
+        assert_eq!(vec!["Sales", "EMEA"], segments("Sales / EMEA/"));
+        assert_eq!(vec!["Sales"], segments("Sales"));
+    }
+
+    #[test]
+    fn entry_builds_the_path() {
+        let mut root = Department::default();
+        let emea = root.entry("Sales/EMEA");
+        assert_eq!("EMEA", emea.name());
+        assert_eq!("Sales/EMEA", emea.path());
+        assert!(root.find("Sales").is_some());
+        assert!(root.find("EMEA").is_none());
+    }
+
+    #[test]
+    fn prune_keeps_departments_with_people_below() {
+        let mut root = Department::default();
+        root.entry("Sales/EMEA/UK")
+            .employees_mut()
+            .push(Employee::new("Amir"));
+        root.entry("Sales/APAC");
+        root.entry("Sales/LATAM").set_capacity(Some(5));
+        root.prune();
+        assert!(root.find("Sales/EMEA/UK").is_some());
+        assert!(root.find("Sales/APAC").is_none());
+        assert!(root.find("Sales/LATAM").is_some());
+    }
+}
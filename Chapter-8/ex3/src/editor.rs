@@ -0,0 +1,414 @@
+/*
+ * A tiny readline for the interactive prompt. The terminal goes into raw mode while a line is being
+ * typed so we see every key press instead of whole lines:
+ *
+ * - Left/Right/Home/End move the cursor, Backspace/Delete edit where it is
+ * - Up/Down step through earlier commands (whatever was half-typed comes back after the newest one)
+ * - Tab completes the word under the cursor from a list of candidates. One match gets filled in,
+ *   several get filled in as far as they agree and are listed if that doesn't get any further
+ * - Ctrl-C throws the current line away, Ctrl-D on an empty line means we're done
+ *
+ * There's no terminal library behind it. `stty` switches raw mode on and off, keys come in as the
+ * bytes and escape sequences a terminal sends, and the line is redrawn with a couple of escape
+ * sequences of our own. Where `stty` isn't around (or stdin isn't a terminal after all) it's just
+ * a plain read_line.
+ */
+
+use std::{
+    io::{self, Read, Write},
+    process::{Command, Stdio},
+};
+
+#[derive(Default)]
+pub struct Editor {
+    history: Vec<String>,
+}
+
+impl Editor {
+    pub fn new() -> Editor {
+        Editor::default()
+    }
+
+    // reads a line after showing `prompt`. Ok(None) means the user hit Ctrl-D on an empty line
+    pub fn read_line(&mut self, prompt: &str, candidates: &[String]) -> io::Result<Option<String>> {
+        let result = match RawMode::enable() {
+            Ok(raw) => {
+                let result = self.edit(prompt, candidates);
+                drop(raw);
+                println!();
+                result
+            }
+            Err(_) => plain_read_line(prompt),
+        };
+
+        if let Ok(Some(line)) = &result {
+            if !line.trim().is_empty() && self.history.last() != Some(line) {
+                self.history.push(line.clone());
+            }
+        }
+        result
+    }
+
+    fn edit(&self, prompt: &str, candidates: &[String]) -> io::Result<Option<String>> {
+        let mut stdin = io::stdin().lock();
+        let mut stdout = io::stdout();
+        let mut line: Vec<char> = Vec::new();
+        let mut cursor = 0;
+        // where we are in the history. history.len() means "the line being typed"
+        let mut recalled = self.history.len();
+        let mut draft: Vec<char> = Vec::new();
+
+        redraw(&mut stdout, prompt, &line, cursor)?;
+        loop {
+            // the input ending is as good as Ctrl-D
+            let Some(key) = read_key(&mut stdin)? else {
+                return Ok(None);
+            };
+
+            match key {
+                Key::Enter => return Ok(Some(line.into_iter().collect())),
+                Key::Ctrl('d') if line.is_empty() => return Ok(None),
+                Key::Ctrl('c') => {
+                    line.clear();
+                    cursor = 0;
+                }
+                Key::Char(c) => {
+                    line.insert(cursor, c);
+                    cursor += 1;
+                }
+                Key::Backspace if cursor > 0 => {
+                    cursor -= 1;
+                    line.remove(cursor);
+                }
+                Key::Delete if cursor < line.len() => {
+                    line.remove(cursor);
+                }
+                Key::Left if cursor > 0 => cursor -= 1,
+                Key::Right if cursor < line.len() => cursor += 1,
+                Key::Home => cursor = 0,
+                Key::End => cursor = line.len(),
+                Key::Up if recalled > 0 => {
+                    if recalled == self.history.len() {
+                        draft = line.clone();
+                    }
+                    recalled -= 1;
+                    line = self.history[recalled].chars().collect();
+                    cursor = line.len();
+                }
+                Key::Down if recalled < self.history.len() => {
+                    recalled += 1;
+                    line = match self.history.get(recalled) {
+                        Some(old) => old.chars().collect(),
+                        None => draft.clone(),
+                    };
+                    cursor = line.len();
+                }
+                Key::Tab => {
+                    let before: String = line[..cursor].iter().collect();
+                    let start = word_start(&before);
+                    match complete(&before[start..], candidates) {
+                        Completion::Replace(word) => {
+                            let start = before[..start].chars().count();
+                            line.splice(start..cursor, word.chars());
+                            cursor = start + word.chars().count();
+                        }
+                        Completion::Options(options) => {
+                            write!(stdout, "\r\n{}\r\n", options.join("  "))?;
+                        }
+                        Completion::Nothing => {}
+                    }
+                }
+                _ => {}
+            }
+            redraw(&mut stdout, prompt, &line, cursor)?;
+        }
+    }
+}
+
+// for when the terminal can't be put into raw mode: no editing, but a line is still a line
+fn plain_read_line(prompt: &str) -> io::Result<Option<String>> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(line.trim_end_matches(['\r', '\n']).to_string())),
+    }
+}
+
+// raw mode for as long as this is around: no echo, no waiting for Enter, and no turning \n into
+// \r\n on the way out. whatever the terminal was set to before comes back when it's dropped
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> io::Result<RawMode> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        stty(&["raw", "-echo"])?;
+        Ok(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+// stty works on whatever terminal its stdin is, so it gets ours
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(error.trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[derive(Debug, PartialEq)]
+enum Key {
+    Char(char),
+    // Ctrl and a letter, as the lowercase letter
+    Ctrl(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    // anything else, e.g. F1 or a sequence we don't know
+    Unknown,
+}
+
+// the next key pressed, or None once the input has ended. arrows and friends arrive as escape
+// sequences, either `ESC [ x` or `ESC O x`, and the ones with numbers as `ESC [ n ~`
+fn read_key(input: &mut impl Read) -> io::Result<Option<Key>> {
+    let Some(byte) = read_byte(input)? else {
+        return Ok(None);
+    };
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f | 0x08 => Key::Backspace,
+        0x1b => match read_byte(input)? {
+            Some(b'[' | b'O') => escape_sequence(input)?,
+            _ => Key::Unknown,
+        },
+        0x01..=0x1a => Key::Ctrl((b'a' + byte - 1) as char),
+        0x00..=0x1f => Key::Unknown,
+        _ => {
+            // the rest of a character that takes more than one byte
+            let len = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+            let mut bytes = vec![byte];
+            for _ in 1..len {
+                bytes.extend(read_byte(input)?);
+            }
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) => Key::Char(c),
+                None => Key::Unknown,
+            }
+        }
+    };
+    Ok(Some(key))
+}
+
+// what's left of an escape sequence once the `ESC [` is out of the way
+fn escape_sequence(input: &mut impl Read) -> io::Result<Key> {
+    let mut number = String::new();
+    loop {
+        let Some(byte) = read_byte(input)? else {
+            return Ok(Key::Unknown);
+        };
+        let key = match byte {
+            b'0'..=b'9' | b';' => {
+                number.push(byte as char);
+                continue;
+            }
+            b'A' => Key::Up,
+            b'B' => Key::Down,
+            b'C' => Key::Right,
+            b'D' => Key::Left,
+            b'H' => Key::Home,
+            b'F' => Key::End,
+            b'~' => match number.as_str() {
+                "1" | "7" => Key::Home,
+                "3" => Key::Delete,
+                "4" | "8" => Key::End,
+                _ => Key::Unknown,
+            },
+            _ => Key::Unknown,
+        };
+        return Ok(key);
+    }
+}
+
+fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    match input.read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+// back to the start of the line, clear it, write it all out again, and put the cursor back where
+// it goes. columns in the escape sequence count from 1
+fn redraw(stdout: &mut io::Stdout, prompt: &str, line: &[char], cursor: usize) -> io::Result<()> {
+    let text: String = line.iter().collect();
+    let column = prompt.chars().count() + cursor + 1;
+    write!(stdout, "\r\x1b[K{}{}\x1b[{}G", prompt, text, column)?;
+    stdout.flush()
+}
+
+// byte index where the word being typed at the end of `before` starts. a quote that's still open
+// keeps the spaces after it inside the word
+fn word_start(before: &str) -> usize {
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in before.char_indices() {
+        if c == '"' {
+            if !quoted {
+                start = i;
+            }
+            quoted = !quoted;
+        } else if c.is_whitespace() && !quoted {
+            start = i + c.len_utf8();
+        }
+    }
+    start
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Completion {
+    // swap the word for this
+    Replace(String),
+    // too many ways to go, show the user what they are
+    Options(Vec<String>),
+    Nothing,
+}
+
+// what Tab does to `word` given everything it could turn into. matching ignores case and quotes
+pub fn complete(word: &str, candidates: &[String]) -> Completion {
+    let typed = word.trim_start_matches('"').to_lowercase();
+    let matches: Vec<&String> = candidates
+        .iter()
+        .filter(|c| c.trim_start_matches('"').to_lowercase().starts_with(&typed))
+        .collect();
+
+    match matches.as_slice() {
+        [] => Completion::Nothing,
+        [only] => Completion::Replace(format!("{} ", only)),
+        _ => {
+            let common = common_prefix(&matches);
+            if common.chars().count() > word.chars().count() {
+                Completion::Replace(common)
+            } else {
+                Completion::Options(matches.into_iter().cloned().collect())
+            }
+        }
+    }
+}
+
+fn common_prefix(words: &[&String]) -> String {
+    let first: Vec<char> = words[0].chars().collect();
+    let mut len = first.len();
+    for word in &words[1..] {
+        len = first
+            .iter()
+            .zip(word.chars())
+            .take(len)
+            .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+            .count();
+    }
+    first[..len].iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates() -> Vec<String> {
+        [
+            "Add",
+            "All",
+            "List",
+            "Load",
+            "Engineering",
+            "\"Human Resources\"",
+        ]
+        .iter()
+        .map(|c| c.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn single_match_is_filled_in() {
+        assert_eq!(
+            Completion::Replace("Engineering ".to_string()),
+            complete("eng", &candidates())
+        );
+        assert_eq!(
+            Completion::Replace("\"Human Resources\" ".to_string()),
+            complete("\"Hu", &candidates())
+        );
+    }
+
+    #[test]
+    fn several_matches() {
+        assert_eq!(
+            Completion::Replace("L".to_string()),
+            complete("", &["List".to_string(), "Load".to_string()])
+        );
+        assert_eq!(
+            Completion::Options(vec!["List".to_string(), "Load".to_string()]),
+            complete("l", &candidates())
+        );
+        assert_eq!(Completion::Nothing, complete("zz", &candidates()));
+    }
+
+    #[test]
+    fn keys() {
+        let mut input: &[u8] = b"a\x1b[A\x1b[D\x1bOH\x1b[3~\x7f\x03\t\r\xc3\xa9";
+        let mut keys = Vec::new();
+        while let Some(key) = read_key(&mut input).unwrap() {
+            keys.push(key);
+        }
+        assert_eq!(
+            vec![
+                Key::Char('a'),
+                Key::Up,
+                Key::Left,
+                Key::Home,
+                Key::Delete,
+                Key::Backspace,
+                Key::Ctrl('c'),
+                Key::Tab,
+                Key::Enter,
+                Key::Char('é'),
+            ],
+            keys
+        );
+    }
+
+    #[test]
+    fn word_under_cursor() {
+        assert_eq!(4, word_start("Add Sal"));
+        assert_eq!(13, word_start("Add Sally to \"Human Res"));
+        assert_eq!(0, word_start("Li"));
+    }
+}
@@ -0,0 +1,111 @@
+/*
+ * An employee is a name plus whatever else we happen to know about them. Title and start date are
+ * optional since `Add Sally to Engineering` is still the most common way people get hired here.
+ */
+
+use std::{fmt, str::FromStr};
+
+// a calendar date, written and read as YYYY-MM-DD
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u8, day: u8) -> Result<Date, String> {
+        if !(1..=12).contains(&month) {
+            return Err(format!("{} isn't a month", month));
+        }
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if day == 0 || day > days_in_month {
+            return Err(format!("{}-{:02} doesn't have a day {}", year, month, day));
+        }
+        Ok(Date { year, month, day })
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Date, String> {
+        let bad = || format!("'{}' isn't a date, use YYYY-MM-DD", s);
+        let parts: Vec<&str> = s.split('-').collect();
+        match parts.as_slice() {
+            [year, month, day] => Date::new(
+                year.parse().map_err(|_| bad())?,
+                month.parse().map_err(|_| bad())?,
+                day.parse().map_err(|_| bad())?,
+            ),
+            _ => Err(bad()),
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// ordered by name first, so a sorted department reads alphabetically
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Employee {
+    pub name: String,
+    pub title: Option<String>,
+    pub start: Option<Date>,
+}
+
+impl Employee {
+    pub fn new(name: &str) -> Employee {
+        Employee {
+            name: name.to_string(),
+            title: None,
+            start: None,
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Employee {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn with_start(mut self, start: Date) -> Employee {
+        self.start = Some(start);
+        self
+    }
+}
+
+// so a bare name can go anywhere an employee is expected
+impl From<&str> for Employee {
+    fn from(name: &str) -> Employee {
+        Employee::new(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_date() {
+        let date: Date = "2024-02-29".parse().unwrap();
+        assert_eq!(Date::new(2024, 2, 29), Ok(date));
+        assert_eq!("2024-02-29", date.to_string());
+    }
+
+    #[test]
+    fn bad_dates() {
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("2023-13-01".parse::<Date>().is_err());
+        assert!("2023-04-31".parse::<Date>().is_err());
+        assert!("yesterday".parse::<Date>().is_err());
+    }
+}
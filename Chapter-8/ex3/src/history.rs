@@ -0,0 +1,369 @@
+/*
+ * Undo/redo for AdminCLI. Every change that goes through `History::apply` lands on the undo stack.
+ * Undo pops it, applies its inverse, and parks it on the redo stack; redo does the opposite.
+ * Applying a brand new change throws the redo stack away, same as every text editor: once you've
+ * gone somewhere new, the old future doesn't exist anymore.
+ */
+
+use crate::{parse::quote, Company, CompanyError, Employee};
+use std::fmt;
+
+// how many changes we hang on to before the oldest ones fall off
+const MAX_HISTORY: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    // `force` lets the name in even if the department already has someone by that name
+    Add {
+        employee: Employee,
+        dept: String,
+        force: bool,
+    },
+    // carries the whole record so undoing it brings back their title and start date too
+    Remove {
+        employee: Employee,
+        dept: String,
+    },
+    Move {
+        name: String,
+        from: String,
+        to: String,
+    },
+    // `from` is the capacity before, so undoing puts it back
+    SetCapacity {
+        dept: String,
+        from: Option<usize>,
+        to: Option<usize>,
+    },
+    // several changes that succeed or fail (and get undone) together
+    Batch(Vec<Change>),
+}
+
+impl Change {
+    // the change that puts things back the way they were
+    pub fn inverse(&self) -> Change {
+        match self {
+            Change::Add { employee, dept, .. } => Change::Remove {
+                employee: employee.clone(),
+                dept: dept.clone(),
+            },
+            // putting someone back is always allowed, even next to a namesake
+            Change::Remove { employee, dept } => Change::Add {
+                employee: employee.clone(),
+                dept: dept.clone(),
+                force: true,
+            },
+            Change::Move { name, from, to } => Change::Move {
+                name: name.clone(),
+                from: to.clone(),
+                to: from.clone(),
+            },
+            Change::SetCapacity { dept, from, to } => Change::SetCapacity {
+                dept: dept.clone(),
+                from: *to,
+                to: *from,
+            },
+            Change::Batch(changes) => {
+                Change::Batch(changes.iter().rev().map(Change::inverse).collect())
+            }
+        }
+    }
+
+    pub fn apply_to(&self, company: &mut Company) -> Result<(), CompanyError> {
+        match self {
+            Change::Add {
+                employee,
+                dept,
+                force: true,
+            } => {
+                // force only gets past the duplicate check, a full department is still full
+                company.check_room(dept)?;
+                company.insert(employee.clone(), dept);
+                Ok(())
+            }
+            Change::Add { employee, dept, .. } => company.add(employee.clone(), dept),
+            Change::Remove { employee, dept } => company.remove(&employee.name, dept).map(|_| ()),
+            Change::Move { name, from, to } => company.move_employee(name, from, to),
+            Change::SetCapacity { dept, to, .. } => company.set_capacity(dept, *to).map(|_| ()),
+            Change::Batch(changes) => {
+                for (i, change) in changes.iter().enumerate() {
+                    if let Err(e) = change.apply_to(company) {
+                        // roll back whatever already went through so the batch is all or nothing
+                        for done in changes[..i].iter().rev() {
+                            done.inverse().apply_to(company)?;
+                        }
+                        return Err(e);
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// written out as the command that would make the change
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::Add {
+                employee,
+                dept,
+                force,
+            } => {
+                write!(f, "Add {} to {}", quote(&employee.name), quote(dept))?;
+                if let Some(title) = &employee.title {
+                    write!(f, " as {}", quote(title))?;
+                }
+                if let Some(start) = &employee.start {
+                    write!(f, " since {}", start)?;
+                }
+                if *force {
+                    write!(f, " --force")?;
+                }
+                Ok(())
+            }
+            Change::Remove { employee, dept } => {
+                write!(f, "Remove {} from {}", quote(&employee.name), quote(dept))
+            }
+            Change::Move { name, from, to } => write!(
+                f,
+                "Move {} from {} to {}",
+                quote(name),
+                quote(from),
+                quote(to)
+            ),
+            Change::SetCapacity { dept, to, .. } => match to {
+                Some(capacity) => write!(f, "SetCapacity {} {}", quote(dept), capacity),
+                None => write!(f, "SetCapacity {} none", quote(dept)),
+            },
+            Change::Batch(changes) => {
+                let commands: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+                write!(f, "{}", commands.join("; "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Change>,
+    redo: Vec<Change>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    // applies `change` and remembers it. a change that fails isn't recorded
+    pub fn apply(&mut self, company: &mut Company, change: Change) -> Result<(), CompanyError> {
+        change.apply_to(company)?;
+        self.undo.push(change);
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        Ok(())
+    }
+
+    // reverts the latest change and returns it, or None when there's nothing left to undo
+    pub fn undo(&mut self, company: &mut Company) -> Option<Result<Change, CompanyError>> {
+        let change = self.undo.pop()?;
+        match change.inverse().apply_to(company) {
+            Ok(()) => {
+                self.redo.push(change.clone());
+                Some(Ok(change))
+            }
+            Err(e) => {
+                self.undo.push(change);
+                Some(Err(e))
+            }
+        }
+    }
+
+    // re-applies the latest undone change and returns it, or None when there's nothing to redo
+    pub fn redo(&mut self, company: &mut Company) -> Option<Result<Change, CompanyError>> {
+        let change = self.redo.pop()?;
+        match change.apply_to(company) {
+            Ok(()) => {
+                self.undo.push(change.clone());
+                Some(Ok(change))
+            }
+            Err(e) => {
+                self.redo.push(change);
+                Some(Err(e))
+            }
+        }
+    }
+
+    // the change Undo would revert next, without reverting it
+    pub fn peek_undo(&self) -> Option<&Change> {
+        self.undo.last()
+    }
+
+    // the change Redo would re-apply next, without re-applying it
+    pub fn peek_redo(&self) -> Option<&Change> {
+        self.redo.last()
+    }
+
+    // forgets everything, e.g. after a Load swaps the whole company out from under us
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(name: &str, dept: &str) -> Change {
+        Change::Add {
+            employee: Employee::new(name),
+            dept: dept.to_string(),
+            force: false,
+        }
+    }
+
+    fn remove(name: &str, dept: &str) -> Change {
+        Change::Remove {
+            employee: Employee::new(name),
+            dept: dept.to_string(),
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_add() {
+        let mut company = Company::new();
+        let mut history = History::new();
+        history
+            .apply(&mut company, add("Sally", "Engineering"))
+            .unwrap();
+
+        assert_eq!(
+            Some(Ok(add("Sally", "Engineering"))),
+            history.undo(&mut company)
+        );
+        assert!(company.is_empty());
+
+        assert_eq!(
+            Some(Ok(add("Sally", "Engineering"))),
+            history.redo(&mut company)
+        );
+        assert_eq!(vec!["Engineering"], company.departments());
+    }
+
+    #[test]
+    fn undo_move() {
+        let mut company = Company::new();
+        let mut history = History::new();
+        history.apply(&mut company, add("Bob", "Sales")).unwrap();
+        let change = Change::Move {
+            name: "Bob".to_string(),
+            from: "Sales".to_string(),
+            to: "Marketing".to_string(),
+        };
+        history.apply(&mut company, change).unwrap();
+
+        history.undo(&mut company);
+        assert_eq!(vec!["Sales"], company.departments());
+    }
+
+    #[test]
+    fn undo_remove_restores_duplicate() {
+        let mut company = Company::new();
+        let mut history = History::new();
+        company.insert("Sally", "Engineering");
+        company.insert("Sally", "Engineering");
+        history
+            .apply(&mut company, remove("Sally", "Engineering"))
+            .unwrap();
+
+        assert!(history.undo(&mut company).unwrap().is_ok());
+        assert_eq!(Ok(2), company.count("Engineering"));
+    }
+
+    #[test]
+    fn failed_batch_rolls_back() {
+        let mut company = Company::new();
+        let mut history = History::new();
+        let batch = Change::Batch(vec![
+            add("Sally", "Engineering"),
+            add("Sally", "Engineering"),
+        ]);
+        assert!(history.apply(&mut company, batch).is_err());
+        assert!(company.is_empty());
+    }
+
+    #[test]
+    fn peek_changes_nothing() {
+        let mut company = Company::new();
+        let mut history = History::new();
+        history.apply(&mut company, add("Bob", "Sales")).unwrap();
+        assert_eq!(Some(&add("Bob", "Sales")), history.peek_undo());
+        assert_eq!(None, history.peek_redo());
+        assert_eq!(Some(Ok(add("Bob", "Sales"))), history.undo(&mut company));
+    }
+
+    #[test]
+    fn undo_set_capacity() {
+        let mut company = Company::new();
+        let mut history = History::new();
+        company.insert("Bob", "Sales");
+        let change = Change::SetCapacity {
+            dept: "Sales".to_string(),
+            from: None,
+            to: Some(1),
+        };
+        assert_eq!("SetCapacity Sales 1", change.to_string());
+        history.apply(&mut company, change).unwrap();
+        assert!(company.add("Cleo", "Sales").is_err());
+
+        history.undo(&mut company);
+        assert_eq!(Ok(()), company.add("Cleo", "Sales"));
+    }
+
+    #[test]
+    fn display_parses_back() {
+        use crate::command::Command;
+
+        let change = Change::Move {
+            name: "List".to_string(),
+            from: r#"The "A" Team"#.to_string(),
+            to: "to".to_string(),
+        };
+        assert_eq!(
+            r#"Move "List" from "The \"A\" Team" to "to""#,
+            change.to_string()
+        );
+        assert_eq!(
+            Ok(Command::Move {
+                name: "List".to_string(),
+                from: r#"The "A" Team"#.to_string(),
+                to: "to".to_string(),
+            }),
+            change.to_string().parse()
+        );
+    }
+
+    #[test]
+    fn new_change_truncates_redo() {
+        let mut company = Company::new();
+        let mut history = History::new();
+        history
+            .apply(&mut company, add("Sally", "Engineering"))
+            .unwrap();
+        history.undo(&mut company);
+        history.apply(&mut company, add("Bob", "Sales")).unwrap();
+
+        assert_eq!(None, history.redo(&mut company));
+    }
+
+    #[test]
+    fn failed_change_isnt_recorded() {
+        let mut company = Company::new();
+        let mut history = History::new();
+        assert!(history.apply(&mut company, remove("Zed", "Sales")).is_err());
+        assert_eq!(None, history.undo(&mut company));
+    }
+}
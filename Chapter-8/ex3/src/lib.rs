@@ -0,0 +1,634 @@
+/*
+ * The business end of AdminCLI. `Company` owns the tree of departments and every method either
+ * hands back data or an error; nothing in here prints. main.rs decides what to say.
+ *
+ * Departments are named by their path, so "Sales/EMEA" works anywhere "Sales" does.
+ */
+
+pub mod audit;
+pub mod command;
+pub mod department;
+pub mod employee;
+pub mod history;
+pub mod messages;
+pub mod parse;
+pub mod shared;
+pub mod storage;
+pub mod suggest;
+
+pub use department::Department;
+pub use employee::{Date, Employee};
+pub use shared::SharedCompany;
+
+use messages::{Language, Msg};
+
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq)]
+pub enum CompanyError {
+    DepartmentNotFound(String),
+    EmployeeNotFound { name: String, dept: String },
+    Duplicate { name: String, dept: String },
+    // `dept` already has `capacity` people in it
+    Full { dept: String, capacity: usize },
+}
+
+// in English. messages::Msg::Company has it in other languages
+impl fmt::Display for CompanyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Msg::Company(self).text(Language::English))
+    }
+}
+
+impl Error for CompanyError {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SortBy {
+    #[default]
+    Name,
+    // headcount, with ties broken by name
+    Size,
+}
+
+// how List orders things. `descending` flips departments and the employees inside them
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sort {
+    pub by: SortBy,
+    pub descending: bool,
+}
+
+// headcount numbers for the whole company
+#[derive(Debug, PartialEq)]
+pub struct Stats<'a> {
+    // (department, headcount) for every department with people directly in it, in tree order
+    pub headcounts: Vec<(&'a str, usize)>,
+    pub total: usize,
+    pub largest: Option<(&'a str, usize)>,
+    pub smallest: Option<(&'a str, usize)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Company {
+    // the departments hang off an unnamed root that never has employees of its own
+    root: Department,
+}
+
+impl Company {
+    pub fn new() -> Company {
+        Company::default()
+    }
+
+    // hands the company over to a handle that can be cloned into as many threads as needed
+    pub fn shared(self) -> SharedCompany {
+        SharedCompany::new(self)
+    }
+
+    // adds `employee` to `dept`, creating the department (and any above it) if needed and keeping it sorted by name.
+    // someone with the exact same name already in `dept` is most likely a typo'd re-add, so that's
+    // an error
+    pub fn add(&mut self, employee: impl Into<Employee>, dept: &str) -> Result<(), CompanyError> {
+        let employee = employee.into();
+        if self.contains(&employee.name, dept) {
+            return Err(CompanyError::Duplicate {
+                name: employee.name,
+                dept: dept.to_string(),
+            });
+        }
+        self.check_room(dept)?;
+        self.insert(employee, dept);
+        Ok(())
+    }
+
+    // Ok if one more person fits in `dept` and every department above it
+    pub fn check_room(&self, dept: &str) -> Result<(), CompanyError> {
+        match self.root.full_on_path(dept) {
+            Some(full) => Err(CompanyError::Full {
+                dept: full.path().to_string(),
+                capacity: full.capacity().unwrap_or_default(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    // caps how many people `dept` (sub-departments included) can have, or lifts the cap with None.
+    // people already over a new cap stay, nobody new gets in. hands back the old capacity
+    pub fn set_capacity(
+        &mut self,
+        dept: &str,
+        capacity: Option<usize>,
+    ) -> Result<Option<usize>, CompanyError> {
+        let department = self
+            .root
+            .find_mut(dept)
+            .ok_or_else(|| CompanyError::DepartmentNotFound(dept.to_string()))?;
+        let old = department.capacity();
+        department.set_capacity(capacity);
+        self.root.prune();
+        Ok(old)
+    }
+
+    // add without the duplicate check, for when two people really do share a name
+    pub fn insert(&mut self, employee: impl Into<Employee>, dept: &str) {
+        let employees = self.root.entry(dept).employees_mut();
+        employees.push(employee.into());
+        employees.sort_unstable();
+    }
+
+    // removes `name` from `dept` and hands back their record. once a department has nobody left in
+    // it or anywhere under it, the department itself goes away
+    pub fn remove(&mut self, name: &str, dept: &str) -> Result<Employee, CompanyError> {
+        let employees = self
+            .root
+            .find_mut(dept)
+            .ok_or_else(|| CompanyError::DepartmentNotFound(dept.to_string()))?
+            .employees_mut();
+        let index = employees
+            .iter()
+            .position(|e| e.name == name)
+            .ok_or_else(|| CompanyError::EmployeeNotFound {
+                name: name.to_string(),
+                dept: dept.to_string(),
+            })?;
+        let employee = employees.remove(index);
+        self.root.prune();
+        Ok(employee)
+    }
+
+    // takes `name` out of `from` and puts them (title, start date and all) in `to`. nothing changes
+    // unless the whole move can happen
+    pub fn move_employee(&mut self, name: &str, from: &str, to: &str) -> Result<(), CompanyError> {
+        if self.contains(name, to) {
+            return Err(CompanyError::Duplicate {
+                name: name.to_string(),
+                dept: to.to_string(),
+            });
+        }
+        let employee = self.remove(name, from)?;
+        if let Err(e) = self.check_room(to) {
+            self.insert(employee, from);
+            return Err(e);
+        }
+        self.insert(employee, to);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str, dept: &str) -> Result<&Employee, CompanyError> {
+        self.list(dept)?
+            .iter()
+            .find(|e| e.name == name)
+            .ok_or_else(|| CompanyError::EmployeeNotFound {
+                name: name.to_string(),
+                dept: dept.to_string(),
+            })
+    }
+
+    pub fn contains(&self, name: &str, dept: &str) -> bool {
+        self.get(name, dept).is_ok()
+    }
+
+    // the extra copies of every name that shows up more than once in `dept`. removing these leaves
+    // exactly one of each name behind
+    pub fn duplicates(&self, dept: &str) -> Result<Vec<Employee>, CompanyError> {
+        let employees = self.list(dept)?;
+        // employees are kept sorted by name, so copies always sit next to each other
+        Ok(employees
+            .windows(2)
+            .filter(|pair| pair[0].name == pair[1].name)
+            .map(|pair| pair[1].clone())
+            .collect())
+    }
+
+    pub fn department(&self, dept: &str) -> Result<&Department, CompanyError> {
+        self.root
+            .find(dept)
+            .ok_or_else(|| CompanyError::DepartmentNotFound(dept.to_string()))
+    }
+
+    // the people directly in `dept`, not counting its sub-departments
+    pub fn list(&self, dept: &str) -> Result<&[Employee], CompanyError> {
+        self.department(dept).map(|d| d.employees())
+    }
+
+    // every department that has people directly in it, with those people. parents come right
+    // before their sub-departments, and siblings are ordered by name
+    pub fn list_all(&self) -> Vec<(&str, &[Employee])> {
+        self.walk()
+            .into_iter()
+            .filter(|d| !d.employees().is_empty())
+            .map(|d| (d.path(), d.employees()))
+            .collect()
+    }
+
+    // the departments that aren't inside any other, ordered however `sort` says
+    pub fn top_level(&self, sort: Sort) -> Vec<&Department> {
+        self.root.sorted_subdepartments(sort)
+    }
+
+    // the path of every department, sub-departments included, in the same order as list_all
+    pub fn departments(&self) -> Vec<&str> {
+        self.walk().into_iter().map(|d| d.path()).collect()
+    }
+
+    // headcount of `dept` including everyone in its sub-departments
+    pub fn count(&self, dept: &str) -> Result<usize, CompanyError> {
+        self.department(dept).map(|d| d.headcount())
+    }
+
+    fn walk(&self) -> Vec<&Department> {
+        let mut all = Vec::new();
+        self.root.walk(&mut all);
+        all
+    }
+
+    // ties for largest/smallest go to whichever department comes first alphabetically
+    pub fn stats(&self) -> Stats<'_> {
+        let headcounts: Vec<(&str, usize)> = self
+            .list_all()
+            .into_iter()
+            .map(|(dept, employees)| (dept, employees.len()))
+            .collect();
+        let total = headcounts.iter().map(|(_, count)| count).sum();
+        let largest = headcounts
+            .iter()
+            .copied()
+            .reduce(|best, next| if next.1 > best.1 { next } else { best });
+        let smallest = headcounts
+            .iter()
+            .copied()
+            .reduce(|best, next| if next.1 < best.1 { next } else { best });
+        Stats {
+            headcounts,
+            total,
+            largest,
+            smallest,
+        }
+    }
+
+    // every (department, name) where the name matches `query` ignoring case. with `partial`, the
+    // query only has to show up somewhere in the name. results are ordered by department
+    pub fn find(&self, query: &str, partial: bool) -> Vec<(&str, &str)> {
+        let query = query.to_lowercase();
+        let mut found = Vec::new();
+        for (dept, employees) in self.list_all() {
+            for employee in employees {
+                let lower = employee.name.to_lowercase();
+                if lower == query || (partial && lower.contains(&query)) {
+                    found.push((dept, employee.name.as_str()));
+                }
+            }
+        }
+        found
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.subdepartments().next().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names<'a>(company: &'a Company, dept: &str) -> Vec<&'a str> {
+        company
+            .list(dept)
+            .unwrap()
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect()
+    }
+
+    fn sample() -> Company {
+        let mut company = Company::new();
+        company.insert("Sally", "Engineering");
+        company.insert("Amir", "Engineering");
+        company.insert("Bob", "Sales");
+        company
+    }
+
+    #[test]
+    fn add_keeps_names_sorted() {
+        let company = sample();
+        assert_eq!(vec!["Amir", "Sally"], names(&company, "Engineering"));
+    }
+
+    #[test]
+    fn add_rejects_duplicates() {
+        let mut company = sample();
+        assert_eq!(
+            Err(CompanyError::Duplicate {
+                name: "Sally".to_string(),
+                dept: "Engineering".to_string()
+            }),
+            company.add("Sally", "Engineering")
+        );
+        assert_eq!(Ok(()), company.add("Sally", "Sales"));
+        company.insert("Sally", "Engineering");
+        assert_eq!(Ok(3), company.count("Engineering"));
+    }
+
+    #[test]
+    fn duplicates_are_the_extra_copies() {
+        let mut company = sample();
+        company.insert("Sally", "Engineering");
+        company.insert("Sally", "Engineering");
+        company.insert("Amir", "Engineering");
+        assert_eq!(
+            Ok(vec![
+                Employee::new("Amir"),
+                Employee::new("Sally"),
+                Employee::new("Sally")
+            ]),
+            company.duplicates("Engineering")
+        );
+        assert_eq!(Ok(vec![]), company.duplicates("Sales"));
+    }
+
+    #[test]
+    fn remove_employee() {
+        let mut company = sample();
+        assert_eq!(
+            Ok(Employee::new("Sally")),
+            company.remove("Sally", "Engineering")
+        );
+        assert_eq!(vec!["Amir"], names(&company, "Engineering"));
+    }
+
+    #[test]
+    fn remove_last_employee_drops_department() {
+        let mut company = sample();
+        assert!(company.remove("Bob", "Sales").is_ok());
+        assert_eq!(vec!["Engineering"], company.departments());
+    }
+
+    #[test]
+    fn remove_unknown_name() {
+        let mut company = sample();
+        assert_eq!(
+            Err(CompanyError::EmployeeNotFound {
+                name: "Zed".to_string(),
+                dept: "Engineering".to_string()
+            }),
+            company.remove("Zed", "Engineering")
+        );
+        assert_eq!(
+            Err(CompanyError::DepartmentNotFound("Marketing".to_string())),
+            company.remove("Sally", "Marketing")
+        );
+        assert_eq!(sample(), company);
+    }
+
+    #[test]
+    fn move_employee_between_departments() {
+        let mut company = sample();
+        assert_eq!(
+            Ok(()),
+            company.move_employee("Sally", "Engineering", "Sales")
+        );
+        assert_eq!(vec!["Amir"], names(&company, "Engineering"));
+        assert_eq!(vec!["Bob", "Sally"], names(&company, "Sales"));
+    }
+
+    #[test]
+    fn move_keeps_the_whole_record() {
+        let mut company = Company::new();
+        let start = Date::new(2023, 1, 15).unwrap();
+        let sally = Employee::new("Sally")
+            .with_title("Senior Engineer")
+            .with_start(start);
+        company.add(sally.clone(), "Engineering").unwrap();
+        company
+            .move_employee("Sally", "Engineering", "Sales")
+            .unwrap();
+        assert_eq!(Ok(&sally), company.get("Sally", "Sales"));
+    }
+
+    #[test]
+    fn move_employee_creates_destination() {
+        let mut company = sample();
+        assert_eq!(Ok(()), company.move_employee("Bob", "Sales", "Marketing"));
+        assert_eq!(vec!["Engineering", "Marketing"], company.departments());
+    }
+
+    #[test]
+    fn failed_move_changes_nothing() {
+        let mut company = sample();
+        assert!(company
+            .move_employee("Zed", "Engineering", "Marketing")
+            .is_err());
+        company.insert("Sally", "Sales");
+        assert!(company
+            .move_employee("Sally", "Engineering", "Sales")
+            .is_err());
+        assert_eq!(Ok(2), company.count("Engineering"));
+    }
+
+    #[test]
+    fn capacity_limits_adds() {
+        let mut company = sample();
+        assert_eq!(Ok(None), company.set_capacity("Engineering", Some(3)));
+        assert_eq!(Ok(()), company.add("Cleo", "Engineering"));
+        assert_eq!(
+            Err(CompanyError::Full {
+                dept: "Engineering".to_string(),
+                capacity: 3
+            }),
+            company.add("Dan", "Engineering")
+        );
+        assert_eq!(Ok(Some(3)), company.set_capacity("Engineering", None));
+        assert_eq!(Ok(()), company.add("Dan", "Engineering"));
+        assert!(company.set_capacity("Legal", Some(1)).is_err());
+    }
+
+    #[test]
+    fn capacity_counts_sub_departments() {
+        let mut company = sample();
+        company.set_capacity("Sales", Some(2)).unwrap();
+        company.add("Dan", "Sales/EMEA").unwrap();
+        assert_eq!(
+            Err(CompanyError::Full {
+                dept: "Sales".to_string(),
+                capacity: 2
+            }),
+            company.add("Cleo", "Sales/EMEA/UK")
+        );
+        // Sales is full, but moving around inside it doesn't change its headcount
+        assert!(company.move_employee("Bob", "Sales", "Sales/EMEA").is_ok());
+        // moving someone out makes room, and a move into a full department changes nothing
+        assert!(company
+            .move_employee("Dan", "Sales/EMEA", "Engineering")
+            .is_ok());
+        assert!(company
+            .move_employee("Sally", "Engineering", "Sales")
+            .is_ok());
+        assert!(company
+            .move_employee("Dan", "Engineering", "Sales")
+            .is_err());
+        assert!(company.contains("Dan", "Engineering"));
+    }
+
+    #[test]
+    fn emptied_department_keeps_its_capacity() {
+        let mut company = sample();
+        company.set_capacity("Sales", Some(5)).unwrap();
+        company.remove("Bob", "Sales").unwrap();
+        assert_eq!(
+            Ok(Some(5)),
+            company.department("Sales").map(|d| d.capacity())
+        );
+    }
+
+    #[test]
+    fn find_ignores_case() {
+        let mut company = sample();
+        company.insert("sally", "Sales");
+        assert_eq!(
+            vec![("Engineering", "Sally"), ("Sales", "sally")],
+            company.find("SALLY", false)
+        );
+        assert!(company.find("Sal", false).is_empty());
+    }
+
+    #[test]
+    fn find_partial() {
+        let company = sample();
+        assert_eq!(
+            vec![("Engineering", "Amir"), ("Engineering", "Sally")],
+            company.find("a", true)
+        );
+    }
+
+    #[test]
+    fn count_and_stats() {
+        let mut company = sample();
+        company.insert("Cleo", "Marketing");
+        assert_eq!(Ok(2), company.count("Engineering"));
+        assert!(company.count("Legal").is_err());
+
+        let stats = company.stats();
+        assert_eq!(
+            vec![("Engineering", 2), ("Marketing", 1), ("Sales", 1)],
+            stats.headcounts
+        );
+        assert_eq!(4, stats.total);
+        assert_eq!(Some(("Engineering", 2)), stats.largest);
+        assert_eq!(Some(("Marketing", 1)), stats.smallest);
+    }
+
+    #[test]
+    fn stats_of_empty_company() {
+        let company = Company::new();
+        let stats = company.stats();
+        assert_eq!(0, stats.total);
+        assert_eq!(None, stats.largest);
+    }
+
+    fn dept_names(depts: Vec<&Department>) -> Vec<&str> {
+        depts.into_iter().map(|d| d.name()).collect()
+    }
+
+    #[test]
+    fn top_level_by_size() {
+        let mut company = sample();
+        company.insert("Cleo", "Marketing");
+        company.insert("Dan", "Marketing/Brand");
+        company.insert("Eve", "Marketing/Brand");
+        let sort = Sort {
+            by: SortBy::Size,
+            descending: false,
+        };
+        assert_eq!(
+            vec!["Sales", "Engineering", "Marketing"],
+            dept_names(company.top_level(sort))
+        );
+    }
+
+    #[test]
+    fn top_level_descending() {
+        let company = sample();
+        let sort = Sort {
+            by: SortBy::Name,
+            descending: true,
+        };
+        assert_eq!(
+            vec!["Sales", "Engineering"],
+            dept_names(company.top_level(sort))
+        );
+    }
+
+    #[test]
+    fn employees_descending() {
+        let company = sample();
+        let names: Vec<&str> = company
+            .department("Engineering")
+            .unwrap()
+            .sorted_employees(true)
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(vec!["Sally", "Amir"], names);
+    }
+
+    #[test]
+    fn nested_departments() {
+        let mut company = sample();
+        company.insert("Amir", "Sales/EMEA");
+        company.insert("Cleo", "Sales/EMEA/UK");
+        company.insert("Dan", "Sales/APAC");
+
+        assert_eq!(
+            vec![
+                "Engineering",
+                "Sales",
+                "Sales/APAC",
+                "Sales/EMEA",
+                "Sales/EMEA/UK"
+            ],
+            company.departments()
+        );
+        assert_eq!(vec!["Bob"], names(&company, "Sales"));
+        assert_eq!(vec!["Amir"], names(&company, "Sales/EMEA"));
+        assert_eq!(Ok(4), company.count("Sales"));
+        assert_eq!(Ok(2), company.count("Sales/EMEA"));
+        assert_eq!(6, company.stats().total);
+    }
+
+    #[test]
+    fn department_without_direct_employees() {
+        let mut company = Company::new();
+        company.insert("Amir", "Sales/EMEA");
+        assert_eq!(Ok(&[][..]), company.list("Sales"));
+        assert_eq!(Ok(1), company.count("Sales"));
+        assert_eq!(
+            vec![("Sales/EMEA", &[Employee::new("Amir")][..])],
+            company.list_all()
+        );
+    }
+
+    #[test]
+    fn removing_the_last_employee_prunes_empty_parents() {
+        let mut company = sample();
+        company.insert("Amir", "Marketing/Brand/Online");
+        company.remove("Amir", "Marketing/Brand/Online").unwrap();
+        assert_eq!(vec!["Engineering", "Sales"], company.departments());
+        assert_eq!(sample(), company);
+    }
+
+    #[test]
+    fn move_into_sub_department() {
+        let mut company = sample();
+        company.move_employee("Bob", "Sales", "Sales/EMEA").unwrap();
+        assert_eq!(
+            vec!["Engineering", "Sales", "Sales/EMEA"],
+            company.departments()
+        );
+        assert!(company.list("Sales").unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_all_is_ordered_by_department() {
+        let company = sample();
+        let depts: Vec<&str> = company.list_all().into_iter().map(|(d, _)| d).collect();
+        assert_eq!(vec!["Engineering", "Sales"], depts);
+    }
+}
@@ -7,70 +7,1115 @@ company: HashMap = {
 }
 */
 
+mod editor;
+mod render;
+mod serve;
+
+use editor::Editor;
+use ex3::{
+    audit::{self, AuditLog},
+    command::{Command, ExportFormat, Target},
+    history::{Change, History},
+    messages::{Language, Msg},
+    parse, storage, suggest, Company, CompanyError, Sort, Stats,
+};
 use std::{
-    collections::HashMap,
-    io::{self, Write},
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, ErrorKind, IsTerminal, Write},
+    path::{Component, Path},
+    process,
 };
 
-const HELP_MESSAGE: &str = r#"
+// what the main loop should do once a command has been handled
+enum Action {
+    Print(String),
+    // like Print, but the command didn't work
+    Fail(String),
+    // ask `question`, and run `command` without asking again if the answer is yes. `typed` is
+    // how to type that in, for when there's nobody to ask
+    Confirm {
+        question: String,
+        command: Command,
+        typed: String,
+    },
+    Exit,
+}
 
-Available commands:
-    - 'Add <Name> to <Department>' to do exactly that
-    - 'List <department>` to list every employee in the company in a tree-like structure
-    - 'List all' to list every employee within this department
-    - 'Exit' to stop AdminCLI
-"#;
+// everything that lives for as long as AdminCLI is running
+#[derive(Default)]
+struct Session {
+    company: Company,
+    history: History,
+    // when on, changes are tried out on a copy of the company and only reported
+    dry_run: bool,
+    language: Language,
+    // every change that goes through gets written down here
+    audit: AuditLog,
+    // when on, only commands that look without touching anything are allowed
+    viewer: bool,
+    // when on, files can only be named, not given a path, so they all stay in the current directory
+    remote: bool,
+}
 
-fn main() {
-    println!("Welcome to AdminCLI. Do administrative things for a totally real company!");
+impl Session {
+    fn say(&self, msg: Msg) -> String {
+        msg.text(self.language)
+    }
+}
 
-    let mut company: HashMap<String, Vec<String>> = HashMap::new();
-    let mut input = String::new();
+// run with a file argument (or with commands piped in) and AdminCLI works through them one per
+// line without any of the interactive chatter, exiting with 1 if any of them failed. with
+// `--serve [address]` it takes commands over TCP instead. `--viewer` anywhere makes AdminCLI
+// read-only, for letting people look without touching. messages come out in whatever language
+// LANG (or LC_ALL/LC_MESSAGES) asks for
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let viewer = args.iter().any(|a| a == "--viewer");
+    args.retain(|a| a != "--viewer");
+    let language = Language::from_env();
+    let say = |msg: Msg| msg.text(language);
+    let (script, serving) = match args.as_slice() {
+        [serve] if serve == "--serve" => (None, Some(serve::DEFAULT_ADDRESS.to_string())),
+        [serve, address] if serve == "--serve" => (None, Some(address.clone())),
+        // anything else starting with -- is a flag we don't have, not a script
+        [path] if !path.starts_with("--") => (Some(path.clone()), None),
+        [] => (None, None),
+        _ => {
+            eprintln!("{}", say(Msg::Usage));
+            process::exit(2);
+        }
+    };
+    let batch = script.is_some() || serving.is_some() || !io::stdin().is_terminal();
+    if !batch {
+        println!("{}", say(Msg::Welcome));
+    }
 
-    loop {
-        println!("{}", HELP_MESSAGE);
-        print!("Enter command: ");
-        input.clear();
-        io::stdout().flush().unwrap();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("\nerror: unable to read your input");
-        let words: Vec<&str> = input.trim().split(' ').collect();
-        match words.as_slice() {
-            ["Add", name, "to", dept] => {
-                company
-                    .entry(dept.to_string())
-                    .or_default()
-                    .push(name.to_string());
-                if let Some(x) = company.get_mut(dept.to_owned()) {
-                    x[..].sort_unstable();
-                }
-            }
-            ["List", "all"] => {
-                for (dept, names) in &company {
-                    println!("\n[{}]", dept);
-                    for name in names {
-                        println!("    {}", name);
-                    }
+    let default_path = Path::new(storage::DEFAULT_PATH);
+    let company = if default_path.exists() {
+        match storage::load(default_path) {
+            Ok(company) => {
+                if !batch {
+                    println!("{}", say(Msg::Loaded(storage::DEFAULT_PATH)));
                 }
+                company
             }
-            ["List", dept] => match company.get(*dept) {
-                Some(names) => {
-                    println!("\n[{}]", dept);
-                    for name in names {
-                        println!("    {}", name);
-                    }
+            // the company gets saved over company.txt on the way out, so whatever's in there has to
+            // be moved out of the way first, or not started with at all
+            Err(e) if e.kind() == ErrorKind::InvalidData => match storage::recover(default_path) {
+                Ok(bad) => {
+                    eprintln!(
+                        "{}",
+                        say(Msg::MovedAside {
+                            path: storage::DEFAULT_PATH,
+                            error: &e,
+                            moved_to: &bad.display().to_string(),
+                        })
+                    );
+                    Company::new()
                 }
-                None => {
-                    println!("\n'{}' department not found", dept);
-                    continue;
+                Err(moving) => {
+                    eprintln!(
+                        "{}",
+                        say(Msg::CouldntMoveAside {
+                            path: storage::DEFAULT_PATH,
+                            error: &e,
+                            moving: &moving,
+                        })
+                    );
+                    process::exit(1);
                 }
             },
-            ["Exit"] => {
-                println!("\nAdminCLI stopped ... Have a nice day\n");
-                break;
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    say(Msg::CouldntLoad {
+                        path: storage::DEFAULT_PATH,
+                        error: &e
+                    })
+                );
+                process::exit(1);
+            }
+        }
+    } else {
+        Company::new()
+    };
+    if let Some(address) = serving {
+        if let Err(e) = serve::serve(&address, company.shared(), language, viewer) {
+            eprintln!(
+                "{}",
+                say(Msg::CouldntServe {
+                    address: &address,
+                    error: &e
+                })
+            );
+        }
+        process::exit(1);
+    }
+    let mut session = Session {
+        company,
+        language,
+        audit: AuditLog::new(audit::DEFAULT_PATH),
+        viewer,
+        ..Session::default()
+    };
+    if viewer && !batch {
+        println!("{}", say(Msg::ReadOnly));
+    }
+
+    let ok = match script {
+        Some(path) => match File::open(&path) {
+            Ok(file) => run_batch(&mut session, BufReader::new(file)),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    say(Msg::CouldntOpen {
+                        path: &path,
+                        error: &e
+                    })
+                );
+                process::exit(1);
+            }
+        },
+        None if batch => run_batch(&mut session, io::stdin().lock()),
+        None => {
+            run_interactive(&mut session);
+            true
+        }
+    };
+
+    // Set language may have switched languages along the way
+    let say = |msg: Msg| session.say(msg);
+    // a viewer can't have changed anything, and may not be allowed to write here anyway
+    let saved = match session.viewer {
+        true => Ok(()),
+        false => storage::save(&session.company, default_path),
+    };
+    if let Err(e) = saved {
+        eprintln!(
+            "\n{}",
+            say(Msg::CouldntSave {
+                path: storage::DEFAULT_PATH,
+                error: &e
+            })
+        );
+        process::exit(1);
+    }
+    if !batch {
+        println!("\n{}\n", say(Msg::Goodbye));
+    }
+    if !ok {
+        process::exit(1);
+    }
+}
+
+fn run_interactive(session: &mut Session) {
+    let mut editor = Editor::new();
+    loop {
+        println!("{}", session.say(Msg::Help));
+        let prompt = session.say(Msg::Prompt {
+            dry_run: session.dry_run,
+        });
+        let input = match editor.read_line(&prompt, &completions(session)) {
+            Ok(Some(input)) => input,
+            // ctrl-d counts as Exit
+            Ok(None) => break,
+            Err(e) => panic!("\n{}", session.say(Msg::InputFailed(&e))),
+        };
+        let mut action = execute(session, &input);
+        while let Action::Confirm {
+            question, command, ..
+        } = action
+        {
+            action = if ask(session, &question) {
+                run_command(session, command, true)
+            } else {
+                Action::Print(format!("\n{}", session.say(Msg::NeverMind)))
+            };
+        }
+        match action {
+            Action::Print(msg) | Action::Fail(msg) => println!("{}", msg),
+            Action::Confirm { .. } => unreachable!(),
+            Action::Exit => break,
+        }
+    }
+}
+
+// runs every line of `script` as a command. blank lines and lines starting with # are skipped.
+// returns false if any command failed. there's nobody around to answer questions, so anything
+// that needs confirming counts as a failure
+fn run_batch(session: &mut Session, script: impl BufRead) -> bool {
+    let mut ok = true;
+    for (i, line) in script.lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                let text = session.say(Msg::LineUnreadable(&e));
+                eprintln!(
+                    "{}",
+                    session.say(Msg::Line {
+                        number: i + 1,
+                        text: &text
+                    })
+                );
+                return false;
+            }
+        };
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        match execute(session, &line) {
+            Action::Print(msg) => println!("{}", msg.trim_start()),
+            Action::Fail(msg) => {
+                let text = msg.trim_start();
+                eprintln!(
+                    "{}",
+                    session.say(Msg::Line {
+                        number: i + 1,
+                        text
+                    })
+                );
+                ok = false;
+            }
+            Action::Confirm {
+                question, typed, ..
+            } => {
+                let text = format!("{} {}", question, session.say(Msg::SkipQuestion(&typed)));
+                eprintln!(
+                    "{}",
+                    session.say(Msg::Line {
+                        number: i + 1,
+                        text: &text
+                    })
+                );
+                ok = false;
+            }
+            Action::Exit => break,
+        }
+    }
+    ok
+}
+
+// what Tab can complete to: every keyword plus the departments that exist right now
+fn completions(session: &Session) -> Vec<String> {
+    let mut candidates: Vec<String> = parse::KEYWORDS.iter().map(|k| k.to_string()).collect();
+    candidates.extend(session.company.departments().into_iter().map(parse::quote));
+    candidates
+}
+
+// prints `question` and waits for a yes or no. the answer can be given in any language we speak
+fn ask(session: &Session, question: &str) -> bool {
+    let mut answer = String::new();
+    loop {
+        print!("\n{} {} ", question, session.say(Msg::YesNo));
+        io::stdout().flush().unwrap();
+        answer.clear();
+        if io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+            return false;
+        }
+        if let Some(yes) = self::answer(&answer) {
+            return yes;
+        }
+    }
+}
+
+// Some(true) for yes, Some(false) for no, and None for anything that's neither
+fn answer(answer: &str) -> Option<bool> {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" | "s" | "si" | "sí" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn execute(session: &mut Session, input: &str) -> Action {
+    let (input, sure) = parse::strip_bang(input);
+    let action = run(session, &input, sure);
+    // when a shortcut was used, show the full command so it sticks
+    match (parse::expand_alias(&input), action) {
+        (Some(full), Action::Print(msg)) => Action::Print(format!("\n> {}{}", full, msg)),
+        (Some(full), Action::Fail(msg)) => Action::Fail(format!("\n> {}{}", full, msg)),
+        (_, action) => action,
+    }
+}
+
+// `sure` skips the question destructive changes would otherwise ask first
+fn run(session: &mut Session, input: &str, sure: bool) -> Action {
+    match input.parse() {
+        Ok(command) => run_command(session, command, sure),
+        Err(e) => Action::Fail(format!("\n{}", session.say(Msg::Parse(&e)))),
+    }
+}
+
+// `run` once the command's been parsed, which is also where an answered question picks up again
+fn run_command(session: &mut Session, command: Command, sure: bool) -> Action {
+    if session.viewer && !command.read_only() {
+        return Action::Fail(format!("\n{}", session.say(Msg::ReadOnly)));
+    }
+    let confirm = command.clone();
+    let change = match command {
+        Command::Add {
+            employee,
+            dept,
+            force,
+        } => Change::Add {
+            employee,
+            dept,
+            force,
+        },
+        Command::Remove { name, dept } => match session.company.get(&name, &dept) {
+            Ok(employee) => Change::Remove {
+                employee: employee.clone(),
+                dept,
+            },
+            Err(e) => return fail(session, &e),
+        },
+        Command::Move { name, from, to } => Change::Move { name, from, to },
+        Command::Dedup(dept) => match session.company.duplicates(&dept) {
+            Ok(extra) if extra.is_empty() => {
+                return Action::Print(format!("\n{}", session.say(Msg::NoDuplicates(&dept))))
+            }
+            Ok(extra) => Change::Batch(
+                extra
+                    .into_iter()
+                    .map(|employee| Change::Remove {
+                        employee,
+                        dept: dept.clone(),
+                    })
+                    .collect(),
+            ),
+            Err(e) => return fail(session, &e),
+        },
+        Command::SetCapacity { dept, capacity } => match session.company.department(&dept) {
+            Ok(department) => Change::SetCapacity {
+                from: department.capacity(),
+                to: capacity,
+                dept,
+            },
+            Err(e) => return fail(session, &e),
+        },
+        command => return inspect(session, command),
+    };
+    if session.dry_run {
+        return dry_run(session, &change);
+    }
+    if let Some(question) = question(&change).filter(|_| !sure) {
+        let typed = match &confirm {
+            Command::Dedup(dept) => format!("Dedup! {}", parse::quote(dept)),
+            _ => parse::add_bang(&change.to_string()),
+        };
+        return Action::Confirm {
+            question: session.say(question),
+            command: confirm,
+            typed,
+        };
+    }
+    match session.history.apply(&mut session.company, change.clone()) {
+        Ok(()) => logged(session, &change.to_string(), Msg::Done(&change)),
+        Err(e @ CompanyError::Duplicate { .. }) => {
+            let Change::Add { employee, dept, .. } = change else {
+                return fail(session, &e);
+            };
+            let typed = Change::Add {
+                employee: employee.clone(),
+                dept: dept.clone(),
+                force: true,
+            }
+            .to_string();
+            Action::Confirm {
+                question: session.say(Msg::ConfirmDuplicate(&e)),
+                command: Command::Add {
+                    employee,
+                    dept,
+                    force: true,
+                },
+                typed,
+            }
+        }
+        Err(e) => fail(session, &e),
+    }
+}
+
+// reports a change that went through after adding `entry` to the audit log. the change stands
+// even if the log can't be written, so that's a warning rather than a failure
+fn logged(session: &Session, entry: &str, done: Msg) -> Action {
+    let mut out = format!("\n{}", session.say(done));
+    if let Err(e) = session.audit.record(entry) {
+        out.push_str(&format!("\n{}", session.say(Msg::CouldntLog(&e))));
+    }
+    Action::Print(out)
+}
+
+// what to ask before a change that takes people out of the company
+fn question(change: &Change) -> Option<Msg<'_>> {
+    match change {
+        Change::Remove { employee, dept } => Some(Msg::ConfirmRemove {
+            name: &employee.name,
+            dept,
+        }),
+        Change::Batch(changes) => Some(Msg::ConfirmDedup(changes.len())),
+        _ => None,
+    }
+}
+
+// tries `change` on a copy of the company and says how it went
+fn dry_run(session: &Session, change: &Change) -> Action {
+    let mut scratch = session.company.clone();
+    match change.apply_to(&mut scratch) {
+        Ok(()) => Action::Print(format!("\n{}", session.say(Msg::WouldDo(change)))),
+        Err(e) => fail(session, &e),
+    }
+}
+
+// every command that doesn't go through the undo history
+fn inspect(session: &mut Session, command: Command) -> Action {
+    let print = |msg: Msg| Action::Print(format!("\n{}", msg.text(session.language)));
+    if let Command::Export { path, .. } | Command::Save(Some(path)) | Command::Load(Some(path)) =
+        &command
+    {
+        if session.remote && !just_a_name(path) {
+            return Action::Fail(format!("\n{}", session.say(Msg::NotAFileName(path))));
+        }
+    }
+    let company = &session.company;
+    match command {
+        Command::DryRun(on) => {
+            session.dry_run = on;
+            print(if on { Msg::DryRunOn } else { Msg::DryRunOff })
+        }
+        Command::SetLanguage(language) => {
+            session.language = language;
+            Action::Print(format!("\n{}", session.say(Msg::LanguageSet)))
+        }
+        Command::Undo if session.dry_run => match session.history.peek_undo() {
+            Some(change) => print(Msg::WouldUndo(change)),
+            None => print(Msg::NothingToUndo),
+        },
+        Command::Redo if session.dry_run => match session.history.peek_redo() {
+            Some(change) => print(Msg::WouldRedo(change)),
+            None => print(Msg::NothingToRedo),
+        },
+        Command::Undo => match session.history.undo(&mut session.company) {
+            Some(Ok(change)) => logged(session, &format!("Undo {}", change), Msg::Undid(&change)),
+            Some(Err(e)) => Action::Fail(format!("\n{}", session.say(Msg::CouldntUndo(&e)))),
+            None => print(Msg::NothingToUndo),
+        },
+        Command::Redo => match session.history.redo(&mut session.company) {
+            Some(Ok(change)) => logged(session, &format!("Redo {}", change), Msg::Redid(&change)),
+            Some(Err(e)) => Action::Fail(format!("\n{}", session.say(Msg::CouldntRedo(&e)))),
+            None => print(Msg::NothingToRedo),
+        },
+        Command::History(count) => match session.audit.recent(count) {
+            Ok(entries) if entries.is_empty() => print(Msg::NothingLogged),
+            Ok(entries) => Action::Print(format!("\n{}", entries.join("\n"))),
+            Err(e) => Action::Fail(format!("\n{}", session.say(Msg::CouldntReadLog(&e)))),
+        },
+        Command::List {
+            target: Target::All,
+            sort,
+        } => Action::Print(render::tree(&company.top_level(sort), sort)),
+        Command::List {
+            target: Target::Department(dept),
+            sort,
+        } => match company.department(&dept) {
+            Ok(dept) => Action::Print(render::department(dept, sort, 0)),
+            Err(e) => fail(session, &e),
+        },
+        Command::Find { query, partial } => {
+            Action::Print(found(session, &query, &company.find(&query, partial)))
+        }
+        Command::Count(dept) => match company.count(&dept) {
+            Ok(count) => print(Msg::Headcount { dept: &dept, count }),
+            Err(e) => fail(session, &e),
+        },
+        Command::Stats => Action::Print(stats_table(session, &company.stats())),
+        Command::Export { format, path } => export(session, format, &path),
+        Command::Save(path) => save(session, path.as_deref().unwrap_or(storage::DEFAULT_PATH)),
+        Command::Load(path) => load(session, path.as_deref().unwrap_or(storage::DEFAULT_PATH)),
+        Command::Exit => Action::Exit,
+        // the rest are changes, and run() has already taken care of those
+        Command::Add { .. }
+        | Command::Remove { .. }
+        | Command::Move { .. }
+        | Command::Dedup(_)
+        | Command::SetCapacity { .. } => {
+            unreachable!()
+        }
+    }
+}
+
+// whether `path` is a file in the current directory, and not somewhere else
+fn just_a_name(path: &str) -> bool {
+    let mut components = Path::new(path).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    )
+}
+
+// a failed command, with a guess at what was meant when it looks like a typo
+fn fail(session: &Session, e: &CompanyError) -> Action {
+    let company = &session.company;
+    let guess = match e {
+        CompanyError::DepartmentNotFound(dept) => suggest::closest(dept, company.departments()),
+        CompanyError::EmployeeNotFound { name, dept } => match company.list(dept) {
+            Ok(employees) => suggest::closest(name, employees.iter().map(|e| e.name.as_str())),
+            Err(_) => None,
+        },
+        _ => None,
+    };
+    let error = session.say(Msg::Company(e));
+    match guess {
+        Some(guess) => Action::Fail(format!(
+            "\n{}",
+            session.say(Msg::DidYouMean {
+                error: &error,
+                guess
+            })
+        )),
+        None => Action::Fail(format!("\n{}", error)),
+    }
+}
+
+fn found(session: &Session, query: &str, found: &[(&str, &str)]) -> String {
+    if found.is_empty() {
+        return format!("\n{}", session.say(Msg::NobodyMatching(query)));
+    }
+    let mut out = format!("\n{}", session.say(Msg::FoundIn(query)));
+    for (dept, name) in found {
+        out.push_str(&format!("\n    {}: {}", dept, name));
+    }
+    out
+}
+
+fn stats_table(session: &Session, stats: &Stats) -> String {
+    let header = (session.say(Msg::Department), session.say(Msg::Employees));
+    let total = session.say(Msg::Total);
+    let width = stats
+        .headcounts
+        .iter()
+        .map(|(dept, _)| dept.chars().count())
+        .chain([header.0.chars().count(), total.chars().count()])
+        .max()
+        .unwrap();
+    let count_width = header.1.chars().count();
+    let rule = format!("{}-+-{}", "-".repeat(width), "-".repeat(count_width));
+
+    let mut out = format!("\n{:<width$} | {}\n{}", header.0, header.1, rule);
+    for (dept, count) in &stats.headcounts {
+        out.push_str(&format!("\n{:<width$} | {:>count_width$}", dept, count));
+    }
+    out.push_str(&format!(
+        "\n{}\n{:<width$} | {:>count_width$}",
+        rule, total, stats.total
+    ));
+    if let (Some(largest), Some(smallest)) = (stats.largest, stats.smallest) {
+        out.push_str(&format!(
+            "\n\n{}: {} ({})\n{}: {} ({})",
+            session.say(Msg::Largest),
+            largest.0,
+            largest.1,
+            session.say(Msg::Smallest),
+            smallest.0,
+            smallest.1
+        ));
+    }
+    out
+}
+
+// writes the company to `path` laid out the way `format` says
+fn export(session: &Session, format: ExportFormat, path: &str) -> Action {
+    let company = &session.company;
+    let text = match format {
+        ExportFormat::Tree => {
+            let sort = Sort::default();
+            render::tree(&company.top_level(sort), sort)
+        }
+        ExportFormat::Table => render::table(company, session.language),
+    };
+    // the renderers start every line with a newline for the terminal's sake, a file wants them at
+    // the end instead
+    match fs::write(path, format!("{}\n", text.trim_start_matches('\n'))) {
+        Ok(()) => Action::Print(format!("\n{}", session.say(Msg::Exported(path)))),
+        Err(e) => Action::Fail(format!(
+            "\n{}",
+            session.say(Msg::CouldntExport { path, error: &e })
+        )),
+    }
+}
+
+fn save(session: &Session, path: &str) -> Action {
+    match storage::save(&session.company, Path::new(path)) {
+        Ok(()) => Action::Print(format!("\n{}", session.say(Msg::Saved(path)))),
+        Err(e) => Action::Fail(format!(
+            "\n{}",
+            session.say(Msg::CouldntSave { path, error: &e })
+        )),
+    }
+}
+
+fn load(session: &mut Session, path: &str) -> Action {
+    match storage::load(Path::new(path)) {
+        Ok(_) if session.dry_run => {
+            Action::Print(format!("\n{}", session.say(Msg::WouldLoad(path))))
+        }
+        Ok(loaded) => {
+            // the old history describes a company that isn't here anymore
+            session.company = loaded;
+            session.history.clear();
+            logged(session, &format!("Load {}", path), Msg::Loaded(path))
+        }
+        Err(e) => Action::Fail(format!(
+            "\n{}",
+            session.say(Msg::CouldntLoad { path, error: &e })
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ex3::Employee;
+
+    fn print(action: Action) -> String {
+        match action {
+            Action::Print(msg) | Action::Fail(msg) => msg,
+            _ => panic!("command should just print"),
+        }
+    }
+
+    #[test]
+    fn remove_command_is_parsed() {
+        let mut session = Session::default();
+        session.company.insert("Amir", "Engineering");
+        session.company.insert("Sally", "Engineering");
+
+        let msg = print(execute(&mut session, "Remove! Amir from Engineering\n"));
+        assert_eq!("\nremoved Amir from Engineering", msg);
+        assert_eq!(Ok(1), session.company.count("Engineering"));
+        assert!(session.company.contains("Sally", "Engineering"));
+
+        let msg = print(execute(&mut session, "Remove Amir from Engineering"));
+        assert_eq!("\n'Amir' not found in Engineering", msg);
+    }
+
+    #[test]
+    fn move_command_is_parsed() {
+        let mut session = Session::default();
+        session.company.insert("Bob", "Sales");
+
+        let msg = print(execute(&mut session, "Move Bob from Sales to Marketing"));
+        assert_eq!("\nmoved Bob from Sales to Marketing", msg);
+        assert_eq!(vec!["Marketing"], session.company.departments());
+    }
+
+    #[test]
+    fn undo_and_redo_commands() {
+        let mut session = Session::default();
+        execute(&mut session, "Add Sally to Engineering");
+
+        let msg = print(execute(&mut session, "Undo"));
+        assert_eq!("\nundid 'Add Sally to Engineering'", msg);
+        assert!(session.company.is_empty());
+
+        let msg = print(execute(&mut session, "Redo"));
+        assert_eq!("\nredid 'Add Sally to Engineering'", msg);
+        assert_eq!(print(execute(&mut session, "Redo")), "\nnothing to redo");
+    }
+
+    #[test]
+    fn lowercase_commands_and_quoted_names() {
+        let mut session = Session::default();
+        let msg = print(execute(
+            &mut session,
+            r#"add "Mary Ann" TO "Human Resources""#,
+        ));
+        assert_eq!("\nadded Mary Ann to Human Resources", msg);
+        assert!(session.company.contains("Mary Ann", "Human Resources"));
+
+        let msg = print(execute(&mut session, "undo"));
+        assert_eq!("\nundid 'Add \"Mary Ann\" to \"Human Resources\"'", msg);
+    }
+
+    #[test]
+    fn alias_prints_the_full_command() {
+        let mut session = Session::default();
+        let msg = print(execute(&mut session, "a Sally to Engineering"));
+        assert_eq!(
+            "\n> Add Sally to Engineering\nadded Sally to Engineering",
+            msg
+        );
+    }
+
+    #[test]
+    fn find_command() {
+        let mut session = Session::default();
+        session.company.insert("Sally", "Engineering");
+        session.company.insert("Sally", "Sales");
+
+        let msg = print(execute(&mut session, "find sally"));
+        assert_eq!(
+            "\n'sally' found in:\n    Engineering: Sally\n    Sales: Sally",
+            msg
+        );
+        let msg = print(execute(&mut session, "Find al --partial"));
+        assert!(msg.contains("Engineering: Sally"));
+        let msg = print(execute(&mut session, "Find Bob"));
+        assert_eq!("\nnobody matching 'Bob' works here", msg);
+    }
+
+    #[test]
+    fn stats_table_is_aligned() {
+        let mut session = Session::default();
+        session.company.insert("Sally", "Engineering");
+        session.company.insert("Amir", "Engineering");
+        session.company.insert("Bob", "Sales");
+
+        let expected = "
+Department  | Employees
+------------+----------
+Engineering |         2
+Sales       |         1
+------------+----------
+Total       |         3
+
+Largest: Engineering (2)
+Smallest: Sales (1)";
+        assert_eq!(expected, print(execute(&mut session, "stats")));
+        assert_eq!(
+            "\nSales has 1 employee(s)",
+            print(execute(&mut session, "Count Sales"))
+        );
+    }
+
+    #[test]
+    fn duplicate_add_asks_first() {
+        let mut session = Session::default();
+        execute(&mut session, "Add Sally to Engineering");
+
+        match execute(&mut session, "add Sally to Engineering") {
+            Action::Confirm {
+                question,
+                command,
+                typed,
+            } => {
+                assert_eq!(
+                    "Sally already works in Engineering. Add another one anyway?",
+                    question
+                );
+                assert_eq!("Add Sally to Engineering --force", typed);
+                run_command(&mut session, command, true);
+            }
+            _ => panic!("duplicate add should ask for confirmation"),
+        }
+        assert_eq!(Ok(2), session.company.count("Engineering"));
+
+        // what gets added is what was asked about, keyword or not
+        execute(&mut session, r#"Add "List" to Sales"#);
+        let Action::Confirm { command, .. } = execute(&mut session, r#"Add "List" to Sales"#)
+        else {
+            panic!("duplicate add should ask for confirmation");
+        };
+        run_command(&mut session, command, true);
+        assert_eq!(Ok(2), session.company.count("Sales"));
+        assert_eq!(
+            vec![Employee::new("List")],
+            session.company.duplicates("Sales").unwrap()
+        );
+    }
+
+    #[test]
+    fn dedup_command() {
+        let mut session = Session::default();
+        session.company.insert("Sally", "Engineering");
+        session.company.insert("Sally", "Engineering");
+        session.company.insert("Amir", "Engineering");
+
+        let msg = print(execute(&mut session, "Dedup! Engineering"));
+        assert_eq!("\nremoved Sally from Engineering", msg);
+        assert_eq!(
+            "\nno duplicates in Engineering",
+            print(execute(&mut session, "Dedup Engineering"))
+        );
+        execute(&mut session, "Undo");
+        assert_eq!(Ok(3), session.company.count("Engineering"));
+    }
+
+    #[test]
+    fn add_with_title_and_start_date() {
+        let mut session = Session::default();
+        execute(
+            &mut session,
+            r#"Add Sally to Engineering as "Senior Engineer" since 2023-01-15"#,
+        );
+        execute(&mut session, "Add Amir to Engineering as Intern");
+        execute(&mut session, "Add Bo to Engineering");
+
+        let expected = "
+[Engineering] (3)
+    Amir   Intern           -
+    Bo     -                -
+    Sally  Senior Engineer  2023-01-15";
+        assert_eq!(expected, print(execute(&mut session, "List Engineering")));
+
+        let msg = print(execute(&mut session, "Add Cy to Sales since yesterday"));
+        assert_eq!("\n'yesterday' isn't a date, use YYYY-MM-DD", msg);
+    }
+
+    #[test]
+    fn undo_remove_brings_back_details() {
+        let mut session = Session::default();
+        execute(&mut session, "Add Sally to Engineering as Boss");
+        execute(&mut session, "Remove! Sally from Engineering");
+        execute(&mut session, "Undo");
+        assert_eq!(
+            Ok(&Employee::new("Sally").with_title("Boss")),
+            session.company.get("Sally", "Engineering")
+        );
+    }
+
+    #[test]
+    fn did_you_mean() {
+        let mut session = Session::default();
+        execute(&mut session, "Add Sally to Engineering");
+
+        assert_eq!(
+            "\n'Enginering' department not found - did you mean 'Engineering'?",
+            print(execute(&mut session, "List Enginering"))
+        );
+        assert_eq!(
+            "\n'Saly' not found in Engineering - did you mean 'Sally'?",
+            print(execute(&mut session, "Remove Saly from Engineering"))
+        );
+        assert_eq!(
+            "\nunknown command 'lsit' - did you mean 'list'?",
+            print(execute(&mut session, "lsit all"))
+        );
+        assert_eq!(
+            "\nunknown command, use only the defined commands",
+            print(execute(&mut session, "hire Sally"))
+        );
+    }
+
+    #[test]
+    fn list_with_sort_options() {
+        let mut session = Session::default();
+        execute(&mut session, "Add Sally to Engineering");
+        execute(&mut session, "Add Amir to Engineering");
+        execute(&mut session, "Add Bob to Sales");
+
+        assert_eq!(
+            "\n[Sales] (1)\n    Bob\n[Engineering] (2)\n    Amir\n    Sally",
+            print(execute(&mut session, "List all by size"))
+        );
+        assert_eq!(
+            "\n[Engineering] (2)\n    Sally\n    Amir",
+            print(execute(&mut session, "list Engineering DESC"))
+        );
+        assert!(matches!(
+            execute(&mut session, "List Engineering by color"),
+            Action::Fail(_)
+        ));
+    }
+
+    #[test]
+    fn list_nested_departments() {
+        let mut session = Session::default();
+        execute(&mut session, "Add Bob to Sales");
+        execute(&mut session, "Add Amir to Sales/EMEA");
+        execute(&mut session, "Add Cleo to Sales/EMEA/UK");
+        execute(&mut session, "Add Dan to Sales/APAC");
+
+        let expected = "
+[Sales] (4)
+    Bob
+    [APAC] (1)
+        Dan
+    [EMEA] (2)
+        Amir
+        [UK] (1)
+            Cleo";
+        assert_eq!(expected, print(execute(&mut session, "List Sales")));
+        assert_eq!(
+            "\n[Sales/EMEA] (2)\n    Amir\n    [UK] (1)\n        Cleo",
+            print(execute(&mut session, "List Sales/EMEA"))
+        );
+        assert_eq!(
+            "\nSales has 4 employee(s)",
+            print(execute(&mut session, "Count Sales"))
+        );
+    }
+
+    #[test]
+    fn set_capacity() {
+        let mut session = Session::default();
+        execute(&mut session, "Add Sally to Engineering");
+        assert_eq!(
+            "\nset the capacity of Engineering to 2",
+            print(execute(&mut session, "SetCapacity Engineering 2"))
+        );
+        execute(&mut session, "Add Amir to Engineering");
+        assert_eq!(
+            "\n[Engineering] (2/2)\n    Amir\n    Sally",
+            print(execute(&mut session, "List Engineering"))
+        );
+        assert!(matches!(
+            execute(&mut session, "Add Bob to Engineering"),
+            Action::Fail(_)
+        ));
+        // the Add that didn't happen isn't in the history, Amir's is
+        execute(&mut session, "Undo");
+        execute(&mut session, "Undo");
+        assert_eq!(
+            None,
+            session
+                .company
+                .department("Engineering")
+                .unwrap()
+                .capacity()
+        );
+        assert!(matches!(
+            execute(&mut session, "SetCapacity Legal 5"),
+            Action::Fail(_)
+        ));
+    }
+
+    #[test]
+    fn changes_are_audited() {
+        let path = env::temp_dir().join(format!("admincli-history-{}.log", process::id()));
+        let _ = fs::remove_file(&path);
+        let mut session = Session {
+            audit: AuditLog::new(&path),
+            ..Session::default()
+        };
+        execute(&mut session, "Add Sally to Engineering");
+        execute(&mut session, "List all");
+        execute(&mut session, "DryRun on");
+        execute(&mut session, "Add Amir to Engineering");
+        execute(&mut session, "DryRun off");
+        execute(&mut session, "Undo");
+
+        let history = print(execute(&mut session, "History"));
+        let entries: Vec<&str> = history.trim_start().lines().collect();
+        assert_eq!(2, entries.len());
+        assert!(entries[0].ends_with("Z Add Sally to Engineering"));
+        assert!(entries[1].ends_with("Z Undo Add Sally to Engineering"));
+        assert_eq!(
+            1,
+            print(execute(&mut session, "History 1"))
+                .trim_start()
+                .lines()
+                .count()
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            "\nthe audit log is empty",
+            print(execute(&mut Session::default(), "History"))
+        );
+    }
+
+    #[test]
+    fn viewers_only_look() {
+        let mut session = Session {
+            viewer: true,
+            ..Session::default()
+        };
+        session.company.insert("Sally", "Engineering");
+        for input in [
+            "Add Amir to Engineering",
+            "rm! Sally from Engineering",
+            "Undo",
+            "Save",
+        ] {
+            assert!(matches!(execute(&mut session, input), Action::Fail(_)));
+        }
+        assert_eq!(
+            "\n[Engineering] (1)\n    Sally",
+            print(execute(&mut session, "List all"))
+        );
+        assert_eq!(
+            "\nEngineering has 1 employee(s)",
+            print(execute(&mut session, "Count Engineering"))
+        );
+    }
+
+    #[test]
+    fn remove_asks_first() {
+        let mut session = Session::default();
+        session.company.insert("Sally", "Engineering");
+
+        match execute(&mut session, "rm Sally from Engineering") {
+            Action::Confirm {
+                question, typed, ..
+            } => {
+                assert_eq!(
+                    "This removes Sally from Engineering. Are you sure?",
+                    question
+                );
+                assert_eq!("Remove! Sally from Engineering", typed);
             }
-            _ => println!("\nunknown command, use only the defined commands"),
+            _ => panic!("Remove should ask first"),
         }
+        assert!(session.company.contains("Sally", "Engineering"));
+
+        let msg = print(execute(&mut session, "rm! Sally from Engineering"));
+        assert_eq!(
+            "\n> Remove Sally from Engineering\nremoved Sally from Engineering",
+            msg
+        );
+        assert!(session.company.is_empty());
+    }
+
+    #[test]
+    fn dry_run_changes_nothing() {
+        let mut session = Session::default();
+        execute(&mut session, "Add Sally to Engineering");
+        execute(&mut session, "DryRun on");
+
+        assert_eq!(
+            "\n(dry run) would have removed Sally from Engineering",
+            print(execute(&mut session, "Remove Sally from Engineering"))
+        );
+        assert_eq!(
+            "\n(dry run) would have added Bob to Sales",
+            print(execute(&mut session, "Add Bob to Sales"))
+        );
+        assert_eq!(
+            "\n'Zed' not found in Engineering",
+            print(execute(&mut session, "Move Zed from Engineering to Sales"))
+        );
+        assert_eq!(
+            "\n(dry run) would undo 'Add Sally to Engineering'",
+            print(execute(&mut session, "Undo"))
+        );
+        assert_eq!(vec!["Engineering"], session.company.departments());
+
+        execute(&mut session, "dryrun OFF");
+        execute(&mut session, "Add Bob to Sales");
+        assert_eq!(vec!["Engineering", "Sales"], session.company.departments());
+    }
+
+    #[test]
+    fn set_language() {
+        let mut session = Session::default();
+        assert_eq!(
+            "\nidioma cambiado a español",
+            print(execute(&mut session, "Set language es"))
+        );
+        execute(&mut session, "Add Bob to Sales");
+        assert_eq!(
+            "\nno existe el departamento 'Sale' - ¿quisiste decir 'Sales'?",
+            print(execute(&mut session, "Count Sale"))
+        );
+        assert_eq!(
+            "\nno hablo 'fr', prueba 'en' o 'es'",
+            print(execute(&mut session, "Set language fr"))
+        );
+        execute(&mut session, "Set language en");
+        assert_eq!(
+            "\nSales has 1 employee(s)",
+            print(execute(&mut session, "Count Sales"))
+        );
     }
 }
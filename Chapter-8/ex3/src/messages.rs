@@ -0,0 +1,617 @@
+/*
+ * Every sentence AdminCLI says to a person, in every language it speaks. Code that wants to say
+ * something picks a `Msg` and asks for its `text` in the session's `Language`, so adding a language
+ * means adding one more arm to each match down here and nothing anywhere else.
+ *
+ * Command keywords stay English no matter what (`Add`, `List`, ...), only what we print changes.
+ * Error text that comes from outside AdminCLI (the OS, a bad date in a file) is passed along as is.
+ */
+
+use crate::{command::ParseError, history::Change, CompanyError};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    // understands plain codes ("es"), locale names ("es_ES.UTF-8"), and the languages' own names
+    pub fn from_code(code: &str) -> Option<Language> {
+        let code = code.trim().to_lowercase();
+        let code = code.split(['_', '.', '-', '@']).next().unwrap_or("");
+        match code {
+            "en" | "english" | "c" | "posix" => Some(Language::English),
+            "es" | "spanish" | "español" | "espanol" => Some(Language::Spanish),
+            _ => None,
+        }
+    }
+
+    // the language the environment asks for, checked the same way as every other program: LC_ALL,
+    // then LC_MESSAGES, then LANG. the first one that's set wins, and anything we don't speak
+    // means English
+    pub fn from_env() -> Language {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Language::from_code(&value))
+            .unwrap_or_default()
+    }
+}
+
+pub enum Msg<'a> {
+    Welcome,
+    Goodbye,
+    Help,
+    Prompt {
+        dry_run: bool,
+    },
+    // what goes after a yes/no question
+    YesNo,
+    NeverMind,
+    InputFailed(&'a dyn Display),
+    // batch mode's way of pointing at the line that went wrong
+    Line {
+        number: usize,
+        text: &'a str,
+    },
+    LineUnreadable(&'a dyn Display),
+    SkipQuestion(&'a str),
+
+    Company(&'a CompanyError),
+    Parse(&'a ParseError),
+    DidYouMean {
+        error: &'a str,
+        guess: &'a str,
+    },
+
+    Done(&'a Change),
+    WouldDo(&'a Change),
+    NoDuplicates(&'a str),
+    ConfirmRemove {
+        name: &'a str,
+        dept: &'a str,
+    },
+    ConfirmDedup(usize),
+    ConfirmDuplicate(&'a CompanyError),
+    DryRunOn,
+    DryRunOff,
+    Undid(&'a Change),
+    Redid(&'a Change),
+    WouldUndo(&'a Change),
+    WouldRedo(&'a Change),
+    CouldntUndo(&'a CompanyError),
+    CouldntRedo(&'a CompanyError),
+    NothingToUndo,
+    NothingToRedo,
+    NothingLogged,
+    CouldntLog(&'a dyn Display),
+    CouldntReadLog(&'a dyn Display),
+
+    Headcount {
+        dept: &'a str,
+        count: usize,
+    },
+    NobodyMatching(&'a str),
+    FoundIn(&'a str),
+    // column headings
+    Department,
+    Employees,
+    Name,
+    Title,
+    Start,
+    Total,
+    Largest,
+    Smallest,
+
+    Exported(&'a str),
+    CouldntExport {
+        path: &'a str,
+        error: &'a dyn Display,
+    },
+    Saved(&'a str),
+    CouldntSave {
+        path: &'a str,
+        error: &'a dyn Display,
+    },
+    Loaded(&'a str),
+    WouldLoad(&'a str),
+    CouldntLoad {
+        path: &'a str,
+        error: &'a dyn Display,
+    },
+    // the company file was corrupt, so it was moved to `moved_to` and AdminCLI starts empty
+    MovedAside {
+        path: &'a str,
+        error: &'a dyn Display,
+        moved_to: &'a str,
+    },
+    CouldntMoveAside {
+        path: &'a str,
+        error: &'a dyn Display,
+        moving: &'a dyn Display,
+    },
+    CouldntOpen {
+        path: &'a str,
+        error: &'a dyn Display,
+    },
+    ReadOnly,
+    // a remote client asked for a file somewhere other than the server's directory
+    NotAFileName(&'a str),
+    // the arguments didn't make sense
+    Usage,
+    Serving(&'a str),
+    CouldntServe {
+        address: &'a str,
+        error: &'a dyn Display,
+    },
+    LanguageSet,
+}
+
+impl Msg<'_> {
+    pub fn text(&self, language: Language) -> String {
+        match language {
+            Language::English => self.english(),
+            Language::Spanish => self.spanish(),
+        }
+    }
+
+    fn english(&self) -> String {
+        let lang = Language::English;
+        match self {
+            Msg::Welcome => {
+                "Welcome to AdminCLI. Do administrative things for a totally real company!".into()
+            }
+            Msg::Goodbye => "AdminCLI stopped ... Have a nice day".into(),
+            Msg::Help => ENGLISH_HELP.into(),
+            Msg::Prompt { dry_run: false } => "Enter command: ".into(),
+            Msg::Prompt { dry_run: true } => "Enter command (dry run): ".into(),
+            Msg::YesNo => "(y/n)".into(),
+            Msg::NeverMind => "never mind then".into(),
+            Msg::InputFailed(e) => format!("error: unable to read your input: {}", e),
+            Msg::Line { number, text } => format!("line {}: {}", number, text),
+            Msg::LineUnreadable(e) => format!("couldn't read it: {}", e),
+            Msg::SkipQuestion(command) => format!("(use '{}' to skip this question)", command),
+
+            Msg::Company(CompanyError::DepartmentNotFound(dept)) => {
+                format!("'{}' department not found", dept)
+            }
+            Msg::Company(CompanyError::EmployeeNotFound { name, dept }) => {
+                format!("'{}' not found in {}", name, dept)
+            }
+            Msg::Company(CompanyError::Duplicate { name, dept }) => {
+                format!("{} already works in {}", name, dept)
+            }
+            Msg::Company(CompanyError::Full { dept, capacity }) => {
+                format!("{} is full, it has room for {} people", dept, capacity)
+            }
+            Msg::Parse(e) => match e {
+                ParseError::Empty => "enter one of the commands below".into(),
+                ParseError::Unknown {
+                    word,
+                    guess: Some(guess),
+                } => format!("unknown command '{}' - did you mean '{}'?", word, guess),
+                ParseError::Unknown { guess: None, .. } => {
+                    "unknown command, use only the defined commands".into()
+                }
+                ParseError::Usage { command, usage } => {
+                    format!("that's not quite how '{}' works, try '{}'", command, usage)
+                }
+                ParseError::ExtraWords(words) => format!("don't know what to do with '{}'", words),
+                ParseError::BadSort(words) => format!("don't know how to sort by '{}'", words),
+                ParseError::BadExportFormat(format) => {
+                    format!("can't export as '{}', use tree or table", format)
+                }
+                ParseError::BadDryRun => "use 'DryRun on' or 'DryRun off'".into(),
+                ParseError::BadCount(count) => {
+                    format!("'{}' isn't a number of entries", count)
+                }
+                ParseError::BadCapacity(value) => {
+                    format!("'{}' isn't a capacity, use a number or 'none'", value)
+                }
+                ParseError::UnknownLanguage(code) => {
+                    format!("don't speak '{}', try 'en' or 'es'", code)
+                }
+                ParseError::Invalid(msg) => msg.clone(),
+            },
+            Msg::DidYouMean { error, guess } => format!("{} - did you mean '{}'?", error, guess),
+
+            Msg::Done(change) => lines(change, |change| match change {
+                Change::Add { employee, dept, .. } => {
+                    format!("added {} to {}", employee.name, dept)
+                }
+                Change::Remove { employee, dept } => {
+                    format!("removed {} from {}", employee.name, dept)
+                }
+                Change::Move { name, from, to } => {
+                    format!("moved {} from {} to {}", name, from, to)
+                }
+                Change::SetCapacity {
+                    dept,
+                    to: Some(capacity),
+                    ..
+                } => format!("set the capacity of {} to {}", dept, capacity),
+                Change::SetCapacity { dept, to: None, .. } => {
+                    format!("lifted the capacity limit on {}", dept)
+                }
+                Change::Batch(_) => unreachable!(),
+            }),
+            Msg::WouldDo(change) => lines(change, |change| {
+                format!("(dry run) would have {}", Msg::Done(change).text(lang))
+            }),
+            Msg::NoDuplicates(dept) => format!("no duplicates in {}", dept),
+            Msg::ConfirmRemove { name, dept } => {
+                format!("This removes {} from {}. Are you sure?", name, dept)
+            }
+            Msg::ConfirmDedup(count) => format!(
+                "This removes {} duplicate employee(s). Are you sure?",
+                count
+            ),
+            Msg::ConfirmDuplicate(e) => {
+                format!("{}. Add another one anyway?", Msg::Company(e).text(lang))
+            }
+            Msg::DryRunOn => "dry run is on, nothing will change until 'DryRun off'".into(),
+            Msg::DryRunOff => "dry run is off".into(),
+            Msg::Undid(change) => format!("undid '{}'", change),
+            Msg::Redid(change) => format!("redid '{}'", change),
+            Msg::WouldUndo(change) => format!("(dry run) would undo '{}'", change),
+            Msg::WouldRedo(change) => format!("(dry run) would redo '{}'", change),
+            Msg::CouldntUndo(e) => format!("couldn't undo: {}", Msg::Company(e).text(lang)),
+            Msg::CouldntRedo(e) => format!("couldn't redo: {}", Msg::Company(e).text(lang)),
+            Msg::NothingToUndo => "nothing to undo".into(),
+            Msg::NothingLogged => "the audit log is empty".into(),
+            Msg::CouldntLog(error) => format!("couldn't write to the audit log: {}", error),
+            Msg::CouldntReadLog(error) => format!("couldn't read the audit log: {}", error),
+            Msg::NothingToRedo => "nothing to redo".into(),
+
+            Msg::Headcount { dept, count } => format!("{} has {} employee(s)", dept, count),
+            Msg::NobodyMatching(query) => format!("nobody matching '{}' works here", query),
+            Msg::FoundIn(query) => format!("'{}' found in:", query),
+            Msg::Department => "Department".into(),
+            Msg::Employees => "Employees".into(),
+            Msg::Name => "Name".into(),
+            Msg::Title => "Title".into(),
+            Msg::Start => "Start".into(),
+            Msg::Total => "Total".into(),
+            Msg::Largest => "Largest".into(),
+            Msg::Smallest => "Smallest".into(),
+
+            Msg::Exported(path) => format!("exported company to {}", path),
+            Msg::CouldntExport { path, error } => format!("couldn't export to {}: {}", path, error),
+            Msg::Saved(path) => format!("saved company to {}", path),
+            Msg::CouldntSave { path, error } => format!("couldn't save to {}: {}", path, error),
+            Msg::Loaded(path) => format!("loaded company from {}", path),
+            Msg::WouldLoad(path) => format!("(dry run) would load company from {}", path),
+            Msg::CouldntLoad { path, error } => format!("couldn't load {}: {}", path, error),
+            Msg::MovedAside {
+                path,
+                error,
+                moved_to,
+            } => format!(
+                "couldn't load {} ({}), so starting with an empty company. the old file is {}",
+                path, error, moved_to
+            ),
+            Msg::CouldntMoveAside {
+                path,
+                error,
+                moving,
+            } => format!(
+                "couldn't load {} ({}), and couldn't move it aside either: {}",
+                path, error, moving
+            ),
+            Msg::CouldntOpen { path, error } => format!("couldn't open {}: {}", path, error),
+            Msg::NotAFileName(path) => format!(
+                "'{}' isn't a plain file name. over the network, files stay next to the server's company.txt",
+                path
+            ),
+            Msg::ReadOnly => {
+                "viewers can only look: List, Find, Count, Stats, and History work, changes and files don't"
+                    .into()
+            }
+            Msg::Usage => "usage: ex3 [--viewer] [script | --serve [address]]".into(),
+            Msg::Serving(address) => format!("AdminCLI is listening on {}", address),
+            Msg::CouldntServe { address, error } => {
+                format!("couldn't listen on {}: {}", address, error)
+            }
+            Msg::LanguageSet => "language set to English".into(),
+        }
+    }
+
+    fn spanish(&self) -> String {
+        let lang = Language::Spanish;
+        match self {
+            Msg::Welcome => {
+                "Bienvenido a AdminCLI. ¡Haz tareas administrativas para una empresa totalmente real!"
+                    .into()
+            }
+            Msg::Goodbye => "AdminCLI se ha detenido ... Que tengas un buen día".into(),
+            Msg::Help => SPANISH_HELP.into(),
+            Msg::Prompt { dry_run: false } => "Escribe un comando: ".into(),
+            Msg::Prompt { dry_run: true } => "Escribe un comando (simulación): ".into(),
+            Msg::YesNo => "(s/n)".into(),
+            Msg::NeverMind => "de acuerdo, no se hace nada".into(),
+            Msg::InputFailed(e) => format!("error: no se pudo leer lo que escribiste: {}", e),
+            Msg::Line { number, text } => format!("línea {}: {}", number, text),
+            Msg::LineUnreadable(e) => format!("no se pudo leer: {}", e),
+            Msg::SkipQuestion(command) => format!("(usa '{}' para no preguntar)", command),
+
+            Msg::Company(CompanyError::DepartmentNotFound(dept)) => {
+                format!("no existe el departamento '{}'", dept)
+            }
+            Msg::Company(CompanyError::EmployeeNotFound { name, dept }) => {
+                format!("'{}' no está en {}", name, dept)
+            }
+            Msg::Company(CompanyError::Duplicate { name, dept }) => {
+                format!("{} ya trabaja en {}", name, dept)
+            }
+            Msg::Company(CompanyError::Full { dept, capacity }) => {
+                format!("{} está lleno, tiene sitio para {} personas", dept, capacity)
+            }
+            Msg::Parse(e) => match e {
+                ParseError::Empty => "escribe uno de los comandos de abajo".into(),
+                ParseError::Unknown {
+                    word,
+                    guess: Some(guess),
+                } => format!("comando desconocido '{}' - ¿quisiste decir '{}'?", word, guess),
+                ParseError::Unknown { guess: None, .. } => {
+                    "comando desconocido, usa solo los comandos de la lista".into()
+                }
+                ParseError::Usage { command, usage } => {
+                    format!("'{}' no se usa así, prueba '{}'", command, usage)
+                }
+                ParseError::ExtraWords(words) => format!("no sé qué hacer con '{}'", words),
+                ParseError::BadSort(words) => format!("no sé ordenar por '{}'", words),
+                ParseError::BadExportFormat(format) => {
+                    format!("no se puede exportar como '{}', usa tree o table", format)
+                }
+                ParseError::BadDryRun => "usa 'DryRun on' o 'DryRun off'".into(),
+                ParseError::BadCount(count) => {
+                    format!("'{}' no es un número de entradas", count)
+                }
+                ParseError::BadCapacity(value) => {
+                    format!("'{}' no es una capacidad, usa un número o 'none'", value)
+                }
+                ParseError::UnknownLanguage(code) => {
+                    format!("no hablo '{}', prueba 'en' o 'es'", code)
+                }
+                ParseError::Invalid(msg) => msg.clone(),
+            },
+            Msg::DidYouMean { error, guess } => format!("{} - ¿quisiste decir '{}'?", error, guess),
+
+            Msg::Done(change) => lines(change, |change| match change {
+                Change::Add { employee, dept, .. } => {
+                    format!("{} añadido a {}", employee.name, dept)
+                }
+                Change::Remove { employee, dept } => {
+                    format!("{} quitado de {}", employee.name, dept)
+                }
+                Change::Move { name, from, to } => {
+                    format!("{} movido de {} a {}", name, from, to)
+                }
+                Change::SetCapacity {
+                    dept,
+                    to: Some(capacity),
+                    ..
+                } => format!("capacidad de {} fijada en {}", dept, capacity),
+                Change::SetCapacity { dept, to: None, .. } => {
+                    format!("{} ya no tiene límite de capacidad", dept)
+                }
+                Change::Batch(_) => unreachable!(),
+            }),
+            Msg::WouldDo(change) => lines(change, |change| match change {
+                Change::Add { employee, dept, .. } => {
+                    format!("(simulación) se añadiría {} a {}", employee.name, dept)
+                }
+                Change::Remove { employee, dept } => {
+                    format!("(simulación) se quitaría {} de {}", employee.name, dept)
+                }
+                Change::Move { name, from, to } => {
+                    format!("(simulación) se movería {} de {} a {}", name, from, to)
+                }
+                Change::SetCapacity {
+                    dept,
+                    to: Some(capacity),
+                    ..
+                } => format!("(simulación) se fijaría la capacidad de {} en {}", dept, capacity),
+                Change::SetCapacity { dept, to: None, .. } => {
+                    format!("(simulación) se quitaría el límite de capacidad de {}", dept)
+                }
+                Change::Batch(_) => unreachable!(),
+            }),
+            Msg::NoDuplicates(dept) => format!("no hay duplicados en {}", dept),
+            Msg::ConfirmRemove { name, dept } => {
+                format!("Esto quita a {} de {}. ¿Seguro?", name, dept)
+            }
+            Msg::ConfirmDedup(count) => {
+                format!("Esto quita {} empleado(s) duplicado(s). ¿Seguro?", count)
+            }
+            Msg::ConfirmDuplicate(e) => format!(
+                "{}. ¿Añadir otro de todos modos?",
+                Msg::Company(e).text(lang)
+            ),
+            Msg::DryRunOn => "simulación activada, nada cambiará hasta 'DryRun off'".into(),
+            Msg::DryRunOff => "simulación desactivada".into(),
+            Msg::Undid(change) => format!("deshecho '{}'", change),
+            Msg::Redid(change) => format!("rehecho '{}'", change),
+            Msg::WouldUndo(change) => format!("(simulación) se desharía '{}'", change),
+            Msg::WouldRedo(change) => format!("(simulación) se reharía '{}'", change),
+            Msg::CouldntUndo(e) => format!("no se pudo deshacer: {}", Msg::Company(e).text(lang)),
+            Msg::CouldntRedo(e) => format!("no se pudo rehacer: {}", Msg::Company(e).text(lang)),
+            Msg::NothingToUndo => "no hay nada que deshacer".into(),
+            Msg::NothingLogged => "el registro de auditoría está vacío".into(),
+            Msg::CouldntLog(error) => {
+                format!("no se pudo escribir en el registro de auditoría: {}", error)
+            }
+            Msg::CouldntReadLog(error) => {
+                format!("no se pudo leer el registro de auditoría: {}", error)
+            }
+            Msg::NothingToRedo => "no hay nada que rehacer".into(),
+
+            Msg::Headcount { dept, count } => format!("{} tiene {} empleado(s)", dept, count),
+            Msg::NobodyMatching(query) => format!("nadie que coincida con '{}' trabaja aquí", query),
+            Msg::FoundIn(query) => format!("'{}' está en:", query),
+            Msg::Department => "Departamento".into(),
+            Msg::Employees => "Empleados".into(),
+            Msg::Name => "Nombre".into(),
+            Msg::Title => "Puesto".into(),
+            Msg::Start => "Inicio".into(),
+            Msg::Total => "Total".into(),
+            Msg::Largest => "Mayor".into(),
+            Msg::Smallest => "Menor".into(),
+
+            Msg::Exported(path) => format!("empresa exportada a {}", path),
+            Msg::CouldntExport { path, error } => {
+                format!("no se pudo exportar a {}: {}", path, error)
+            }
+            Msg::Saved(path) => format!("empresa guardada en {}", path),
+            Msg::CouldntSave { path, error } => format!("no se pudo guardar en {}: {}", path, error),
+            Msg::Loaded(path) => format!("empresa cargada desde {}", path),
+            Msg::WouldLoad(path) => format!("(simulación) se cargaría la empresa desde {}", path),
+            Msg::CouldntLoad { path, error } => format!("no se pudo cargar {}: {}", path, error),
+            Msg::MovedAside {
+                path,
+                error,
+                moved_to,
+            } => format!(
+                "no se pudo cargar {} ({}), así que se empieza con una empresa vacía. el archivo antiguo es {}",
+                path, error, moved_to
+            ),
+            Msg::CouldntMoveAside {
+                path,
+                error,
+                moving,
+            } => format!(
+                "no se pudo cargar {} ({}), y tampoco se pudo apartar: {}",
+                path, error, moving
+            ),
+            Msg::CouldntOpen { path, error } => format!("no se pudo abrir {}: {}", path, error),
+            Msg::NotAFileName(path) => format!(
+                "'{}' no es un simple nombre de archivo. por la red, los archivos se quedan junto al company.txt del servidor",
+                path
+            ),
+            Msg::ReadOnly => {
+                "en modo lectura solo se puede mirar: List, Find, Count, Stats e History funcionan, los cambios y los archivos no"
+                    .into()
+            }
+            Msg::Usage => "uso: ex3 [--viewer] [script | --serve [dirección]]".into(),
+            Msg::Serving(address) => format!("AdminCLI escucha en {}", address),
+            Msg::CouldntServe { address, error } => {
+                format!("no se pudo escuchar en {}: {}", address, error)
+            }
+            Msg::LanguageSet => "idioma cambiado a español".into(),
+        }
+    }
+}
+
+// one line per change, with a batch spread out over as many lines as it has changes
+fn lines(change: &Change, line: impl Fn(&Change) -> String + Copy) -> String {
+    match change {
+        Change::Batch(changes) => changes
+            .iter()
+            .map(|change| lines(change, line))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        change => line(change),
+    }
+}
+
+const ENGLISH_HELP: &str = r#"
+
+Available commands (keywords work in any case, and "quotes" keep multi-word names together):
+    - 'Add <Name> to <Department> [as <Title>] [since <YYYY-MM-DD>] [--force]' to do exactly that
+      (--force allows a second person with the same name)
+      Departments can be nested: 'Add Amir to Sales/EMEA' puts Amir in the EMEA team inside Sales
+    - 'Remove <Name> from <Department>' to take someone out of a department
+    - 'Move <Name> from <Department> to <Department>' to transfer someone
+    - 'List <Department> [by name|size] [asc|desc]' to list every employee within this department and the ones under it
+    - 'List all [by name|size] [asc|desc]' to list every employee in the company in a tree-like structure
+    - 'Find <Name> [--partial]' to see which departments someone is in (--partial matches any part of a name)
+    - 'Count <Department>' to see how many people work in a department, sub-departments included
+    - 'Stats' for a headcount table of the whole company
+    - 'Dedup <Department>' to drop repeated names from a department
+    - 'SetCapacity <Department> <number|none>' to cap how many people a department can have
+    - 'Undo' to take back the last Add, Remove, Move, Dedup, or SetCapacity
+    - 'Redo' to put back what Undo took away
+    - 'History [n]' to see the last n changes (10 by default) from the audit log in audit.log
+    - 'DryRun on|off' to have changes only say what they would do, without doing it
+    - 'Export tree|table <path>' to write the company to a file as an indented tree or a table
+    - 'Save [path]' to write the company to a file (company.txt by default)
+    - 'Load [path]' to replace the company with what's in a file
+    - 'Set language en|es' to switch AdminCLI to English or Spanish
+    - 'Exit' to save to company.txt and stop AdminCLI
+
+Remove and Dedup ask before doing anything. Put a ! after the command ('Remove! Sally from Sales') to skip that.
+Shortcuts: a = Add, rm = Remove, mv = Move, l/ls = List, f = Find, u = Undo, r = Redo, q/quit = Exit
+"#;
+
+const SPANISH_HELP: &str = r#"
+
+Comandos disponibles (da igual mayúsculas o minúsculas, y las "comillas" mantienen juntos los nombres de varias palabras):
+    - 'Add <Nombre> to <Departamento> [as <Puesto>] [since <AAAA-MM-DD>] [--force]' para añadir a alguien
+      (--force permite una segunda persona con el mismo nombre)
+      Los departamentos se pueden anidar: 'Add Amir to Sales/EMEA' pone a Amir en el equipo EMEA dentro de Sales
+    - 'Remove <Nombre> from <Departamento>' para quitar a alguien de un departamento
+    - 'Move <Nombre> from <Departamento> to <Departamento>' para trasladar a alguien
+    - 'List <Departamento> [by name|size] [asc|desc]' para ver a todos en este departamento y en los que tiene dentro
+    - 'List all [by name|size] [asc|desc]' para ver a toda la empresa en forma de árbol
+    - 'Find <Nombre> [--partial]' para ver en qué departamentos está alguien (--partial busca cualquier parte del nombre)
+    - 'Count <Departamento>' para ver cuánta gente trabaja en un departamento, subdepartamentos incluidos
+    - 'Stats' para una tabla con la plantilla de toda la empresa
+    - 'Dedup <Departamento>' para quitar nombres repetidos de un departamento
+    - 'SetCapacity <Departamento> <número|none>' para limitar cuánta gente puede tener un departamento
+    - 'Undo' para deshacer el último Add, Remove, Move, Dedup o SetCapacity
+    - 'Redo' para rehacer lo que Undo deshizo
+    - 'History [n]' para ver los últimos n cambios (10 si no se indica) del registro de auditoría en audit.log
+    - 'DryRun on|off' para que los cambios solo digan lo que harían, sin hacerlo
+    - 'Export tree|table <ruta>' para escribir la empresa en un archivo como árbol o como tabla
+    - 'Save [ruta]' para guardar la empresa en un archivo (company.txt si no se indica)
+    - 'Load [ruta]' para reemplazar la empresa por lo que haya en un archivo
+    - 'Set language en|es' para cambiar AdminCLI a inglés o español
+    - 'Exit' para guardar en company.txt y salir de AdminCLI
+
+Remove y Dedup preguntan antes de hacer nada. Pon un ! después del comando ('Remove! Sally from Sales') para no preguntar.
+Atajos: a = Add, rm = Remove, mv = Move, l/ls = List, f = Find, u = Undo, r = Redo, q/quit = Exit
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_codes() {
+        assert_eq!(Some(Language::Spanish), Language::from_code("es_ES.UTF-8"));
+        assert_eq!(Some(Language::Spanish), Language::from_code("ES"));
+        assert_eq!(Some(Language::English), Language::from_code("C"));
+        assert_eq!(Some(Language::English), Language::from_code("en-GB"));
+        assert_eq!(None, Language::from_code("fr_FR"));
+    }
+
+    #[test]
+    fn same_message_in_both_languages() {
+        let e = CompanyError::DepartmentNotFound("Legal".to_string());
+        assert_eq!(
+            "'Legal' department not found",
+            Msg::Company(&e).text(Language::English)
+        );
+        assert_eq!(
+            "no existe el departamento 'Legal'",
+            Msg::Company(&e).text(Language::Spanish)
+        );
+    }
+
+    #[test]
+    fn batches_get_a_line_each() {
+        let remove = |name: &str| Change::Remove {
+            employee: crate::Employee::new(name),
+            dept: "Sales".to_string(),
+        };
+        let batch = Change::Batch(vec![remove("Bob"), remove("Cleo")]);
+        assert_eq!(
+            "(dry run) would have removed Bob from Sales\n(dry run) would have removed Cleo from Sales",
+            Msg::WouldDo(&batch).text(Language::English)
+        );
+    }
+}
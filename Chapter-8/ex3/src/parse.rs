@@ -0,0 +1,265 @@
+/*
+ * Splitting a command line into words. Words are separated by whitespace, except inside double
+ * quotes, so `Add "Mary Ann" to "Human Resources"` is four words. Unquoted words that happen to be
+ * command keywords are lowercased so `ADD`, `Add`, and `add` all look the same to the matcher.
+ * Quoted words are never touched, which is how you add someone actually named "List". Inside
+ * quotes, `\"` is a quote that doesn't end the word and `\\` is a backslash.
+ *
+ * The first word can also be one of the shortcuts in ALIASES, which gets swapped for the keyword it
+ * stands for before anything else looks at it.
+ *
+ * A `!` right after the command word (`Remove! Sally from Sales`) means "yes, I'm sure". It's taken
+ * off by `strip_bang` before tokenizing.
+ */
+
+pub const KEYWORDS: [&str; 24] = [
+    "add",
+    "to",
+    "as",
+    "since",
+    "remove",
+    "from",
+    "move",
+    "dedup",
+    "setcapacity",
+    "list",
+    "all",
+    "find",
+    "count",
+    "stats",
+    "undo",
+    "redo",
+    "history",
+    "export",
+    "save",
+    "load",
+    "dryrun",
+    "set",
+    "language",
+    "exit",
+];
+
+// the keywords that can start a command
+pub const COMMANDS: [&str; 18] = [
+    "add",
+    "remove",
+    "move",
+    "dedup",
+    "setcapacity",
+    "list",
+    "find",
+    "count",
+    "stats",
+    "undo",
+    "redo",
+    "history",
+    "export",
+    "save",
+    "load",
+    "dryrun",
+    "set",
+    "exit",
+];
+
+// shortcut -> the keyword it stands for
+const ALIASES: [(&str, &str); 10] = [
+    ("a", "add"),
+    ("rm", "remove"),
+    ("mv", "move"),
+    ("l", "list"),
+    ("ls", "list"),
+    ("f", "find"),
+    ("u", "undo"),
+    ("r", "redo"),
+    ("q", "exit"),
+    ("quit", "exit"),
+];
+
+pub fn alias(word: &str) -> Option<&'static str> {
+    let word = word.to_lowercase();
+    ALIASES
+        .iter()
+        .find(|(short, _)| *short == word)
+        .map(|(_, keyword)| *keyword)
+}
+
+// `input` with a leading shortcut spelled out ("a Sally to Sales" -> "Add Sally to Sales"), or None
+// when the command didn't start with one
+pub fn expand_alias(input: &str) -> Option<String> {
+    let input = input.trim();
+    let first = input.split_whitespace().next()?;
+    let keyword = alias(first)?;
+    let mut full = keyword[..1].to_uppercase() + &keyword[1..];
+    full.push_str(&input[first.len()..]);
+    Some(full)
+}
+
+// `input` without the `!` on its command word, and whether there was one
+pub fn strip_bang(input: &str) -> (String, bool) {
+    let input = input.trim();
+    let first = input.split_whitespace().next().unwrap_or("");
+    match first.strip_suffix('!') {
+        Some(command) if !command.is_empty() => {
+            (format!("{}{}", command, &input[first.len()..]), true)
+        }
+        _ => (input.to_string(), false),
+    }
+}
+
+// `input` with a `!` on its command word, so it runs without asking
+pub fn add_bang(input: &str) -> String {
+    let input = input.trim();
+    let first = input.split_whitespace().next().unwrap_or("");
+    format!("{}!{}", first, &input[first.len()..])
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut chars = input.trim().chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut word = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') if matches!(chars.peek(), Some('"' | '\\')) => {
+                        word.extend(chars.next());
+                    }
+                    Some(c) => word.push(c),
+                    None => return Err(format!("missing closing quote after \"{}", word)),
+                }
+            }
+            words.push(word);
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            let lower = word.to_lowercase();
+            if KEYWORDS.contains(&lower.as_str()) {
+                word = lower;
+            } else if words.is_empty() {
+                if let Some(keyword) = alias(&word) {
+                    word = keyword.to_string();
+                }
+            }
+            words.push(word);
+        }
+    }
+    Ok(words)
+}
+
+// the way to write `word` so tokenize reads it back as one word, exactly as it is. anything that
+// would be split up, lowercased, or swapped for a keyword gets quoted
+pub fn quote(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let bare = !word.is_empty()
+        && !word.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\' || c == '!')
+        && !KEYWORDS.contains(&lower.as_str())
+        && alias(word).is_none();
+    if bare {
+        return word.to_string();
+    }
+    let mut quoted = String::from('"');
+    for c in word.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_are_case_insensitive() {
+        assert_eq!(
+            vec!["add", "sally", "to", "engineering"],
+            tokenize("ADD sally To engineering").unwrap()
+        );
+    }
+
+    #[test]
+    fn quoted_words() {
+        assert_eq!(
+            vec!["add", "Mary Ann", "to", "Human Resources"],
+            tokenize(r#"Add "Mary Ann" to   "Human Resources"  "#).unwrap()
+        );
+        assert_eq!(
+            vec!["add", "List", "to", "x"],
+            tokenize(r#"add "List" to x"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn unclosed_quote() {
+        assert!(tokenize(r#"Add "Mary Ann to Sales"#).is_err());
+    }
+
+    #[test]
+    fn aliases_only_apply_to_the_command() {
+        assert_eq!(
+            vec!["remove", "a", "from", "Sales"],
+            tokenize("rm a from Sales").unwrap()
+        );
+        assert_eq!(vec!["exit"], tokenize("Q").unwrap());
+        assert_eq!(vec!["l"], tokenize("\"l\"").unwrap());
+    }
+
+    #[test]
+    fn expand_alias_spells_out_the_command() {
+        assert_eq!(
+            Some("Add Sally to Sales".to_string()),
+            expand_alias("a Sally to Sales")
+        );
+        assert_eq!(None, expand_alias("Add Sally to Sales"));
+    }
+
+    #[test]
+    fn bang_on_the_command_word() {
+        assert_eq!(
+            ("Remove Sally from Sales".to_string(), true),
+            strip_bang("Remove! Sally from Sales")
+        );
+        assert_eq!(
+            ("rm Bob! from Sales".to_string(), false),
+            strip_bang("rm Bob! from Sales")
+        );
+        assert_eq!("Dedup! Sales", add_bang("Dedup Sales"));
+    }
+
+    #[test]
+    fn quote_round_trips() {
+        assert_eq!("Sally", quote("Sally"));
+        assert_eq!(r#""List""#, quote("List"));
+        assert_eq!(r#""say \"hi\"""#, quote(r#"say "hi""#));
+        for word in [
+            "Mary Ann",
+            "",
+            "List",
+            "to",
+            "rm",
+            "Q",
+            r#"say "hi""#,
+            r#"back\slash"#,
+            r#"ends with \"#,
+            "Sally!",
+        ] {
+            assert_eq!(vec![word], tokenize(&quote(word)).unwrap());
+            let command = format!("Add {} to {}", quote(word), quote(word));
+            assert_eq!(vec!["add", word, "to", word], tokenize(&command).unwrap());
+        }
+    }
+}
@@ -0,0 +1,154 @@
+/*
+ * Turning the company into text for people to read. `List` prints these and `Export` writes the
+ * very same thing to a file, so what you see is what you get.
+ */
+
+use ex3::{
+    messages::{Language, Msg},
+    Company, Department, Employee, Sort,
+};
+
+// departments one after another, as an indented tree
+pub fn tree(depts: &[&Department], sort: Sort) -> String {
+    depts.iter().map(|dept| department(dept, sort, 0)).collect()
+}
+
+// a department and everything under it, each level indented a bit further and headed by its total
+// headcount. one employee per line. once anyone in a department has a title or start date, those
+// show up as extra columns, with '-' filling in whatever we don't know
+pub fn department(dept: &Department, sort: Sort, depth: usize) -> String {
+    let indent = "    ".repeat(depth);
+    // the department asked for gets its full path, the ones under it just their own name
+    let heading = if depth == 0 { dept.path() } else { dept.name() };
+    // a department with a capacity shows how full it is, e.g. (7/10)
+    let headcount = match dept.capacity() {
+        Some(capacity) => format!("{}/{}", dept.headcount(), capacity),
+        None => dept.headcount().to_string(),
+    };
+    let mut out = format!("\n{}[{}] ({})", indent, heading, headcount);
+    let employees = dept.sorted_employees(sort.descending);
+    let detailed = employees
+        .iter()
+        .any(|e| e.title.is_some() || e.start.is_some());
+    if !detailed {
+        for employee in &employees {
+            out.push_str(&format!("\n{}    {}", indent, employee.name));
+        }
+    } else {
+        out.push_str(&employee_columns(&employees, &indent));
+    }
+    for sub in dept.sorted_subdepartments(sort) {
+        out.push_str(&department(sub, sort, depth + 1));
+    }
+    out
+}
+
+fn employee_columns(employees: &[&Employee], indent: &str) -> String {
+    let mut out = String::new();
+    let name_width = employees
+        .iter()
+        .map(|e| e.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let title_width = employees
+        .iter()
+        .map(|e| e.title.as_deref().unwrap_or("-").chars().count())
+        .max()
+        .unwrap_or(0);
+    for employee in employees {
+        out.push_str(&format!(
+            "\n{}    {:<name_width$}  {:<title_width$}  {}",
+            indent,
+            employee.name,
+            employee.title.as_deref().unwrap_or("-"),
+            start(employee)
+        ));
+    }
+    out
+}
+
+// one row per employee under a Department/Name/Title/Start header, every column padded to line up
+pub fn table(company: &Company, language: Language) -> String {
+    let header = [Msg::Department, Msg::Name, Msg::Title, Msg::Start].map(|m| m.text(language));
+    let rows: Vec<[String; 4]> = company
+        .list_all()
+        .into_iter()
+        .flat_map(|(dept, employees)| {
+            employees.iter().map(move |e| {
+                [
+                    dept.to_string(),
+                    e.name.clone(),
+                    e.title.clone().unwrap_or_else(|| "-".to_string()),
+                    start(e),
+                ]
+            })
+        })
+        .collect();
+
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: [&str; 4]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell))
+            .collect();
+        // no trailing spaces after the last column
+        format!("\n{}", padded.join(" | ").trim_end())
+    };
+
+    let mut out = line(header.each_ref().map(|h| h.as_str()));
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    out.push_str(&format!("\n{}", rule.join("-+-")));
+    for row in &rows {
+        out.push_str(&line([&row[0], &row[1], &row[2], &row[3]]));
+    }
+    out
+}
+
+fn start(employee: &Employee) -> String {
+    match employee.start {
+        Some(start) => start.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_columns_line_up() {
+        let mut company = Company::new();
+        company.insert(
+            Employee::new("Sally").with_title("Senior Engineer"),
+            "Engineering",
+        );
+        company.insert("Bob", "Sales/EMEA");
+
+        let expected = "
+Department  | Name  | Title           | Start
+------------+-------+-----------------+------
+Engineering | Sally | Senior Engineer | -
+Sales/EMEA  | Bob   | -               | -";
+        assert_eq!(expected, table(&company, Language::English));
+    }
+
+    #[test]
+    fn tree_of_every_department() {
+        let mut company = Company::new();
+        company.insert("Bob", "Sales");
+        company.insert("Amir", "Sales/EMEA");
+        company.insert("Sally", "Engineering");
+
+        let sort = Sort::default();
+        assert_eq!(
+            "\n[Engineering] (1)\n    Sally\n[Sales] (2)\n    Bob\n    [EMEA] (1)\n        Amir",
+            tree(&company.top_level(sort), sort)
+        );
+    }
+}
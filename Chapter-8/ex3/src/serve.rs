@@ -0,0 +1,151 @@
+/*
+ * `ex3 --serve <address>`: AdminCLI over TCP, one command per line, for as many clients at once as
+ * care to connect. Every connection gets a session of its own (its own undo history, dry run
+ * setting, and language) but they all work on the same company, and the company is saved whenever
+ * somebody disconnects.
+ *
+ * Since somebody else may have changed the company in between, Undo can fail where it wouldn't
+ * on your own; it says so and leaves the company alone when it does.
+ *
+ * Save, Load and Export only take a plain file name over the connection, never a path, so clients
+ * can't get at anything outside the directory the server was started in.
+ */
+
+use crate::{answer, execute, run_command, Action, Session};
+use ex3::{
+    audit::{self, AuditLog},
+    messages::{Language, Msg},
+    storage, SharedCompany,
+};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    mem,
+    net::{TcpListener, TcpStream},
+    path::Path,
+    thread,
+};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+// takes connections until the process is killed. only fails if it can't listen on `address` at all.
+// with `viewer` on, every connection is read-only
+pub fn serve(
+    address: &str,
+    company: SharedCompany,
+    language: Language,
+    viewer: bool,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    println!(
+        "{}",
+        Msg::Serving(&listener.local_addr()?.to_string()).text(language)
+    );
+    // a connection that falls over before it gets going isn't worth stopping the server for
+    for stream in listener.incoming().flatten() {
+        let company = company.clone();
+        thread::spawn(move || {
+            // a client that hangs up halfway through a reply is gone either way
+            let finished = client(&stream, &company, language, viewer);
+            let saved = storage::save(&company.lock(), Path::new(storage::DEFAULT_PATH));
+            if let Err(e) = saved {
+                eprintln!(
+                    "{}",
+                    Msg::CouldntSave {
+                        path: storage::DEFAULT_PATH,
+                        error: &e
+                    }
+                    .text(language)
+                );
+            }
+            // only once everything's saved, so a goodbye means it's safe to stop the server
+            if let Ok(language) = finished {
+                let _ = writeln!(&stream, "{}", Msg::Goodbye.text(language));
+            }
+        });
+    }
+    Ok(())
+}
+
+// one connection, from hello to Exit (or hanging up). returns whatever language the session ended
+// up in, for saying goodbye
+fn client(
+    stream: &TcpStream,
+    company: &SharedCompany,
+    language: Language,
+    viewer: bool,
+) -> io::Result<Language> {
+    let mut session = Session {
+        language,
+        audit: AuditLog::new(audit::DEFAULT_PATH),
+        viewer,
+        remote: true,
+        ..Session::default()
+    };
+    let mut lines = BufReader::new(stream).lines();
+    let mut out = stream;
+    writeln!(out, "{}", session.say(Msg::Welcome))?;
+
+    while let Some(line) = lines.next() {
+        let line = line?;
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let mut action = execute_shared(&mut session, company, &line);
+        while let Action::Confirm {
+            question, command, ..
+        } = action
+        {
+            action = if ask(&session, &mut lines, &mut out, &question)? {
+                run_shared(&mut session, company, |session| {
+                    run_command(session, command, true)
+                })
+            } else {
+                Action::Print(session.say(Msg::NeverMind))
+            };
+        }
+        match action {
+            Action::Print(msg) | Action::Fail(msg) => writeln!(out, "{}", msg.trim_start())?,
+            Action::Confirm { .. } => unreachable!(),
+            Action::Exit => break,
+        }
+    }
+    Ok(session.language)
+}
+
+// like the interactive `ask`, but over the connection. hanging up counts as a no
+fn ask(
+    session: &Session,
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+    out: &mut impl Write,
+    question: &str,
+) -> io::Result<bool> {
+    loop {
+        writeln!(out, "{} {}", question, session.say(Msg::YesNo))?;
+        let Some(reply) = lines.next() else {
+            return Ok(false);
+        };
+        if let Some(yes) = answer(&reply?) {
+            return Ok(yes);
+        }
+    }
+}
+
+// runs `input` against the shared company. the session works on a copy of it for just the one
+// command, so nobody else can change it halfway through and nobody waits longer than that. the
+// copy only goes back once the command's done, so one that panics leaves the company as it was
+// rather than empty
+fn execute_shared(session: &mut Session, company: &SharedCompany, input: &str) -> Action {
+    run_shared(session, company, |session| execute(session, input))
+}
+
+fn run_shared(
+    session: &mut Session,
+    company: &SharedCompany,
+    f: impl FnOnce(&mut Session) -> Action,
+) -> Action {
+    let mut shared = company.lock();
+    session.company = shared.clone();
+    let action = f(session);
+    *shared = mem::take(&mut session.company);
+    action
+}
@@ -0,0 +1,91 @@
+/*
+ * A Company more than one thread can get at. `SharedCompany` is just an Arc<Mutex<Company>> with a
+ * friendlier face: clone the handle into each thread, then `lock` it to read or change the company.
+ * Every clone points at the same company.
+ */
+
+use crate::Company;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[derive(Debug, Clone, Default)]
+pub struct SharedCompany {
+    inner: Arc<Mutex<Company>>,
+}
+
+impl SharedCompany {
+    pub fn new(company: Company) -> SharedCompany {
+        SharedCompany {
+            inner: Arc::new(Mutex::new(company)),
+        }
+    }
+
+    // waits for any other thread to finish with the company, then hands it over until the guard
+    // is dropped. a thread that panicked halfway through a change can't leave the company in a
+    // broken state (every Company method either finishes or changes nothing), so we carry on
+    // instead of passing the panic along
+    pub fn lock(&self) -> MutexGuard<'_, Company> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // runs `f` with the company locked, for when a single call is all that's needed
+    pub fn with<T>(&self, f: impl FnOnce(&mut Company) -> T) -> T {
+        f(&mut self.lock())
+    }
+
+    // a copy of the company as it is right now, e.g. for saving without holding the lock
+    pub fn snapshot(&self) -> Company {
+        self.lock().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn clones_share_one_company() {
+        let shared = Company::new().shared();
+        let other = shared.clone();
+        other
+            .with(|company| company.add("Sally", "Engineering"))
+            .unwrap();
+        assert!(shared.lock().contains("Sally", "Engineering"));
+    }
+
+    #[test]
+    fn many_writers() {
+        let shared = Company::new().shared();
+        let writers: Vec<_> = (0..8)
+            .map(|t| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        let name = format!("Worker {}-{}", t, i);
+                        shared.with(|c| c.add(name.as_str(), "Factory")).unwrap();
+                        if i % 2 == 0 {
+                            shared
+                                .with(|c| c.add(name.as_str(), "Factory/Night"))
+                                .unwrap();
+                        }
+                    }
+                    // and half of the night shift leaves again, to keep the remove path busy too
+                    for i in (0..100).step_by(4) {
+                        let name = format!("Worker {}-{}", t, i);
+                        shared.with(|c| c.remove(&name, "Factory/Night")).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let company = shared.snapshot();
+        assert_eq!(Ok(800), company.list("Factory").map(|e| e.len()));
+        assert_eq!(Ok(200), company.count("Factory/Night"));
+        assert_eq!(Ok(1000), company.count("Factory"));
+    }
+}
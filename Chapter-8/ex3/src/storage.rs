@@ -0,0 +1,308 @@
+/*
+ * Saving and loading the company. The file looks just like `List all` output, minus the indent and the headcounts:
+ *
+ *     [Engineering]
+ *     Amir
+ *     Sally | Senior Engineer | 2023-01-15
+ *     [Sales]
+ *     Bob | | 2021-06-01
+ *     [Sales/EMEA]
+ *     Amir
+ *
+ * Sub-departments get their own section under their full path, and a department with a capacity
+ * says so after its header, as in `[Engineering] capacity 10`. Title and start date come after
+ * the name, split up by `|`, and can be left out (or empty) when we don't know them. Blank lines
+ * are skipped, so hand-edited files don't need to be tidy.
+ *
+ * Names, titles and departments are written with a `\` in front of any `|`, `[`, `]` or `\` in
+ * them, and with newlines as `\n`, so `[Boss]` the person doesn't come back as a department.
+ */
+
+use crate::{Company, Employee};
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_PATH: &str = "company.txt";
+
+pub fn to_text(company: &Company) -> String {
+    let mut text = String::new();
+    // every department with people or a capacity in it. the rest come back on their own
+    let mut depts = Vec::new();
+    company.root.walk(&mut depts);
+    for dept in depts {
+        if dept.employees().is_empty() && dept.capacity().is_none() {
+            continue;
+        }
+        match dept.capacity() {
+            Some(capacity) => text.push_str(&format!(
+                "[{}] capacity {}\n",
+                escape(dept.path()),
+                capacity
+            )),
+            None => text.push_str(&format!("[{}]\n", escape(dept.path()))),
+        }
+        for employee in dept.employees() {
+            text.push_str(&employee_line(employee));
+            text.push('\n');
+        }
+    }
+    text
+}
+
+fn employee_line(employee: &Employee) -> String {
+    let name = escape(&employee.name);
+    match (&employee.title, &employee.start) {
+        (Some(title), Some(start)) => format!("{} | {} | {}", name, escape(title), start),
+        (None, Some(start)) => format!("{} | | {}", name, start),
+        (Some(title), None) => format!("{} | {}", name, escape(title)),
+        (None, None) => name,
+    }
+}
+
+fn escape(field: &str) -> String {
+    let mut escaped = String::new();
+    for c in field.chars() {
+        match c {
+            '\\' | '|' | '[' | ']' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// undoes `escape` on `text` up to the first `stop` that wasn't escaped. returns what came before
+// it, and what's left after it if there was one
+fn unescape_until(text: &str, stop: char) -> (String, Option<&str>) {
+    let mut unescaped = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, 'n')) => unescaped.push('\n'),
+                Some((_, 'r')) => unescaped.push('\r'),
+                Some((_, c)) => unescaped.push(c),
+                None => unescaped.push('\\'),
+            },
+            c if c == stop => return (unescaped, Some(&text[i + c.len_utf8()..])),
+            c => unescaped.push(c),
+        }
+    }
+    (unescaped, None)
+}
+
+fn parse_employee(line: &str) -> Result<Employee, String> {
+    let mut unescaped = Vec::new();
+    let mut rest = Some(line);
+    while let Some(text) = rest {
+        let (field, after) = unescape_until(text, '|');
+        unescaped.push(field);
+        rest = after;
+    }
+    let fields: Vec<&str> = unescaped.iter().map(|f| f.trim()).collect();
+    let mut employee = Employee::new(fields[0]);
+    match fields.as_slice() {
+        [_] => {}
+        [_, title] | [_, title, ""] => {
+            employee.title = Some(title.to_string()).filter(|t| !t.is_empty());
+        }
+        [_, title, start] => {
+            employee.title = Some(title.to_string()).filter(|t| !t.is_empty());
+            employee.start = Some(start.parse()?);
+        }
+        _ => return Err(format!("'{}' has too many fields", line)),
+    }
+    Ok(employee)
+}
+
+pub fn from_text(text: &str) -> Result<Company, String> {
+    let mut company = Company::new();
+    let mut current: Option<String> = None;
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let header = line.strip_prefix('[').map(|l| unescape_until(l, ']'));
+        if let Some((dept, Some(rest))) = header {
+            match rest.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [] => {}
+                ["capacity", capacity] => {
+                    let capacity = capacity
+                        .parse()
+                        .map_err(|_| format!("line {}: '{}' isn't a capacity", i + 1, capacity))?;
+                    company.root.entry(&dept).set_capacity(Some(capacity));
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: '{}' after [{}] doesn't make sense",
+                        i + 1,
+                        rest.trim(),
+                        dept
+                    ))
+                }
+            }
+            current = Some(dept);
+        } else {
+            match &current {
+                Some(dept) => {
+                    let employee =
+                        parse_employee(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+                    company.insert(employee, dept);
+                }
+                None => {
+                    return Err(format!(
+                        "line {}: '{}' isn't under a department",
+                        i + 1,
+                        line
+                    ))
+                }
+            }
+        }
+    }
+
+    // a department header with nobody under it (and no capacity) never gets added, so it just
+    // disappears
+    Ok(company)
+}
+
+pub fn save(company: &Company, path: &Path) -> io::Result<()> {
+    fs::write(path, to_text(company))
+}
+
+pub fn load(path: &Path) -> io::Result<Company> {
+    let text = fs::read_to_string(path)?;
+    from_text(&text).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+// moves a company file that couldn't be loaded out of the way, so it's still there to fix by hand
+// and the next save doesn't write over it. returns where it went
+pub fn recover(path: &Path) -> io::Result<PathBuf> {
+    let mut bad = path.as_os_str().to_owned();
+    bad.push(".bad");
+    let bad = PathBuf::from(bad);
+    fs::rename(path, &bad)?;
+    Ok(bad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut company = Company::new();
+        company.insert("Sally", "Engineering");
+        company.insert("Amir", "Engineering");
+        company.insert("Bob", "Sales");
+
+        let text = to_text(&company);
+        assert_eq!("[Engineering]\nAmir\nSally\n[Sales]\nBob\n", text);
+        assert_eq!(Ok(company), from_text(&text));
+    }
+
+    #[test]
+    fn round_trip_with_details() {
+        let mut company = Company::new();
+        let sally = Employee::new("Sally")
+            .with_title("Senior Engineer")
+            .with_start("2023-01-15".parse().unwrap());
+        company.insert(sally, "Engineering");
+        company.insert(Employee::new("Amir").with_title("Intern"), "Engineering");
+        company.insert(
+            Employee::new("Bob").with_start("2021-06-01".parse().unwrap()),
+            "Sales",
+        );
+
+        let text = to_text(&company);
+        assert_eq!(
+            "[Engineering]\nAmir | Intern\nSally | Senior Engineer | 2023-01-15\n[Sales]\nBob | | 2021-06-01\n",
+            text
+        );
+        assert_eq!(Ok(company), from_text(&text));
+    }
+
+    #[test]
+    fn round_trip_sub_departments() {
+        let mut company = Company::new();
+        company.insert("Bob", "Sales");
+        company.insert("Amir", "Sales/EMEA");
+        company.insert("Cleo", "Marketing/Brand");
+
+        let text = to_text(&company);
+        assert_eq!(
+            "[Marketing/Brand]\nCleo\n[Sales]\nBob\n[Sales/EMEA]\nAmir\n",
+            text
+        );
+        assert_eq!(Ok(company), from_text(&text));
+    }
+
+    #[test]
+    fn round_trip_capacity() {
+        let mut company = Company::new();
+        company.insert("Sally", "Engineering");
+        company.set_capacity("Engineering", Some(10)).unwrap();
+        company.insert("Bob", "Sales/EMEA");
+        company.set_capacity("Sales", Some(3)).unwrap();
+
+        let text = to_text(&company);
+        assert_eq!(
+            "[Engineering] capacity 10\nSally\n[Sales] capacity 3\n[Sales/EMEA]\nBob\n",
+            text
+        );
+        assert_eq!(Ok(company), from_text(&text));
+        assert!(from_text("[Sales] capacity lots\n").is_err());
+    }
+
+    #[test]
+    fn round_trip_special_characters() {
+        let mut company = Company::new();
+        company.insert(Employee::new("a|b|c|d"), "Engineering");
+        company.insert(
+            Employee::new("[Boss]").with_title("Head | Chief"),
+            "Engineering",
+        );
+        company.insert(Employee::new("two\nlines\\"), "R[&]D");
+
+        let text = to_text(&company);
+        assert_eq!(
+            "[Engineering]\n\\[Boss\\] | Head \\| Chief\na\\|b\\|c\\|d\n[R\\[&\\]D]\ntwo\\nlines\\\\\n",
+            text
+        );
+        assert_eq!(Ok(company), from_text(&text));
+    }
+
+    #[test]
+    fn bad_start_date() {
+        assert!(from_text("[Sales]\nBob | | 2021-13-01\n").is_err());
+    }
+
+    #[test]
+    fn name_without_department() {
+        assert!(from_text("Bob\n[Sales]\n").is_err());
+    }
+
+    #[test]
+    fn recovers_from_a_corrupt_file() {
+        let dir = std::env::temp_dir().join(format!("admincli-storage-{}", std::process::id()));
+        let path = dir.join("company.txt");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "Bob\n[Sales]\n").unwrap();
+        assert_eq!(ErrorKind::InvalidData, load(&path).unwrap_err().kind());
+
+        let bad = recover(&path).unwrap();
+        assert_eq!(dir.join("company.txt.bad"), bad);
+        assert!(!path.exists());
+        assert_eq!("Bob\n[Sales]\n", fs::read_to_string(&bad).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
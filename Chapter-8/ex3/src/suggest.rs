@@ -0,0 +1,57 @@
+/*
+ * "Did you mean ...?" help for typos. `levenshtein` counts how many single-character inserts,
+ * deletes, or swaps it takes to turn one word into another, and `closest` uses it to pick the
+ * candidate a mistyped word was most likely meant to be.
+ */
+
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // only the previous row of the usual table is needed to fill in the next one
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let swap = prev[j] + usize::from(ca != cb);
+            row[j + 1] = swap.min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut row);
+    }
+    prev[b.len()]
+}
+
+// the candidate nearest to `word` (ignoring case), as long as it's close enough to be a typo
+// rather than a different word entirely: about one mistake for every three letters, rounded up
+pub fn closest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let word = word.to_lowercase();
+    let limit = word.chars().count().div_ceil(3);
+    candidates
+        .into_iter()
+        .map(|c| (levenshtein(&word, &c.to_lowercase()), c))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        assert_eq!(0, levenshtein("Sales", "Sales"));
+        assert_eq!(1, levenshtein("Enginering", "Engineering"));
+        assert_eq!(3, levenshtein("kitten", "sitting"));
+        assert_eq!(4, levenshtein("", "list"));
+    }
+
+    #[test]
+    fn closest_candidate() {
+        let depts = ["Engineering", "Sales", "Marketing"];
+        assert_eq!(Some("Engineering"), closest("enginering", depts));
+        assert_eq!(Some("Sales"), closest("sale", depts));
+        assert_eq!(None, closest("Legal", depts));
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+// a fresh directory per test so company.txt files don't leak between them
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("admincli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run_piped(dir: &PathBuf, commands: &str) -> Output {
+    run_piped_in(dir, commands, "C")
+}
+
+// `locale` ends up in LC_ALL, which beats whatever language the machine running the tests uses
+fn run_piped_in(dir: &PathBuf, commands: &str, locale: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ex3"))
+        .current_dir(dir)
+        .env("LC_ALL", locale)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(commands.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn piped_commands() {
+    let dir = scratch_dir("piped");
+    let output = run_piped(
+        &dir,
+        "# hire some people\nAdd Sally to Engineering\n\nAdd Amir to Engineering\nList Engineering\n",
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        "added Sally to Engineering\nadded Amir to Engineering\n[Engineering] (2)\n    Amir\n    Sally\n",
+        stdout
+    );
+    assert!(!stdout.contains("Welcome"));
+    assert_eq!(
+        "[Engineering]\nAmir\nSally\n",
+        fs::read_to_string(dir.join("company.txt")).unwrap()
+    );
+}
+
+#[test]
+fn failed_command_sets_exit_status() {
+    let dir = scratch_dir("failed");
+    let output = run_piped(
+        &dir,
+        "Add Sally to Engineering\nRemove Bob from Engineering\n",
+    );
+
+    assert_eq!(Some(1), output.status.code());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!("line 2: 'Bob' not found in Engineering\n", stderr);
+}
+
+#[test]
+fn script_file_argument() {
+    let dir = scratch_dir("script");
+    fs::write(dir.join("commands.txt"), "Add Bob to Sales\nCount Sales\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_ex3"))
+        .current_dir(&dir)
+        .env("LC_ALL", "C")
+        .arg("commands.txt")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        "added Bob to Sales\nSales has 1 employee(s)\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
+#[test]
+fn bad_arguments() {
+    let dir = scratch_dir("arguments");
+    for args in [
+        &["commands.txt", "extra"][..],
+        &["--serve", "127.0.0.1:9000", "extra"][..],
+        &["--veiwer"][..],
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_ex3"))
+            .current_dir(&dir)
+            .env("LC_ALL", "C")
+            .args(args)
+            .output()
+            .unwrap();
+
+        assert_eq!(Some(2), output.status.code(), "{:?}", args);
+        assert_eq!(
+            "usage: ex3 [--viewer] [script | --serve [address]]\n",
+            String::from_utf8(output.stderr).unwrap()
+        );
+    }
+}
+
+#[test]
+fn export_tree_and_table() {
+    let dir = scratch_dir("export");
+    let output = run_piped(
+        &dir,
+        "Add Bob to Sales\nAdd Amir to Sales/EMEA as Rep\nExport tree org.txt\nExport table org-table.txt\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "[Sales] (2)\n    Bob\n    [EMEA] (1)\n        Amir  Rep  -\n",
+        fs::read_to_string(dir.join("org.txt")).unwrap()
+    );
+    assert_eq!(
+        "Department | Name | Title | Start\n-----------+------+-------+------\nSales      | Bob  | -     | -\nSales/EMEA | Amir | Rep   | -\n",
+        fs::read_to_string(dir.join("org-table.txt")).unwrap()
+    );
+}
+
+#[test]
+fn spanish_from_the_environment() {
+    let dir = scratch_dir("spanish");
+    let output = run_piped_in(
+        &dir,
+        "Add Bob to Sales\nRemove Zed from Sales\nSet language en\nCount Sales\n",
+        "es_ES.UTF-8",
+    );
+
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "Bob añadido a Sales\nlanguage set to English\nSales has 1 employee(s)\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+    assert_eq!(
+        "línea 2: 'Zed' no está en Sales\n",
+        String::from_utf8(output.stderr).unwrap()
+    );
+}
+
+#[test]
+fn corrupt_company_file_is_moved_aside() {
+    let dir = scratch_dir("corrupt");
+    fs::write(dir.join("company.txt"), "Bob\n[Sales]\n").unwrap();
+    let output = run_piped(&dir, "Add Sally to Engineering\n");
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("company.txt.bad"), "{}", stderr);
+    assert_eq!(
+        "Bob\n[Sales]\n",
+        fs::read_to_string(dir.join("company.txt.bad")).unwrap()
+    );
+    assert_eq!(
+        "[Engineering]\nSally\n",
+        fs::read_to_string(dir.join("company.txt")).unwrap()
+    );
+}
@@ -0,0 +1,126 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Lines, Write},
+    net::TcpStream,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+};
+
+// starts `ex3 --serve` on a port the OS picks, and returns it along with the address it ended up on
+fn start_server(name: &str) -> (Child, String, PathBuf) {
+    let dir = env::temp_dir().join(format!("admincli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let mut server = Command::new(env!("CARGO_BIN_EXE_ex3"))
+        .args(["--serve", "127.0.0.1:0"])
+        .current_dir(&dir)
+        .env("LC_ALL", "C")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut banner = String::new();
+    BufReader::new(server.stdout.take().unwrap())
+        .read_line(&mut banner)
+        .unwrap();
+    let address = banner.trim().rsplit(' ').next().unwrap().to_string();
+    (server, address, dir)
+}
+
+struct Client {
+    lines: Lines<BufReader<TcpStream>>,
+    out: TcpStream,
+}
+
+impl Client {
+    fn connect(address: &str) -> Client {
+        let stream = TcpStream::connect(address).unwrap();
+        let mut lines = BufReader::new(stream.try_clone().unwrap()).lines();
+        // the welcome
+        lines.next().unwrap().unwrap();
+        Client { lines, out: stream }
+    }
+
+    // sends `command` and reads back the first `n` lines of the reply
+    fn send(&mut self, command: &str, n: usize) -> Vec<String> {
+        writeln!(self.out, "{}", command).unwrap();
+        (0..n)
+            .map(|_| self.lines.next().unwrap().unwrap())
+            .collect()
+    }
+}
+
+#[test]
+fn clients_share_one_company() {
+    let (mut server, address, dir) = start_server("serve");
+    let mut sally = Client::connect(&address);
+    let mut amir = Client::connect(&address);
+
+    assert_eq!(
+        vec!["added Sally to Engineering"],
+        sally.send("Add Sally to Engineering", 1)
+    );
+    assert_eq!(
+        vec!["added Amir to Engineering"],
+        amir.send("Add Amir to Engineering", 1)
+    );
+    assert_eq!(
+        vec!["[Engineering] (2)", "    Amir", "    Sally"],
+        sally.send("List Engineering", 3)
+    );
+
+    // each connection has its own history
+    assert_eq!(vec!["nothing to redo"], sally.send("Redo", 1));
+    assert_eq!(
+        vec!["undid 'Add Amir to Engineering'"],
+        amir.send("Undo", 1)
+    );
+
+    // questions get asked over the connection too
+    assert_eq!(
+        vec!["This removes Sally from Engineering. Are you sure? (y/n)"],
+        amir.send("Remove Sally from Engineering", 1)
+    );
+    assert_eq!(vec!["never mind then"], amir.send("n", 1));
+    assert_eq!(
+        vec!["AdminCLI stopped ... Have a nice day"],
+        amir.send("Exit", 1)
+    );
+
+    assert_eq!(
+        vec!["[Engineering] (1)", "    Sally"],
+        sally.send("List all", 2)
+    );
+    server.kill().unwrap();
+    server.wait().unwrap();
+    assert_eq!(
+        "[Engineering]\nSally\n",
+        fs::read_to_string(dir.join("company.txt")).unwrap()
+    );
+}
+
+#[test]
+fn files_stay_in_the_server_directory() {
+    let (mut server, address, dir) = start_server("serve-files");
+    let mut client = Client::connect(&address);
+    client.send("Add Sally to Engineering", 1);
+
+    for command in [
+        "Save /tmp/company.txt",
+        "Save ../company.txt",
+        "Load /etc/passwd",
+        "Export tree sub/org.txt",
+    ] {
+        let reply = client.send(command, 1);
+        assert!(reply[0].contains("isn't a plain file name"), "{:?}", reply);
+    }
+    assert_eq!(
+        vec!["saved company to backup.txt"],
+        client.send("Save backup.txt", 1)
+    );
+    server.kill().unwrap();
+    server.wait().unwrap();
+    assert_eq!(
+        "[Engineering]\nSally\n",
+        fs::read_to_string(dir.join("backup.txt")).unwrap()
+    );
+}
@@ -0,0 +1,104 @@
+use std::{cmp::Ordering, error::Error, fmt};
+
+// a value that's somewhere from `min` to `max`, with the bounds picked at runtime. anything that
+// can be put in order works, floats included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<T> {
+    value: T,
+    min: T,
+    max: T,
+}
+
+// why a value couldn't be bounded, with the value and the bound it's on the wrong side of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundsError<T> {
+    TooSmall { value: T, min: T },
+    TooLarge { value: T, max: T },
+    // it isn't more or less than the bounds, like a NaN
+    Unordered(T),
+}
+
+impl<T: fmt::Display> fmt::Display for BoundsError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoundsError::TooSmall { value, min } => {
+                write!(
+                    f,
+                    "value must be greater than or equal to {min}, got {value}"
+                )
+            }
+            BoundsError::TooLarge { value, max } => {
+                write!(f, "value must be less than or equal to {max}, got {value}")
+            }
+            BoundsError::Unordered(value) => write!(f, "{value} can't be compared with anything"),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> Error for BoundsError<T> {}
+
+impl<T: PartialOrd + Copy> Bounded<T> {
+    // with `min` over `max` there's no value in between, and this is always an error
+    pub fn new(value: T, min: T, max: T) -> Result<Bounded<T>, BoundsError<T>> {
+        match (value.partial_cmp(&min), value.partial_cmp(&max)) {
+            (None, _) | (_, None) => Err(BoundsError::Unordered(value)),
+            (Some(Ordering::Less), _) => Err(BoundsError::TooSmall { value, min }),
+            (_, Some(Ordering::Greater)) => Err(BoundsError::TooLarge { value, max }),
+            _ => Ok(Bounded { value, min, max }),
+        }
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    // the lowest and highest it could be. not `min` and `max`, which `Ord` already has
+    pub fn bounds(&self) -> (T, T) {
+        (self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        let bounded = Bounded::new(5, 1, 6).unwrap();
+        assert_eq!((5, (1, 6)), (bounded.value(), bounded.bounds()));
+        assert_eq!(
+            Err(BoundsError::TooLarge { value: 7, max: 6 }),
+            Bounded::new(7, 1, 6)
+        );
+        assert_eq!(
+            Err(BoundsError::TooSmall { value: 0, min: 1 }),
+            Bounded::new(0, 1, 6)
+        );
+        // nothing fits between bounds the wrong way round
+        assert!(Bounded::new(3, 6, 1).is_err());
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(0.5, Bounded::new(0.5, 0.0, 1.0).unwrap().value());
+        assert_eq!(
+            Err(BoundsError::TooLarge {
+                value: 1.5,
+                max: 1.0
+            }),
+            Bounded::new(1.5, 0.0, 1.0)
+        );
+        assert!(matches!(
+            Bounded::new(f64::NAN, 0.0, 1.0),
+            Err(BoundsError::Unordered(_))
+        ));
+        assert_eq!(
+            "value must be less than or equal to 1, got 1.5",
+            BoundsError::TooLarge {
+                value: 1.5,
+                max: 1.0
+            }
+            .to_string()
+        );
+    }
+}
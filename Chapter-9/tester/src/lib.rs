@@ -0,0 +1,301 @@
+pub mod bounded;
+
+pub use bounded::{Bounded, BoundsError};
+use std::{cmp::Ordering, error::Error, fmt, str::FromStr};
+
+// a number from MIN to MAX, checked once when it's made so nothing else has to. a `Bounded` with
+// its bounds fixed in the type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ranged<const MIN: i32, const MAX: i32> {
+    bounded: Bounded<i32>,
+}
+
+// the guessing game's numbers
+pub type Guess = Ranged<1, 100>;
+pub type DiceRoll = Ranged<1, 6>;
+pub type Percentage = Ranged<0, 100>;
+
+// why a value couldn't be in range, with the value and the bound it's on the wrong side of. or,
+// when parsing, the text that wasn't a number at all
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    TooSmall { value: i32, min: i32 },
+    TooLarge { value: i32, max: i32 },
+    NotANumber(String),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::TooSmall { value, min } => {
+                write!(
+                    f,
+                    "Guess value must be greater than or equal to {min}, got {value}"
+                )
+            }
+            GuessError::TooLarge { value, max } => {
+                write!(
+                    f,
+                    "Guess value must be less than or equal to {max}, got {value}"
+                )
+            }
+            GuessError::NotANumber(s) => write!(f, "'{s}' isn't a number"),
+        }
+    }
+}
+
+impl Error for GuessError {}
+
+impl From<BoundsError<i32>> for GuessError {
+    fn from(e: BoundsError<i32>) -> GuessError {
+        match e {
+            BoundsError::TooSmall { value, min } => GuessError::TooSmall { value, min },
+            BoundsError::TooLarge { value, max } => GuessError::TooLarge { value, max },
+            BoundsError::Unordered(_) => unreachable!("an i32 always compares with another"),
+        }
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> Ranged<MIN, MAX> {
+    // stops a range like Ranged<6, 1> from compiling, rather than every value being out of it
+    const NOT_EMPTY: () = assert!(MIN <= MAX, "the range is empty");
+
+    // for values that are already known to be fine. panics if they aren't
+    pub fn new(val: i32) -> Ranged<MIN, MAX> {
+        match Ranged::try_new(val) {
+            Ok(ranged) => ranged,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    // for anything else, like whatever a user typed
+    pub fn try_new(val: i32) -> Result<Ranged<MIN, MAX>, GuessError> {
+        let () = Self::NOT_EMPTY;
+        let bounded = Bounded::new(val, MIN, MAX)?;
+        Ok(Ranged { bounded })
+    }
+
+    // for a value that's already been brought into the range
+    fn within(val: i32) -> Ranged<MIN, MAX> {
+        let () = Self::NOT_EMPTY;
+        let bounded = Bounded::new(val, MIN, MAX).expect("the value was put in the range");
+        Ranged { bounded }
+    }
+
+    // the nearest value in the range: anything under it is MIN, and anything over it is MAX
+    pub fn clamped(val: i32) -> Ranged<MIN, MAX> {
+        Ranged::within(val.clamp(MIN, MAX))
+    }
+
+    // counts around the range as many times as it takes, like the hours on a clock: one past MAX
+    // is MIN, and one under MIN is MAX
+    pub fn wrapping(val: i32) -> Ranged<MIN, MAX> {
+        // in i64, since the range can be wider than an i32 can hold
+        let size = MAX as i64 - MIN as i64 + 1;
+        let value = (val as i64 - MIN as i64).rem_euclid(size) + MIN as i64;
+        Ranged::within(value as i32)
+    }
+
+    pub fn value(&self) -> i32 {
+        self.bounded.value()
+    }
+
+    // `by` further on, as long as that's still in the range
+    pub fn checked_add(self, by: i32) -> Option<Ranged<MIN, MAX>> {
+        self.value()
+            .checked_add(by)
+            .and_then(|val| Ranged::try_new(val).ok())
+    }
+
+    // `by` further back, as long as that's still in the range
+    pub fn checked_sub(self, by: i32) -> Option<Ranged<MIN, MAX>> {
+        self.value()
+            .checked_sub(by)
+            .and_then(|val| Ranged::try_new(val).ok())
+    }
+
+    // moved by `by` either way, or which end of the range it went past and where it ended up. past
+    // the end of an i32 counts as the end of an i32, which is past the end of the range anyway
+    pub fn offset(self, by: i32) -> Result<Ranged<MIN, MAX>, GuessError> {
+        Ranged::try_new(self.value().saturating_add(by))
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> From<Ranged<MIN, MAX>> for Bounded<i32> {
+    fn from(ranged: Ranged<MIN, MAX>) -> Bounded<i32> {
+        ranged.bounded
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> TryFrom<i32> for Ranged<MIN, MAX> {
+    type Error = GuessError;
+
+    fn try_from(val: i32) -> Result<Ranged<MIN, MAX>, GuessError> {
+        Ranged::try_new(val)
+    }
+}
+
+// so `"42".parse::<Guess>()` takes typed input straight to a checked value
+impl<const MIN: i32, const MAX: i32> FromStr for Ranged<MIN, MAX> {
+    type Err = GuessError;
+
+    fn from_str(s: &str) -> Result<Ranged<MIN, MAX>, GuessError> {
+        let s = s.trim();
+        match s.parse() {
+            Ok(val) => Ranged::try_new(val),
+            Err(_) => Err(GuessError::NotANumber(s.to_string())),
+        }
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> fmt::Display for Ranged<MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value().fmt(f)
+    }
+}
+
+// comparing with a plain number, without taking the value out first
+impl<const MIN: i32, const MAX: i32> PartialEq<i32> for Ranged<MIN, MAX> {
+    fn eq(&self, other: &i32) -> bool {
+        self.value() == *other
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> PartialOrd<i32> for Ranged<MIN, MAX> {
+    fn partial_cmp(&self, other: &i32) -> Option<Ordering> {
+        self.value().partial_cmp(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_range() {
+        assert_eq!(1, Guess::try_new(1).unwrap().value());
+        assert_eq!(100, Guess::new(100).value());
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(
+            Some(GuessError::TooSmall { value: 0, min: 1 }),
+            Guess::try_new(0).err()
+        );
+        assert_eq!(
+            Some(GuessError::TooLarge {
+                value: 250,
+                max: 100
+            }),
+            Guess::try_new(250).err()
+        );
+        assert_eq!(
+            "Guess value must be less than or equal to 100, got 250",
+            Guess::try_new(250).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "less than or equal to 100")]
+    fn greater_than_100() {
+        Guess::new(200);
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Ok(Guess::new(42)), "42".parse());
+        assert_eq!(Ok(Guess::new(42)), " 42\n".parse());
+        assert_eq!(Ok(Guess::new(7)), Guess::try_from(7));
+        let too_big: Result<DiceRoll, _> = 7.try_into();
+        assert!(too_big.is_err());
+        assert_eq!(
+            Err(GuessError::NotANumber("forty".to_string())),
+            "forty".parse::<Guess>()
+        );
+        assert_eq!(
+            Err(GuessError::TooLarge {
+                value: 250,
+                max: 100
+            }),
+            "250".parse::<Guess>()
+        );
+        assert_eq!(
+            "'forty' isn't a number",
+            GuessError::NotANumber("forty".to_string()).to_string()
+        );
+        assert_eq!("42", Guess::new(42).to_string());
+        assert_eq!("  42", format!("{:>4}", Guess::new(42)));
+    }
+
+    #[test]
+    fn comparisons() {
+        let guess = Guess::new(42);
+        assert!(guess == 42 && guess != 43);
+        assert!(guess < 50 && guess > 1);
+        assert!(Guess::new(1) < guess);
+        assert_eq!(
+            Some(Guess::new(99)),
+            [3, 99, 42].map(Guess::new).into_iter().max()
+        );
+    }
+
+    #[test]
+    fn coerced() {
+        assert_eq!(1, Guess::clamped(-20).value());
+        assert_eq!(42, Guess::clamped(42).value());
+        assert_eq!(100, Guess::clamped(250).value());
+        assert_eq!(1, Guess::wrapping(101).value());
+        assert_eq!(100, Guess::wrapping(0).value());
+        assert_eq!(50, Guess::wrapping(250).value());
+        assert_eq!(6, DiceRoll::wrapping(-6).value());
+        assert_eq!(1, DiceRoll::wrapping(7).value());
+        // every i32 is already in range
+        assert_eq!(
+            -1,
+            Ranged::<{ i32::MIN }, { i32::MAX }>::wrapping(-1).value()
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let guess = Guess::new(42);
+        assert_eq!(Some(Guess::new(50)), guess.checked_add(8));
+        assert_eq!(Some(Guess::new(100)), guess.checked_add(58));
+        assert_eq!(None, guess.checked_add(59));
+        assert_eq!(Some(Guess::new(1)), guess.checked_sub(41));
+        assert_eq!(None, guess.checked_sub(42));
+        assert_eq!(None, guess.checked_add(i32::MAX));
+        assert_eq!(None, guess.checked_sub(i32::MIN));
+        assert_eq!(Ok(Guess::new(32)), guess.offset(-10));
+        assert_eq!(
+            Err(GuessError::TooSmall { value: -8, min: 1 }),
+            guess.offset(-50)
+        );
+        assert_eq!(
+            Err(GuessError::TooLarge {
+                value: i32::MAX,
+                max: 100
+            }),
+            guess.offset(i32::MAX)
+        );
+    }
+
+    #[test]
+    fn as_bounded() {
+        let bounded: Bounded<i32> = Guess::new(42).into();
+        assert_eq!((42, (1, 100)), (bounded.value(), bounded.bounds()));
+    }
+
+    #[test]
+    fn other_ranges() {
+        assert!(DiceRoll::try_new(6).is_ok());
+        assert_eq!(
+            Some(GuessError::TooLarge { value: 7, max: 6 }),
+            DiceRoll::try_new(7).err()
+        );
+        assert_eq!(0, Percentage::new(0).value());
+        assert!(Percentage::try_new(-1).is_err());
+        assert_eq!(-5, Ranged::<-10, -1>::new(-5).value());
+    }
+}
@@ -1,18 +1,10 @@
-pub struct Guess {
-    value: i32,
-}
+use tester::Guess;
 
-impl Guess {
-    pub fn new(val: i32) -> Guess {
-        if val < 1 || val > 100 {
-            panic!("Your guess must be between 1 and 100, got {}", val);
+fn main() {
+    for input in ["42", "250", "forty"] {
+        match input.parse::<Guess>() {
+            Ok(guess) => println!("guessed {guess}"),
+            Err(e) => println!("{e}"),
         }
-        Guess { value: val }
-    }
-
-    pub fn value(&self) -> i32 {
-        self.value
     }
 }
-
-fn main() {}